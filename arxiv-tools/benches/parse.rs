@@ -0,0 +1,186 @@
+//! Parsing of a page of 2,000 entries, the largest page the API returns.
+//!
+//! The whole feed deserialized at once into owned strings, as `parse_atom` did before entries were read one at a
+//! time, is kept here as the baseline. The allocations of a parse of every path are counted and printed before it is
+//! measured.
+#![allow(clippy::needless_return)]
+use arxiv_tools::atom::FeedReader;
+use arxiv_tools::{parse_atom, Whitespace};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use serde::Deserialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The system allocator, counting allocations.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        return System.alloc(layout);
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        return System.realloc(ptr, layout, new_size);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Number of allocations made by `f`.
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    return ALLOCATIONS.load(Ordering::Relaxed) - before;
+}
+
+/// The feed model deserialized at once, with owned strings, before [`FeedReader`].
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+#[allow(dead_code)]
+struct OwnedFeed {
+    title: String,
+    id: String,
+    updated: String,
+    #[serde(rename = "link")]
+    links: Vec<OwnedLink>,
+    #[serde(rename = "totalResults")]
+    total_results: Option<u64>,
+    #[serde(rename = "startIndex")]
+    start_index: Option<u64>,
+    #[serde(rename = "itemsPerPage")]
+    items_per_page: Option<u64>,
+    #[serde(rename = "entry")]
+    entries: Vec<OwnedEntry>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+#[allow(dead_code)]
+struct OwnedEntry {
+    id: String,
+    title: String,
+    summary: String,
+    published: String,
+    updated: String,
+    #[serde(rename = "author")]
+    authors: Vec<OwnedAuthor>,
+    #[serde(rename = "comment")]
+    comments: Vec<String>,
+    journal_ref: Option<String>,
+    doi: Option<String>,
+    #[serde(rename = "link")]
+    links: Vec<OwnedLink>,
+    primary_category: Option<OwnedTerm>,
+    #[serde(rename = "category")]
+    categories: Vec<OwnedTerm>,
+    msc_class: Option<String>,
+    acm_class: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+#[allow(dead_code)]
+struct OwnedAuthor {
+    name: String,
+    #[serde(rename = "affiliation")]
+    affiliations: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+#[allow(dead_code)]
+struct OwnedLink {
+    #[serde(rename = "@href")]
+    href: String,
+    #[serde(rename = "@rel")]
+    rel: Option<String>,
+    #[serde(rename = "@title")]
+    title: Option<String>,
+    #[serde(rename = "@type")]
+    mime: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+#[allow(dead_code)]
+struct OwnedTerm {
+    #[serde(rename = "@term")]
+    term: String,
+}
+
+fn owned_feed(xml: &str) -> OwnedFeed {
+    return quick_xml::de::from_str(xml).unwrap();
+}
+
+fn feed_reader(xml: &str) -> usize {
+    let mut reader = FeedReader::new(Whitespace::default());
+    let mut papers = 0;
+    for chunk in xml.as_bytes().chunks(16 * 1024) {
+        papers += reader.push(chunk).unwrap().len();
+    }
+    reader.finish().unwrap();
+    return papers;
+}
+
+/// A feed of `entries` entries, repeating the entries of the recorded feeds.
+fn feed(entries: usize) -> String {
+    let recorded = [
+        include_str!("../fixtures/feed.xml"),
+        include_str!("../fixtures/rich.xml"),
+    ]
+    .map(|xml| {
+        let start = xml.find("<entry>").unwrap();
+        let end = xml.rfind("</entry>").unwrap() + "</entry>".len();
+        return (
+            &xml[..start],
+            xml[start..end]
+                .split_inclusive("</entry>")
+                .collect::<Vec<_>>(),
+        );
+    });
+    let (header, _) = recorded[0];
+    let pool = recorded
+        .iter()
+        .flat_map(|(_, entries)| entries.iter().copied())
+        .collect::<Vec<&str>>();
+    let mut xml = String::from(header);
+    for i in 0..entries {
+        xml.push_str(pool[i % pool.len()]);
+    }
+    xml.push_str("\n</feed>\n");
+    return xml;
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let xml = feed(2000);
+    let mut group = c.benchmark_group("parse 2000 entries");
+    group.throughput(Throughput::Bytes(xml.len() as u64));
+    println!(
+        "allocations: owned feed {}, parse_atom {}, FeedReader {}",
+        allocations(|| owned_feed(&xml)),
+        allocations(|| parse_atom(&xml).unwrap()),
+        allocations(|| feed_reader(&xml))
+    );
+    group.bench_function("owned feed (baseline)", |b| {
+        b.iter(|| owned_feed(black_box(&xml)));
+    });
+    group.bench_function("parse_atom", |b| {
+        b.iter(|| parse_atom(black_box(&xml)).unwrap());
+    });
+    group.bench_function("FeedReader, 16 KiB chunks", |b| {
+        b.iter(|| feed_reader(black_box(&xml)));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
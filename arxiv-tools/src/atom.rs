@@ -0,0 +1,558 @@
+//! # Atom
+//! Typed model of the Atom feed returned by the API, deserialized with quick-xml's serde support and mapped to
+//! [`SearchResults`].
+//!
+//! Elements are matched by their local name, so a feed binding the Atom, OpenSearch and arXiv namespaces to other
+//! prefixes than the API does, e.g. `<ax:comment>` instead of `<arxiv:comment>`, parses the same. Elements the model
+//! doesn't know are skipped.
+//!
+//! [`parse_atom`] parses responses that were not fetched by this crate, e.g. saved to disk or pulled from a cache:
+//! ```rust
+//! # use arxiv_tools::parse_atom;
+//! let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+//!   <entry>
+//!     <id>http://arxiv.org/abs/1706.03762v7</id>
+//!     <title>Attention Is All You Need</title>
+//!     <author><name>Ashish Vaswani</name></author>
+//!   </entry>
+//! </feed>"#;
+//!
+//! let results = parse_atom(xml).unwrap();
+//! assert_eq!(results.papers[0].title, "Attention Is All You Need");
+//! ```
+use crate::intern::{intern, Interner};
+use crate::{ArxivError, Author, Link, Paper, SearchResults};
+use chrono::{DateTime, Utc};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::ops::Range;
+
+#[cfg(test)]
+mod tests;
+
+/// Prefix of the entry id the API uses to report an error, e.g. for a malformed query.
+const API_ERROR_ID: &str = "http://arxiv.org/api/errors";
+
+/// Prefix of the feed title, followed by the query the API answered.
+const QUERY_TITLE_PREFIX: &str = "ArXiv Query: ";
+
+/// Feed-level metadata: which query produced the results, and when.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeedMeta {
+    /// `<title>`, e.g. `ArXiv Query: search_query=all:attention&id_list=&start=0&max_results=2`.
+    pub title: String,
+    /// `<id>`, a unique id of the query.
+    pub id: String,
+    /// `<updated>`, the time the results were produced, e.g. `2024-12-05T00:00:00-05:00`.
+    pub updated: String,
+    /// The `self` link: the request URL of the query.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_link: Option<String>,
+    /// The `next` link, if the feed provides one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_link: Option<String>,
+}
+
+/// How runs of whitespace in the title, abstract and comments of a paper are normalized.
+///
+/// The API wraps long titles and abstracts, continuing them on indented lines, e.g. `Counting Lattice Paths\n  with
+/// Restricted Steps`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Whitespace {
+    /// As sent by the API, including the line breaks and indentation of wrapped lines.
+    Raw,
+    /// Trimmed, with the spaces of every line collapsed, but the line breaks kept.
+    Collapse,
+    /// Trimmed, with every run of whitespace, including line breaks, replaced by a single space.
+    #[default]
+    SingleLine,
+}
+
+impl Whitespace {
+    /// `text` normalized according to `self`.
+    /// ```rust
+    /// # use arxiv_tools::Whitespace;
+    /// let title = "Counting Lattice Paths\n  with  Restricted Steps ";
+    /// assert_eq!(Whitespace::Raw.apply(title), title);
+    /// assert_eq!(Whitespace::Collapse.apply(title), "Counting Lattice Paths\nwith Restricted Steps");
+    /// assert_eq!(Whitespace::SingleLine.apply(title), "Counting Lattice Paths with Restricted Steps");
+    /// ```
+    pub fn apply(&self, text: &str) -> String {
+        match self {
+            Whitespace::Raw => return text.to_string(),
+            Whitespace::Collapse => {
+                return text
+                    .lines()
+                    .map(collapse_whitespace)
+                    .filter(|line| !line.is_empty())
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            }
+            Whitespace::SingleLine => return collapse_whitespace(text),
+        }
+    }
+}
+
+impl FeedMeta {
+    /// The query echoed by the title, e.g. `search_query=all:attention&id_list=&start=0&max_results=2`.
+    pub fn query(&self) -> Option<&str> {
+        return self.title.strip_prefix(QUERY_TITLE_PREFIX);
+    }
+}
+
+/// The feed-level elements. Entries are deserialized one at a time by [`FeedReader`].
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Feed<'a> {
+    title: String,
+    id: String,
+    updated: String,
+    #[serde(rename = "link", borrow)]
+    links: Vec<AtomLink<'a>>,
+    #[serde(rename = "totalResults")]
+    total_results: Option<u64>,
+    #[serde(rename = "startIndex")]
+    start_index: Option<u64>,
+    #[serde(rename = "itemsPerPage")]
+    items_per_page: Option<u64>,
+}
+
+/// An `<entry>`, borrowing its text from the feed where no entity had to be unescaped.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Entry<'a> {
+    #[serde(borrow)]
+    id: Cow<'a, str>,
+    #[serde(borrow)]
+    title: Cow<'a, str>,
+    #[serde(borrow)]
+    summary: Cow<'a, str>,
+    #[serde(borrow)]
+    published: Cow<'a, str>,
+    #[serde(borrow)]
+    updated: Cow<'a, str>,
+    #[serde(rename = "author", borrow)]
+    authors: Vec<AtomAuthor<'a>>,
+    #[serde(rename = "comment")]
+    comments: Vec<String>,
+    journal_ref: Option<String>,
+    doi: Option<String>,
+    #[serde(rename = "link", borrow)]
+    links: Vec<AtomLink<'a>>,
+    #[serde(borrow)]
+    primary_category: Option<Term<'a>>,
+    #[serde(rename = "category", borrow)]
+    categories: Vec<Term<'a>>,
+    msc_class: Option<String>,
+    acm_class: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct AtomAuthor<'a> {
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    #[serde(rename = "affiliation")]
+    affiliations: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct AtomLink<'a> {
+    #[serde(rename = "@href", borrow)]
+    href: Cow<'a, str>,
+    #[serde(rename = "@rel")]
+    rel: Option<String>,
+    #[serde(rename = "@title")]
+    title: Option<String>,
+    #[serde(rename = "@type")]
+    mime: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Term<'a> {
+    #[serde(rename = "@term", borrow)]
+    term: Cow<'a, str>,
+}
+
+impl Feed<'_> {
+    fn into_results(self, papers: Vec<Paper>) -> SearchResults {
+        let link = |rel: &str| {
+            return self
+                .links
+                .iter()
+                .find(|link| link.rel.as_deref() == Some(rel))
+                .map(|link| link.href.to_string());
+        };
+        let meta = FeedMeta {
+            self_link: link("self"),
+            next_link: link("next"),
+            title: self.title,
+            id: self.id,
+            updated: self.updated,
+        };
+        return SearchResults {
+            meta,
+            papers,
+            total: self.total_results,
+            start: self.start_index,
+            per_page: self.items_per_page,
+        };
+    }
+}
+
+impl Entry<'_> {
+    /// The `href` of the link titled `title`, e.g. `pdf`.
+    fn link(&self, title: &str) -> Option<&str> {
+        return self
+            .links
+            .iter()
+            .find(|link| link.title.as_deref() == Some(title))
+            .map(|link| link.href.as_ref());
+    }
+
+    /// The paper of the entry, or the error the entry reports.
+    fn try_into_paper(
+        self,
+        whitespace: Whitespace,
+        interner: Option<&Interner>,
+    ) -> Result<Paper, ArxivError> {
+        if self.id.starts_with(API_ERROR_ID) {
+            return Err(ArxivError::Api(self.summary.trim().replace("\n", "")));
+        }
+        return Ok(self.into_paper(whitespace, interner));
+    }
+
+    fn into_paper(self, whitespace: Whitespace, interner: Option<&Interner>) -> Paper {
+        let doi = match &self.doi {
+            Some(doi) => collapsed(doi),
+            None => self
+                .link("doi")
+                .map(normalize_doi)
+                .filter(|doi| !doi.is_empty()),
+        };
+        let pdf_url = self.link("pdf").map(|href| href.to_string());
+        let comment = self
+            .comments
+            .iter()
+            .map(|comment| whitespace.apply(comment))
+            .filter(|comment| !comment.trim().is_empty())
+            .collect::<Vec<String>>()
+            .join("; ");
+        return Paper {
+            title: whitespace.apply(&self.title),
+            authors: self
+                .authors
+                .into_iter()
+                .map(|author| Author {
+                    name: intern(interner, &author.name),
+                    affiliations: author.affiliations,
+                })
+                .collect(),
+            abstract_text: whitespace.apply(&self.summary),
+            doi,
+            pdf_url,
+            id: self.id.into_owned(),
+            published: parse_timestamp(&self.published),
+            updated: parse_timestamp(&self.updated),
+            comment: Some(comment).filter(|comment| !comment.is_empty()),
+            journal_ref: self.journal_ref.as_deref().and_then(collapsed),
+            primary_category: self
+                .primary_category
+                .map(|category| intern(interner, &category.term))
+                .unwrap_or_default(),
+            categories: self
+                .categories
+                .into_iter()
+                .map(|category| intern(interner, &category.term))
+                .collect(),
+            msc_class: self.msc_class.as_deref().and_then(collapsed),
+            acm_class: self.acm_class.as_deref().and_then(collapsed),
+            links: self
+                .links
+                .into_iter()
+                .map(|link| Link {
+                    href: link.href.into_owned(),
+                    rel: link.rel,
+                    title: link.title,
+                    mime: link.mime,
+                })
+                .collect(),
+            #[cfg(feature = "raw-xml")]
+            raw_xml: None,
+        };
+    }
+}
+
+/// Resolver prefixes of DOI links, e.g. `http://dx.doi.org/` of the links the API sends.
+const DOI_RESOLVERS: [&str; 4] = [
+    "https://doi.org/",
+    "http://doi.org/",
+    "https://dx.doi.org/",
+    "http://dx.doi.org/",
+];
+
+/// The bare DOI of a DOI link, e.g. `10.1103/PhysRevD.76.013009` for `http://dx.doi.org/10.1103/PhysRevD.76.013009`.
+fn normalize_doi(link: &str) -> String {
+    let link = link.trim();
+    let doi = DOI_RESOLVERS
+        .iter()
+        .find_map(|resolver| link.strip_prefix(resolver))
+        .unwrap_or(link);
+    return doi.to_string();
+}
+
+/// `text` with every run of whitespace, including line breaks, replaced by a single space, and trimmed.
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    for word in text.split_whitespace() {
+        if !collapsed.is_empty() {
+            collapsed.push(' ');
+        }
+        collapsed.push_str(word);
+    }
+    return collapsed;
+}
+
+/// A `published` or `updated` timestamp in UTC, or `None` if it is not RFC 3339.
+fn parse_timestamp(text: &str) -> Option<DateTime<Utc>> {
+    let timestamp = DateTime::parse_from_rfc3339(text.trim()).ok()?;
+    return Some(timestamp.with_timezone(&Utc));
+}
+
+/// The text of an optional element, e.g. `journal_ref`, with whitespace collapsed, or `None` if it is blank.
+fn collapsed(text: &str) -> Option<String> {
+    let text = collapse_whitespace(text);
+    if text.is_empty() {
+        return None;
+    }
+    return Some(text);
+}
+
+/// Parse an Atom feed of the API, with the whitespace of titles, abstracts and comments normalized to a single line.
+///
+/// A feed reporting an API error, e.g. for a malformed id, is an [`ArxivError::Api`], and a malformed feed an
+/// [`ArxivError::Xml`].
+pub fn parse_atom(xml: &str) -> Result<SearchResults, ArxivError> {
+    return parse_atom_with(xml, Whitespace::default());
+}
+
+/// Parse an Atom feed of the API like [`parse_atom`], normalizing the whitespace of titles, abstracts and comments
+/// according to `whitespace`.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(bytes = xml.len()), err(Display))
+)]
+pub fn parse_atom_with(xml: &str, whitespace: Whitespace) -> Result<SearchResults, ArxivError> {
+    let results = FeedReader::new(whitespace).read_all(xml.as_bytes())?;
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        papers = results.papers.len(),
+        total = results.total,
+        "parsed feed"
+    );
+    return Ok(results);
+}
+
+/// Incremental parser of an Atom feed, returning every entry as soon as its closing tag arrives instead of once the
+/// whole feed is read, e.g. to process a page of 2,000 entries while it is downloaded:
+/// ```rust
+/// # use arxiv_tools::atom::FeedReader;
+/// # use arxiv_tools::Whitespace;
+/// let xml = include_bytes!("../fixtures/feed.xml");
+/// let mut reader = FeedReader::new(Whitespace::default());
+/// let mut papers = Vec::new();
+/// for chunk in xml.chunks(512) {
+///     papers.extend(reader.push(chunk).unwrap());
+/// }
+/// let results = reader.finish().unwrap();
+/// assert_eq!(papers.len(), 2);
+/// assert_eq!(results.total, Some(28374));
+/// ```
+/// Only the feed-level elements and the entry being received are buffered.
+#[derive(Debug)]
+pub struct FeedReader {
+    whitespace: Whitespace,
+    interner: Option<Interner>,
+    buffer: Vec<u8>,
+    /// The metadata and paging of the feed, parsed once the first entry starts.
+    header: Option<SearchResults>,
+    /// The qualified name of the root element, e.g. `feed`.
+    root: Option<Vec<u8>>,
+    #[cfg(feature = "raw-xml")]
+    raw_xml: bool,
+}
+
+/// Byte ranges of [`FeedReader::buffer`] found by one pass over it.
+#[derive(Debug, Default)]
+struct Scan {
+    /// Where the first entry starts, if the header was not parsed yet.
+    header_end: Option<usize>,
+    entries: Vec<Range<usize>>,
+}
+
+impl FeedReader {
+    pub fn new(whitespace: Whitespace) -> Self {
+        return FeedReader {
+            whitespace,
+            interner: None,
+            buffer: Vec::new(),
+            header: None,
+            root: None,
+            #[cfg(feature = "raw-xml")]
+            raw_xml: false,
+        };
+    }
+
+    /// Share the categories and author names of the papers through `interner`.
+    pub fn with_interner(&mut self, interner: Interner) -> &mut Self {
+        self.interner = Some(interner);
+        return self;
+    }
+
+    /// Keep the original `<entry>` element of every paper in [`Paper::raw_xml`].
+    #[cfg(feature = "raw-xml")]
+    pub fn retain_raw_xml(&mut self, retain: bool) -> &mut Self {
+        self.raw_xml = retain;
+        return self;
+    }
+
+    /// Parse the whole feed `xml` at once, like [`parse_atom_with`].
+    pub fn read_all(mut self, xml: &[u8]) -> Result<SearchResults, ArxivError> {
+        let (papers, consumed) = self.read(xml)?;
+        self.buffer = xml[consumed..].to_vec();
+        let mut results = self.finish()?;
+        results.papers = papers;
+        return Ok(results);
+    }
+
+    /// Append `chunk` of the feed, and return the papers of the entries it completes.
+    ///
+    /// An entry reporting an API error is an [`ArxivError::Api`], and a malformed entry an [`ArxivError::Xml`].
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Vec<Paper>, ArxivError> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.extend_from_slice(chunk);
+        let read = self.read(&buffer);
+        if let Ok((_, consumed)) = &read {
+            buffer.drain(..*consumed);
+        }
+        self.buffer = buffer;
+        return read.map(|(papers, _)| papers);
+    }
+
+    /// The number of papers matching the query, once the feed-level elements were received.
+    pub fn total(&self) -> Option<u64> {
+        return self.header.as_ref()?.total;
+    }
+
+    /// Parse the header and the complete entries of `xml`, the buffered part of the feed, returning the papers and
+    /// the number of bytes read.
+    fn read(&mut self, xml: &[u8]) -> Result<(Vec<Paper>, usize), ArxivError> {
+        let scan = self.scan(xml);
+        if let Some(header_end) = scan.header_end {
+            let mut header = xml[..header_end].to_vec();
+            header.extend_from_slice(b"</");
+            header.extend_from_slice(self.root.as_deref().unwrap_or(b"feed"));
+            header.push(b'>');
+            let feed: Feed = deserialize(&header)?;
+            self.header = Some(feed.into_results(Vec::new()));
+        }
+        let mut papers = Vec::with_capacity(scan.entries.len());
+        for range in &scan.entries {
+            let entry: Entry = deserialize(&xml[range.clone()])?;
+            #[allow(unused_mut)]
+            let mut paper = entry.try_into_paper(self.whitespace, self.interner.as_ref())?;
+            #[cfg(feature = "raw-xml")]
+            if self.raw_xml {
+                paper.raw_xml = Some(String::from_utf8_lossy(&xml[range.clone()]).to_string());
+            }
+            papers.push(paper);
+        }
+        let consumed = scan
+            .entries
+            .last()
+            .map(|range| range.end)
+            .or(scan.header_end)
+            .unwrap_or(0);
+        return Ok((papers, consumed));
+    }
+
+    /// End the feed, returning its metadata and paging, without the papers already returned by
+    /// [`FeedReader::push`].
+    ///
+    /// A feed that ends within an entry or before its closing tag is an [`ArxivError::Xml`].
+    pub fn finish(self) -> Result<SearchResults, ArxivError> {
+        let Some(header) = self.header else {
+            // no entry started, so the whole feed is still buffered
+            let feed: Feed = deserialize(&self.buffer)?;
+            return Ok(feed.into_results(Vec::new()));
+        };
+        let rest = String::from_utf8_lossy(&self.buffer);
+        let root = String::from_utf8_lossy(self.root.as_deref().unwrap_or(b"feed"));
+        if rest.trim() != format!("</{}>", root) {
+            return Err(ArxivError::Xml(String::from("the feed ended unexpectedly")));
+        }
+        return Ok(header);
+    }
+
+    /// Find the entries completed by `xml`, and the end of the header if the first entry started.
+    ///
+    /// `xml` starts at the beginning of the feed until the header is parsed, and right after the last entry
+    /// returned otherwise. The pass stops at the first incomplete or malformed event; a malformed feed is then
+    /// reported by [`FeedReader::finish`].
+    fn scan(&mut self, xml: &[u8]) -> Scan {
+        let mut reader = Reader::from_reader(xml);
+        reader.config_mut().check_end_names = false;
+        let mut scan = Scan::default();
+        let mut depth = if self.header.is_some() { 1 } else { 0 };
+        let mut entry_start = None;
+        loop {
+            let position = reader.buffer_position() as usize;
+            let (start, complete) = match reader.read_event() {
+                Ok(Event::Start(element)) => {
+                    depth += 1;
+                    if depth == 1 {
+                        self.root = Some(element.name().as_ref().to_vec());
+                    }
+                    (depth == 2 && is_entry(&element), false)
+                }
+                Ok(Event::Empty(element)) => {
+                    let entry = depth == 1 && is_entry(&element);
+                    (entry, entry)
+                }
+                Ok(Event::End(_)) => {
+                    depth -= 1;
+                    (false, depth == 1 && entry_start.is_some())
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                Ok(_) => (false, false),
+            };
+            if start {
+                if self.header.is_none() && scan.header_end.is_none() {
+                    scan.header_end = Some(position);
+                }
+                entry_start = Some(position);
+            }
+            if complete {
+                if let Some(entry_start) = entry_start.take() {
+                    scan.entries
+                        .push(entry_start..reader.buffer_position() as usize);
+                }
+            }
+        }
+        return scan;
+    }
+}
+
+fn is_entry(element: &BytesStart) -> bool {
+    return element.local_name().as_ref() == b"entry";
+}
+
+fn deserialize<'de, T: Deserialize<'de>>(xml: &'de [u8]) -> Result<T, ArxivError> {
+    let xml = std::str::from_utf8(xml).map_err(|e| ArxivError::Xml(e.to_string()))?;
+    return quick_xml::de::from_str(xml).map_err(|e| ArxivError::Xml(e.to_string()));
+}
@@ -0,0 +1,354 @@
+use super::*;
+use std::sync::Arc;
+
+#[test]
+fn test_parse_rich_feed() {
+    let results = parse_atom(include_str!("../../fixtures/rich.xml")).unwrap();
+    assert_eq!(results.total, Some(3));
+    assert_eq!(results.papers.len(), 3);
+
+    let paper = &results.papers[0];
+    assert_eq!(paper.id, "http://arxiv.org/abs/math/0611001v3");
+    assert_eq!(
+        paper.authors,
+        vec![
+            Author {
+                name: "A. Author".into(),
+                affiliations: vec![String::from("Institute A"), String::from("Institute B")],
+            },
+            Author::new("B. Author"),
+        ]
+    );
+    assert_eq!(paper.comment.as_deref(), Some("18 pages; final version"));
+    assert_eq!(
+        paper.journal_ref.as_deref(),
+        Some("J. Combin. Theory Ser. A 115 (2008) 1201-1224")
+    );
+    assert_eq!(paper.doi.as_deref(), Some("10.1016/j.jcta.2008.01.001"));
+    assert_eq!(
+        paper.pdf_url.as_deref(),
+        Some("http://arxiv.org/pdf/math/0611001v3")
+    );
+    assert_eq!(&*paper.primary_category, "math.CO");
+    assert_eq!(paper.categories, ["math.CO", "05A15"].map(Arc::<str>::from));
+    assert_eq!(paper.msc_class.as_deref(), Some("05A15; 05A16"));
+    assert_eq!(paper.acm_class, None);
+    assert_eq!(
+        paper.links,
+        vec![
+            Link {
+                href: String::from("http://dx.doi.org/10.1016/j.jcta.2008.01.001"),
+                rel: Some(String::from("related")),
+                title: Some(String::from("doi")),
+                mime: None,
+            },
+            Link {
+                href: String::from("http://arxiv.org/abs/math/0611001v3"),
+                rel: Some(String::from("alternate")),
+                title: None,
+                mime: Some(String::from("text/html")),
+            },
+            Link {
+                href: String::from("http://arxiv.org/pdf/math/0611001v3"),
+                rel: Some(String::from("related")),
+                title: Some(String::from("pdf")),
+                mime: Some(String::from("application/pdf")),
+            },
+        ]
+    );
+
+    let paper = &results.papers[1];
+    assert_eq!(paper.abstract_text, "We give a linear-time algorithm.");
+    // only `arxiv:doi`, no DOI link
+    assert_eq!(paper.doi.as_deref(), Some("10.1145/3450000.3450001"));
+    assert_eq!(paper.journal_ref, None);
+    assert_eq!(paper.categories, ["cs.DS", "math.CO"].map(Arc::<str>::from));
+    assert_eq!(paper.msc_class, None);
+    assert_eq!(paper.acm_class.as_deref(), Some("F.2.2; G.2.2"));
+
+    let paper = &results.papers[2];
+    assert_eq!(paper.title, "Minimal Entry");
+    assert_eq!(paper.comment, None);
+    assert_eq!(paper.journal_ref, None);
+    assert_eq!(paper.doi, None);
+}
+
+/// Every recorded feed parses, and every entry has the fields the API always sends.
+#[test]
+fn test_recorded_feeds() {
+    for (name, xml) in [
+        ("feed.xml", include_str!("../../fixtures/feed.xml")),
+        ("empty.xml", include_str!("../../fixtures/empty.xml")),
+        ("rich.xml", include_str!("../../fixtures/rich.xml")),
+        (
+            "journal_ref.xml",
+            include_str!("../../fixtures/journal_ref.xml"),
+        ),
+        ("prefixes.xml", include_str!("../../fixtures/prefixes.xml")),
+    ] {
+        let results = parse_atom(xml).unwrap_or_else(|e| panic!("{}: {}", name, e));
+        assert!(results.total.is_some(), "{}", name);
+        for paper in &results.papers {
+            assert!(paper.id.starts_with("http://arxiv.org/abs/"), "{}", name);
+            assert!(!paper.title.is_empty(), "{}", name);
+            assert!(!paper.authors.is_empty(), "{}", name);
+            assert!(paper.published.is_some(), "{}", name);
+            assert!(paper.pdf_url.is_some(), "{}", name);
+            assert!(!paper.primary_category.is_empty(), "{}", name);
+        }
+    }
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(matches!(
+        parse_atom(include_str!("../../fixtures/error.xml")),
+        Err(ArxivError::Api(_))
+    ));
+    assert!(matches!(
+        parse_atom("<feed><entry></feed>"),
+        Err(ArxivError::Xml(_))
+    ));
+}
+
+#[test]
+fn test_feed_meta() {
+    let results = parse_atom(include_str!("../../fixtures/feed.xml")).unwrap();
+    let meta = &results.meta;
+    assert_eq!(
+        meta.title,
+        "ArXiv Query: search_query=all:attention&id_list=&start=0&max_results=2"
+    );
+    assert_eq!(
+        meta.query(),
+        Some("search_query=all:attention&id_list=&start=0&max_results=2")
+    );
+    assert_eq!(meta.id, "http://arxiv.org/api/cHxbiOdZaP56ODnBPIenZhzg5f8");
+    assert_eq!(meta.updated, "2024-12-05T00:00:00-05:00");
+    assert_eq!(
+        meta.self_link.as_deref(),
+        Some("http://arxiv.org/api/query?search_query%3Dall%3Aattention%26id_list%3D%26start%3D0%26max_results%3D2")
+    );
+    assert_eq!(meta.next_link, None);
+
+    let json = serde_json::to_string(&results).unwrap();
+    let loaded: SearchResults = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.meta, results.meta);
+}
+
+#[test]
+fn test_author_affiliations() {
+    let results = parse_atom(include_str!("../../fixtures/feed.xml")).unwrap();
+    assert!(results.papers[0].authors[0].affiliations.is_empty());
+    assert_eq!(
+        results.papers[1].authors[0].affiliations,
+        vec!["University of Somewhere"]
+    );
+
+    // authors saved as plain names are still read
+    let author: Author = serde_json::from_str(r#""J. Doe""#).unwrap();
+    assert_eq!(author, Author::new("J. Doe"));
+    let author: Author =
+        serde_json::from_str(r#"{"name":"J. Doe","affiliations":["Somewhere"]}"#).unwrap();
+    assert_eq!(author.affiliations, vec!["Somewhere"]);
+    assert_eq!(
+        serde_json::to_string(&Author::new("J. Doe")).unwrap(),
+        r#"{"name":"J. Doe"}"#
+    );
+}
+
+#[test]
+fn test_normalize_doi() {
+    assert_eq!(
+        normalize_doi("http://dx.doi.org/10.1103/PhysRevD.76.013009"),
+        "10.1103/PhysRevD.76.013009"
+    );
+    assert_eq!(normalize_doi("https://doi.org/10.1000/1"), "10.1000/1");
+    assert_eq!(normalize_doi(" 10.1000/1 "), "10.1000/1");
+    assert_eq!(normalize_doi(""), "");
+
+    // a DOI link without `arxiv:doi`
+    let xml = r#"<feed><entry>
+        <id>http://arxiv.org/abs/0000.00000v1</id>
+        <link title="doi" href="http://dx.doi.org/10.1000/2" rel="related"/>
+    </entry></feed>"#;
+    assert_eq!(
+        parse_atom(xml).unwrap().papers[0].doi.as_deref(),
+        Some("10.1000/2")
+    );
+}
+
+#[test]
+fn test_journal_ref() {
+    let results = parse_atom(include_str!("../../fixtures/journal_ref.xml")).unwrap();
+    let papers = &results.papers;
+    assert_eq!(papers.len(), 3);
+
+    // entities are unescaped, and the following comment is not affected
+    assert_eq!(
+        papers[0].journal_ref.as_deref(),
+        Some("Astron. & Astrophys. 400 (2003) 1-10")
+    );
+    assert_eq!(papers[0].comment.as_deref(), Some("10 pages, accepted"));
+
+    // line breaks and indentation are collapsed
+    assert_eq!(
+        papers[1].journal_ref.as_deref(),
+        Some("Phys. Rev. A 72, 012345 (2005)")
+    );
+    assert_eq!(papers[1].comment.as_deref(), Some("v2: typos fixed"));
+
+    // the journal reference of a previous entry doesn't leak into the next one
+    assert_eq!(papers[2].journal_ref, None);
+    assert_eq!(papers[2].comment, None);
+
+    let results = parse_atom(include_str!("../../fixtures/feed.xml")).unwrap();
+    assert_eq!(results.papers[0].journal_ref, None);
+    assert_eq!(
+        results.papers[1].journal_ref.as_deref(),
+        Some("Phys.Rev.D76:013009,2007")
+    );
+}
+
+#[test]
+fn test_whitespace() {
+    let xml = include_str!("../../fixtures/rich.xml");
+
+    let paper = &parse_atom(xml).unwrap().papers[0];
+    assert_eq!(paper.title, "Counting Lattice Paths with Restricted Steps");
+    assert_eq!(
+        paper.abstract_text,
+        "We count lattice paths with steps in a finite set $S$ that stay in the first quadrant, using the kernel method."
+    );
+
+    let paper = &parse_atom_with(xml, Whitespace::Collapse).unwrap().papers[0];
+    assert_eq!(paper.title, "Counting Lattice Paths\nwith Restricted Steps");
+    assert_eq!(
+        paper.abstract_text,
+        "We count lattice paths with steps in a finite set $S$ that stay in the\nfirst quadrant, using the kernel method."
+    );
+
+    let paper = &parse_atom_with(xml, Whitespace::Raw).unwrap().papers[0];
+    assert_eq!(
+        paper.title,
+        "Counting Lattice Paths\n  with Restricted Steps"
+    );
+    assert!(paper.abstract_text.contains("stay in the\nfirst quadrant"));
+    assert_eq!(paper.comment.as_deref(), Some("18 pages; final version"));
+}
+
+/// Namespace prefixes other than the ones the API uses are resolved the same way.
+#[test]
+fn test_namespace_prefixes() {
+    let results = parse_atom(include_str!("../../fixtures/prefixes.xml")).unwrap();
+    assert_eq!(results.total, Some(1));
+    assert_eq!(results.per_page, Some(10));
+    assert_eq!(
+        results.meta.query(),
+        Some("search_query=&id_list=math/0611001&start=0&max_results=10")
+    );
+
+    let paper = &results.papers[0];
+    assert_eq!(paper.id, "http://arxiv.org/abs/math/0611001v3");
+    assert_eq!(
+        paper.authors,
+        vec![Author {
+            name: "A. Author".into(),
+            affiliations: vec![String::from("Institute A")],
+        }]
+    );
+    assert_eq!(paper.doi.as_deref(), Some("10.1016/j.jcta.2008.01.001"));
+    assert_eq!(paper.comment.as_deref(), Some("18 pages; final version"));
+    assert_eq!(
+        paper.journal_ref.as_deref(),
+        Some("J. Combin. Theory Ser. A 115 (2008) 1201-1224")
+    );
+    assert_eq!(paper.msc_class.as_deref(), Some("05A15"));
+    assert_eq!(
+        paper.pdf_url.as_deref(),
+        Some("http://arxiv.org/pdf/math/0611001v3")
+    );
+    assert_eq!(&*paper.primary_category, "math.CO");
+    assert_eq!(paper.categories, ["math.CO"].map(Arc::<str>::from));
+}
+
+/// Papers of a feed read in chunks, and the rest of the results once it ends.
+fn read_chunked(xml: &str, chunk_size: usize) -> Result<SearchResults, ArxivError> {
+    let mut reader = FeedReader::new(Whitespace::default());
+    let mut papers = Vec::new();
+    for chunk in xml.as_bytes().chunks(chunk_size) {
+        papers.extend(reader.push(chunk)?);
+    }
+    let mut results = reader.finish()?;
+    results.papers = papers;
+    return Ok(results);
+}
+
+#[test]
+fn test_feed_reader() {
+    for xml in [
+        include_str!("../../fixtures/feed.xml"),
+        include_str!("../../fixtures/empty.xml"),
+        include_str!("../../fixtures/rich.xml"),
+        include_str!("../../fixtures/journal_ref.xml"),
+        include_str!("../../fixtures/prefixes.xml"),
+    ] {
+        let expected = serde_json::to_value(parse_atom(xml).unwrap()).unwrap();
+        // chunks split tags, entities and multi-byte characters
+        for chunk_size in [1, 7, 64, 1024, xml.len()] {
+            let results = read_chunked(xml, chunk_size).unwrap();
+            assert_eq!(serde_json::to_value(results).unwrap(), expected);
+        }
+    }
+}
+
+#[test]
+fn test_feed_reader_returns_entries_early() {
+    let xml = include_str!("../../fixtures/rich.xml");
+    let second = xml.find("<id>http://arxiv.org/abs/2101.00001v1").unwrap();
+    let mut reader = FeedReader::new(Whitespace::default());
+    let papers = reader.push(&xml.as_bytes()[..second]).unwrap();
+    assert_eq!(papers.len(), 1);
+    assert_eq!(papers[0].id, "http://arxiv.org/abs/math/0611001v3");
+    assert_eq!(reader.total(), Some(3));
+    // only the entry being received is buffered
+    assert!(reader.buffer.len() < 100);
+}
+
+#[test]
+fn test_feed_reader_errors() {
+    let xml = include_str!("../../fixtures/feed.xml");
+    let truncated = &xml[..xml.rfind("</entry>").unwrap()];
+    assert!(matches!(
+        read_chunked(truncated, 64),
+        Err(ArxivError::Xml(_))
+    ));
+    assert!(matches!(
+        read_chunked(include_str!("../../fixtures/error.xml"), 64),
+        Err(ArxivError::Api(message)) if message == "incorrect id format for 1234.12345"
+    ));
+    assert!(matches!(
+        read_chunked("<feed><entry></feed>", 4),
+        Err(ArxivError::Xml(_))
+    ));
+}
+
+#[cfg(feature = "raw-xml")]
+#[test]
+fn test_raw_xml() {
+    let xml = include_str!("../../fixtures/feed.xml");
+    let results = FeedReader::new(Whitespace::default())
+        .read_all(xml.as_bytes())
+        .unwrap();
+    assert_eq!(results.papers[0].raw_xml, None);
+
+    let mut reader = FeedReader::new(Whitespace::default());
+    reader.retain_raw_xml(true);
+    let results = reader.read_all(xml.as_bytes()).unwrap();
+    for paper in &results.papers {
+        let raw = paper.raw_xml.as_deref().unwrap();
+        assert!(raw.starts_with("<entry>"));
+        assert!(raw.ends_with("</entry>"));
+        assert!(raw.contains(&paper.id));
+    }
+}
@@ -0,0 +1,114 @@
+//! # Paper Builder
+//! Builds [`Paper`] values for fixtures and tests without spelling out every field:
+//! ```rust
+//! # use arxiv_tools::Paper;
+//! let paper = Paper::builder()
+//!     .id("2412.01234v2")
+//!     .title("Scaling Laws for Retrieval")
+//!     .author("Jane Doe")
+//!     .category("cs.CL")
+//!     .category("cs.LG")
+//!     .published("2024-12-02T09:00:00Z".parse().unwrap())
+//!     .build();
+//! assert_eq!(paper.id, "http://arxiv.org/abs/2412.01234v2");
+//! assert_eq!(&*paper.primary_category, "cs.CL");
+//! assert_eq!(paper.updated, paper.published);
+//! ```
+use crate::{ArxivId, Author, Paper};
+use chrono::{DateTime, Utc};
+
+#[cfg(test)]
+mod tests;
+
+/// Builder of a [`Paper`], created with [`Paper::builder`]. Unset fields keep their [`Default`].
+#[derive(Clone, Debug, Default)]
+pub struct PaperBuilder {
+    paper: Paper,
+}
+
+impl PaperBuilder {
+    pub fn new() -> Self {
+        return PaperBuilder::default();
+    }
+
+    /// The identifier. An arXiv identifier such as `2412.01234v2` is stored as the abs URL the API sends,
+    /// e.g. `http://arxiv.org/abs/2412.01234v2`; anything else is stored as given.
+    pub fn id(&mut self, id: &str) -> &mut Self {
+        self.paper.id = match id.parse::<ArxivId>() {
+            Ok(id) => format!("http://arxiv.org/abs/{}", id),
+            Err(_) => id.to_string(),
+        };
+        return self;
+    }
+    pub fn title(&mut self, title: &str) -> &mut Self {
+        self.paper.title = title.to_string();
+        return self;
+    }
+    pub fn abstract_text(&mut self, abstract_text: &str) -> &mut Self {
+        self.paper.abstract_text = abstract_text.to_string();
+        return self;
+    }
+    /// Add an author named `name`, after the authors added before.
+    pub fn author(&mut self, name: &str) -> &mut Self {
+        self.paper.authors.push(Author::new(name));
+        return self;
+    }
+    /// Add `authors`, after the authors added before.
+    pub fn authors<I: IntoIterator<Item = Author>>(&mut self, authors: I) -> &mut Self {
+        self.paper.authors.extend(authors);
+        return self;
+    }
+    /// Add the category `code`, e.g. `cs.CL`. The first category added is the primary one unless
+    /// [`PaperBuilder::primary_category`] is set.
+    pub fn category(&mut self, code: &str) -> &mut Self {
+        if self.paper.categories.is_empty() && self.paper.primary_category.is_empty() {
+            self.paper.primary_category = code.into();
+        }
+        if !self.paper.categories.iter().any(|listed| &**listed == code) {
+            self.paper.categories.push(code.into());
+        }
+        return self;
+    }
+    /// Make `code` the primary category, adding it to the categories if it is not listed yet.
+    pub fn primary_category(&mut self, code: &str) -> &mut Self {
+        self.paper.primary_category = code.into();
+        if !self.paper.categories.iter().any(|listed| &**listed == code) {
+            self.paper.categories.insert(0, code.into());
+        }
+        return self;
+    }
+    pub fn published(&mut self, published: DateTime<Utc>) -> &mut Self {
+        self.paper.published = Some(published);
+        return self;
+    }
+    /// When the paper was last updated, the publication date if unset.
+    pub fn updated(&mut self, updated: DateTime<Utc>) -> &mut Self {
+        self.paper.updated = Some(updated);
+        return self;
+    }
+    pub fn doi(&mut self, doi: &str) -> &mut Self {
+        self.paper.doi = Some(doi.to_string());
+        return self;
+    }
+    pub fn comment(&mut self, comment: &str) -> &mut Self {
+        self.paper.comment = Some(comment.to_string());
+        return self;
+    }
+    pub fn journal_ref(&mut self, journal_ref: &str) -> &mut Self {
+        self.paper.journal_ref = Some(journal_ref.to_string());
+        return self;
+    }
+    pub fn pdf_url(&mut self, pdf_url: &str) -> &mut Self {
+        self.paper.pdf_url = Some(pdf_url.to_string());
+        return self;
+    }
+
+    /// The paper with the fields set so far.
+    pub fn build(&self) -> Paper {
+        let mut paper = self.paper.clone();
+        if paper.updated.is_none() {
+            paper.updated = paper.published;
+        }
+        return paper;
+    }
+}
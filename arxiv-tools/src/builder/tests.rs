@@ -0,0 +1,67 @@
+use super::*;
+use chrono::TimeZone;
+use std::sync::Arc;
+
+#[test]
+fn test_build_paper() {
+    let published = Utc.with_ymd_and_hms(2007, 1, 1, 16, 40, 21).unwrap();
+    let updated = Utc.with_ymd_and_hms(2007, 3, 14, 10, 12, 45).unwrap();
+    let paper = Paper::builder()
+        .id("http://arxiv.org/abs/hep-ph/0701001v2")
+        .title("Neutrino Masses")
+        .abstract_text("We review neutrino masses.")
+        .authors([Author::new("A. Author")])
+        .author("B. Author")
+        .category("hep-ex")
+        .primary_category("hep-ph")
+        .category("hep-ex")
+        .published(published)
+        .updated(updated)
+        .doi("10.1103/PhysRevD.76.013009")
+        .comment("12 pages")
+        .journal_ref("Phys.Rev.D76:013009,2007")
+        .pdf_url("http://arxiv.org/pdf/hep-ph/0701001v2")
+        .build();
+
+    assert_eq!(paper.id, "http://arxiv.org/abs/hep-ph/0701001v2");
+    assert_eq!(paper.version(), Some(2));
+    assert_eq!(paper.title, "Neutrino Masses");
+    assert_eq!(paper.abstract_text, "We review neutrino masses.");
+    assert_eq!(
+        paper.authors,
+        vec![Author::new("A. Author"), Author::new("B. Author")]
+    );
+    assert_eq!(&*paper.primary_category, "hep-ph");
+    assert_eq!(paper.categories, ["hep-ph", "hep-ex"].map(Arc::<str>::from));
+    assert_eq!(paper.published, Some(published));
+    assert_eq!(paper.updated, Some(updated));
+    assert!(paper.is_revision());
+    assert_eq!(paper.doi.as_deref(), Some("10.1103/PhysRevD.76.013009"));
+    assert_eq!(paper.comment.as_deref(), Some("12 pages"));
+    assert_eq!(
+        paper.journal_ref.as_deref(),
+        Some("Phys.Rev.D76:013009,2007")
+    );
+    assert_eq!(
+        paper.pdf_url.as_deref(),
+        Some("http://arxiv.org/pdf/hep-ph/0701001v2")
+    );
+}
+
+#[test]
+fn test_build_defaults() {
+    let paper = PaperBuilder::new().id("urn:example:1").build();
+    assert_eq!(paper.id, "urn:example:1");
+    assert!(paper.title.is_empty());
+    assert!(paper.categories.is_empty());
+    assert_eq!(paper.updated, None);
+
+    let mut builder = Paper::builder();
+    builder.id("2101.00001").category("cs.CL");
+    let first = builder.build();
+    let second = builder.title("Changed").build();
+    assert_eq!(first.id, "http://arxiv.org/abs/2101.00001");
+    assert_eq!(&*first.primary_category, "cs.CL");
+    assert_eq!(first, second);
+    assert!(first.title.is_empty());
+}
@@ -0,0 +1,163 @@
+//! # Cache
+//! Responses can be cached by the session, so that repeated queries within a time-to-live are answered without
+//! a request. Any store implementing [`Cache`] can be plugged in, e.g. Redis or S3; an in-memory
+//! [`MemoryCache`] and an on-disk [`FileCache`] are included.
+//!
+//! ```rust
+//! # use arxiv_tools::ArxivSession;
+//! # use arxiv_tools::cache::FileCache;
+//! # use std::time::Duration;
+//! let session = ArxivSession::new();
+//! let cache = FileCache::new(std::env::temp_dir().join("arxiv_tools_doc_cache")).unwrap();
+//! session.set_cache(cache, Duration::from_secs(3600));
+//! ```
+//!
+//! Entries are keyed by [`cache_key`], which canonicalizes the request URL so that equivalent requests, e.g. with
+//! their parameters in a different order, share an entry. Only successful responses are cached.
+use crate::transport::async_trait;
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use url::Url;
+
+#[cfg(test)]
+mod tests;
+
+/// A store of response bodies with a time-to-live.
+///
+/// Caches are best-effort: an entry that cannot be read is a miss, and a failed write is ignored.
+#[async_trait]
+pub trait Cache: Debug + Send + Sync {
+    /// The body stored under `key`, unless it is missing or expired.
+    async fn get(&self, key: &str) -> Option<Bytes>;
+
+    /// Store `value` under `key` for `ttl`, replacing a previous entry.
+    async fn put(&self, key: &str, value: Bytes, ttl: Duration);
+}
+
+/// The cache key of `url`: the URL with its query parameters sorted by name.
+pub fn cache_key(url: &Url) -> String {
+    let mut pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    pairs.sort();
+    let mut key = url.clone();
+    key.set_fragment(None);
+    if pairs.is_empty() {
+        key.set_query(None);
+    } else {
+        key.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    return key.to_string();
+}
+
+/// A cache in the memory of the process.
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<String, (Instant, Bytes)>>,
+}
+
+impl MemoryCache {
+    pub fn new() -> Self {
+        return MemoryCache::default();
+    }
+
+    /// Drop the expired entries.
+    pub fn purge(&self) {
+        let now = Instant::now();
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|_, (expires, _)| *expires > now);
+    }
+}
+
+#[async_trait]
+impl Cache for MemoryCache {
+    async fn get(&self, key: &str) -> Option<Bytes> {
+        let entries = self.entries.lock().unwrap();
+        let (expires, value) = entries.get(key)?;
+        if *expires <= Instant::now() {
+            return None;
+        }
+        return Some(value.clone());
+    }
+
+    async fn put(&self, key: &str, value: Bytes, ttl: Duration) {
+        let expires = Instant::now() + ttl;
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), (expires, value));
+    }
+}
+
+/// The 64-bit FNV-1a hash of `bytes`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    return bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        return (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3);
+    });
+}
+
+/// A cache of one file per entry in a directory, kept across runs.
+///
+/// Each file starts with the expiry time in seconds since the Unix epoch and the key, each on its own line,
+/// followed by the body. Files are named after the 64-bit FNV-1a hash of the key, which, unlike the hasher of the
+/// standard library, stays the same across Rust releases.
+#[derive(Clone, Debug)]
+pub struct FileCache {
+    dir: PathBuf,
+}
+
+impl FileCache {
+    /// Use `dir` as cache directory, creating it if needed.
+    pub fn new<P: AsRef<Path>>(dir: P) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir.as_ref())?;
+        return Ok(FileCache {
+            dir: dir.as_ref().to_path_buf(),
+        });
+    }
+
+    pub fn dir(&self) -> &Path {
+        return &self.dir;
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        return self
+            .dir
+            .join(format!("{:016x}.cache", fnv1a(key.as_bytes())));
+    }
+}
+
+#[async_trait]
+impl Cache for FileCache {
+    async fn get(&self, key: &str) -> Option<Bytes> {
+        let content = tokio::fs::read(self.path(key)).await.ok()?;
+        let mut parts = content.splitn(3, |b| *b == b'\n');
+        let expires: u64 = std::str::from_utf8(parts.next()?).ok()?.parse().ok()?;
+        if parts.next()? != key.as_bytes() || expires <= unix_time() {
+            return None;
+        }
+        return Some(Bytes::copy_from_slice(parts.next()?));
+    }
+
+    async fn put(&self, key: &str, value: Bytes, ttl: Duration) {
+        let expires = unix_time().saturating_add(ttl.as_secs());
+        let mut content = format!("{}\n{}\n", expires, key).into_bytes();
+        content.extend_from_slice(&value);
+        let path = self.path(key);
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(".partial");
+        if tokio::fs::write(&partial, content).await.is_ok() {
+            let _ = tokio::fs::rename(&partial, &path).await;
+        }
+    }
+}
+
+fn unix_time() -> u64 {
+    return SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+}
@@ -0,0 +1,55 @@
+use super::*;
+
+#[test]
+fn test_cache_key() {
+    let a =
+        Url::parse("https://export.arxiv.org/api/query?search_query=ti:llm&start=0&max_results=10")
+            .unwrap();
+    let b =
+        Url::parse("https://export.arxiv.org/api/query?max_results=10&search_query=ti:llm&start=0")
+            .unwrap();
+    assert_eq!(cache_key(&a), cache_key(&b));
+    assert_eq!(
+        cache_key(&a),
+        "https://export.arxiv.org/api/query?max_results=10&search_query=ti%3Allm&start=0"
+    );
+
+    let c = Url::parse("https://export.arxiv.org/api/query?search_query=ti:llm&start=10").unwrap();
+    assert_ne!(cache_key(&a), cache_key(&c));
+}
+
+#[tokio::test]
+async fn test_memory_cache() {
+    let cache = MemoryCache::new();
+    cache
+        .put("a", Bytes::from_static(b"fresh"), Duration::from_secs(60))
+        .await;
+    cache
+        .put("b", Bytes::from_static(b"stale"), Duration::ZERO)
+        .await;
+    assert_eq!(cache.get("a").await, Some(Bytes::from_static(b"fresh")));
+    assert_eq!(cache.get("b").await, None);
+    assert_eq!(cache.get("c").await, None);
+
+    cache.purge();
+    assert_eq!(cache.entries.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_file_cache() {
+    let dir = std::env::temp_dir().join(format!("arxiv_tools_test_cache_{}", std::process::id()));
+    let cache = FileCache::new(&dir).unwrap();
+    let body = Bytes::from_static(b"<feed>\n</feed>");
+    cache.put("a", body.clone(), Duration::from_secs(60)).await;
+    cache.put("b", body.clone(), Duration::ZERO).await;
+    assert_eq!(cache.get("a").await, Some(body.clone()));
+    assert_eq!(cache.get("b").await, None);
+    // file names don't depend on the Rust release
+    assert!(dir.join("af63dc4c8601ec8c.cache").exists());
+
+    // entries survive a new cache on the same directory
+    let reopened = FileCache::new(&dir).unwrap();
+    assert_eq!(reopened.get("a").await, Some(body));
+    assert_eq!(reopened.get("c").await, None);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,660 @@
+//! # Categories
+//! The arXiv subject classification, see <https://arxiv.org/category_taxonomy>.
+//!
+//! Categories convert to and from their arXiv codes. Codes outside the taxonomy are kept as [`Category::Other`].
+//! ```rust
+//! # use arxiv_tools::Category;
+//! let category: Category = "cs.CL".parse().unwrap();
+//! assert_eq!(category, Category::CsCl);
+//! assert_eq!(category.to_string(), "cs.CL");
+//!
+//! let legacy: Category = "cmp-lg".parse().unwrap();
+//! assert_eq!(legacy, Category::Other(String::from("cmp-lg")));
+//! ```
+use crate::{Paper, Scope};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+/// Categories that are aliases of another category, and subsumed legacy archives, with the code they map to.
+const ALIASES: &[(&str, &str)] = &[
+    ("cs.NA", "math.NA"),
+    ("cs.SY", "eess.SY"),
+    ("math.IT", "cs.IT"),
+    ("math.MP", "math-ph"),
+    ("q-fin.EC", "econ.GN"),
+    ("stat.TH", "math.ST"),
+    ("acc-phys", "physics.acc-ph"),
+    ("adap-org", "nlin.AO"),
+    ("alg-geom", "math.AG"),
+    ("ao-sci", "physics.ao-ph"),
+    ("atom-ph", "physics.atom-ph"),
+    ("bayes-an", "physics.data-an"),
+    ("chao-dyn", "nlin.CD"),
+    ("chem-ph", "physics.chem-ph"),
+    ("cmp-lg", "cs.CL"),
+    ("comp-gas", "nlin.CG"),
+    ("dg-ga", "math.DG"),
+    ("funct-an", "math.FA"),
+    ("mtrl-th", "cond-mat.mtrl-sci"),
+    ("patt-sol", "nlin.PS"),
+    ("plasm-ph", "physics.plasm-ph"),
+    ("q-alg", "math.QA"),
+    ("solv-int", "nlin.SI"),
+    ("supr-con", "cond-mat.supr-con"),
+];
+
+macro_rules! categories {
+    ($($variant:ident => $code:literal, $name:literal, $description:literal,)*) => {
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum Category {
+            $($variant,)*
+            /// A category outside the taxonomy, e.g. a legacy archive such as `cmp-lg`.
+            Other(String),
+        }
+
+        impl Category {
+            /// The arXiv code of the category, e.g. `cs.AI`.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Category::$variant => return $code,)*
+                    Category::Other(code) => return code,
+                }
+            }
+            /// The name of the category in the taxonomy, e.g. `Computation and Language` for `cs.CL`.
+            ///
+            /// Categories outside the taxonomy have no name, and their code is returned instead.
+            pub fn name(&self) -> &str {
+                match self {
+                    $(Category::$variant => return $name,)*
+                    Category::Other(code) => return code,
+                }
+            }
+            /// A short description of the subjects covered by the category, based on the taxonomy.
+            pub fn description(&self) -> Option<&'static str> {
+                match self {
+                    $(Category::$variant => return Some($description),)*
+                    Category::Other(_) => return None,
+                }
+            }
+        }
+
+        impl FromStr for Category {
+            type Err = Infallible;
+
+            fn from_str(code: &str) -> Result<Self, Self::Err> {
+                match code {
+                    $($code => return Ok(Category::$variant),)*
+                    _ => return Ok(Category::Other(code.to_string())),
+                }
+            }
+        }
+    };
+}
+
+categories! {
+    CsAi => "cs.AI", "Artificial Intelligence",
+        "Covers all areas of AI except Vision, Robotics, Machine Learning, Multiagent Systems, and Computation and Language, which have separate subject areas.",
+    CsAr => "cs.AR", "Hardware Architecture",
+        "Covers systems organization and hardware architecture.",
+    CsCc => "cs.CC", "Computational Complexity",
+        "Covers models of computation, complexity classes, structural complexity, complexity tradeoffs, and upper and lower bounds.",
+    CsCe => "cs.CE", "Computational Engineering, Finance, and Science",
+        "Covers applications of computer science to the mathematical modeling of complex systems in science, engineering, and finance.",
+    CsCg => "cs.CG", "Computational Geometry",
+        "Roughly includes material in ACM Subject Classes I.3.5 and F.2.2.",
+    CsCl => "cs.CL", "Computation and Language",
+        "Covers natural language processing, computational linguistics, and speech processing.",
+    CsCr => "cs.CR", "Cryptography and Security",
+        "Covers all areas of cryptography and security including authentication, public key cryptosystems, proof-carrying code, etc.",
+    CsCv => "cs.CV", "Computer Vision and Pattern Recognition",
+        "Covers image processing, computer vision, pattern recognition, and scene understanding.",
+    CsCy => "cs.CY", "Computers and Society",
+        "Covers impact of computers on society, computer ethics, information technology and public policy, legal aspects of computing, and computers and education.",
+    CsDb => "cs.DB", "Databases",
+        "Covers database management, datamining, and data processing.",
+    CsDc => "cs.DC", "Distributed, Parallel, and Cluster Computing",
+        "Covers fault-tolerance, distributed algorithms, stability, parallel computation, and cluster computing.",
+    CsDl => "cs.DL", "Digital Libraries",
+        "Covers all aspects of the digital library design and document and text creation.",
+    CsDm => "cs.DM", "Discrete Mathematics",
+        "Covers combinatorics, graph theory, and applications of probability.",
+    CsDs => "cs.DS", "Data Structures and Algorithms",
+        "Covers data structures and analysis of algorithms.",
+    CsEt => "cs.ET", "Emerging Technologies",
+        "Covers approaches to information processing based on alternatives to silicon CMOS-based technologies, such as quantum, optical, and molecular computing.",
+    CsFl => "cs.FL", "Formal Languages and Automata Theory",
+        "Covers automata theory, formal language theory, grammars, and combinatorics on words.",
+    CsGl => "cs.GL", "General Literature",
+        "Covers introductory material, survey material, predictions of future trends, biographies, and miscellaneous computer-science related material.",
+    CsGr => "cs.GR", "Graphics",
+        "Covers all aspects of computer graphics.",
+    CsGt => "cs.GT", "Computer Science and Game Theory",
+        "Covers all theoretical and applied aspects at the intersection of computer science and game theory, including mechanism design and learning in games.",
+    CsHc => "cs.HC", "Human-Computer Interaction",
+        "Covers human factors, user interfaces, and collaborative computing.",
+    CsIr => "cs.IR", "Information Retrieval",
+        "Covers indexing, dictionaries, retrieval, content and analysis.",
+    CsIt => "cs.IT", "Information Theory",
+        "Covers theoretical and experimental aspects of information theory and coding.",
+    CsLg => "cs.LG", "Machine Learning",
+        "Papers on all aspects of machine learning research, including supervised, unsupervised, and reinforcement learning, and learning theory.",
+    CsLo => "cs.LO", "Logic in Computer Science",
+        "Covers all aspects of logic in computer science, including finite model theory, logics of programs, modal logic, and program verification.",
+    CsMa => "cs.MA", "Multiagent Systems",
+        "Covers multiagent systems, distributed artificial intelligence, intelligent agents, and coordinated interactions.",
+    CsMm => "cs.MM", "Multimedia",
+        "Roughly includes material in ACM Subject Class H.5.1.",
+    CsMs => "cs.MS", "Mathematical Software",
+        "Roughly includes material in ACM Subject Class G.4.",
+    CsNa => "cs.NA", "Numerical Analysis",
+        "An alias for math.NA.",
+    CsNe => "cs.NE", "Neural and Evolutionary Computing",
+        "Covers neural networks, connectionism, genetic algorithms, artificial life, and adaptive behavior.",
+    CsNi => "cs.NI", "Networking and Internet Architecture",
+        "Covers all aspects of computer communication networks, including network architecture and design, network protocols, and internetwork standards.",
+    CsOh => "cs.OH", "Other Computer Science",
+        "For documents that do not fit anywhere else.",
+    CsOs => "cs.OS", "Operating Systems",
+        "Roughly includes material in ACM Subject Classes D.4.1, D.4.2, D.4.3, D.4.4, D.4.5, D.4.7, and D.4.9.",
+    CsPf => "cs.PF", "Performance",
+        "Covers performance measurement and evaluation, queueing, and simulation.",
+    CsPl => "cs.PL", "Programming Languages",
+        "Covers programming language semantics, language features, programming approaches, compilers, and program transformation.",
+    CsRo => "cs.RO", "Robotics",
+        "Roughly includes material in ACM Subject Class I.2.9.",
+    CsSc => "cs.SC", "Symbolic Computation",
+        "Roughly includes material in ACM Subject Class I.1.",
+    CsSd => "cs.SD", "Sound",
+        "Covers all aspects of computing with sound, and sound as an information channel.",
+    CsSe => "cs.SE", "Software Engineering",
+        "Covers design tools, software metrics, testing and debugging, programming environments, etc.",
+    CsSi => "cs.SI", "Social and Information Networks",
+        "Covers the design, analysis, and modeling of social and information networks, including their applications for on-line information access, communication, and interaction.",
+    CsSy => "cs.SY", "Systems and Control",
+        "An alias for eess.SY.",
+    EconEm => "econ.EM", "Econometrics",
+        "Econometric theory, micro-econometrics, macro-econometrics, and empirical content of economic relations discovered via new methods.",
+    EconGn => "econ.GN", "General Economics",
+        "General methodological, applied, and empirical contributions to economics.",
+    EconTh => "econ.TH", "Theoretical Economics",
+        "Includes theoretical contributions to contract theory, decision theory, game theory, general equilibrium, growth, learning and evolution, macroeconomics, and market and mechanism design.",
+    EessAs => "eess.AS", "Audio and Speech Processing",
+        "Theory and methods for processing signals representing audio, speech, and language, and their applications.",
+    EessIv => "eess.IV", "Image and Video Processing",
+        "Theory, algorithms, and architectures for the formation, capture, processing, communication, analysis, and display of images, video, and multidimensional signals.",
+    EessSp => "eess.SP", "Signal Processing",
+        "Theory, algorithms, performance analysis and applications of signal and data analysis.",
+    EessSy => "eess.SY", "Systems and Control",
+        "Theoretical and experimental research covering all facets of automatic control systems.",
+    MathAc => "math.AC", "Commutative Algebra",
+        "Commutative rings, modules, ideals, homological algebra, computational aspects, invariant theory, and connections to algebraic geometry and combinatorics.",
+    MathAg => "math.AG", "Algebraic Geometry",
+        "Algebraic varieties, stacks, sheaves, schemes, moduli spaces, complex geometry, and quantum cohomology.",
+    MathAp => "math.AP", "Analysis of PDEs",
+        "Existence and uniqueness, boundary conditions, linear and non-linear operators, stability, soliton theory, integrable PDEs, and conservation laws.",
+    MathAt => "math.AT", "Algebraic Topology",
+        "Homotopy theory, homological algebra, and algebraic treatments of manifolds.",
+    MathCa => "math.CA", "Classical Analysis and ODEs",
+        "Special functions, orthogonal polynomials, harmonic analysis, ODEs, differential relations, calculus of variations, approximations, and integral transforms.",
+    MathCo => "math.CO", "Combinatorics",
+        "Discrete mathematics, graph theory, enumeration, combinatorial optimization, Ramsey theory, and combinatorial game theory.",
+    MathCt => "math.CT", "Category Theory",
+        "Enriched categories, topoi, abelian categories, monoidal categories, and homological algebra.",
+    MathCv => "math.CV", "Complex Variables",
+        "Holomorphic functions, automorphic group actions and forms, pseudoconvexity, complex geometry, and analytic spaces.",
+    MathDg => "math.DG", "Differential Geometry",
+        "Complex, contact, Riemannian, pseudo-Riemannian and Finsler geometry, relativity, gauge theory, and global analysis.",
+    MathDs => "math.DS", "Dynamical Systems",
+        "Dynamics of differential equations and flows, mechanics, classical few-body problems, iterations, complex dynamics, and delayed differential equations.",
+    MathFa => "math.FA", "Functional Analysis",
+        "Banach spaces, function spaces, real functions, integral transforms, theory of distributions, and measure theory.",
+    MathGm => "math.GM", "General Mathematics",
+        "Mathematical material of general interest, and topics not covered elsewhere.",
+    MathGn => "math.GN", "General Topology",
+        "Continuum theory, point-set topology, spaces with algebraic structure, foundations, and dimension theory.",
+    MathGr => "math.GR", "Group Theory",
+        "Finite groups, topological groups, representation theory, cohomology, classification and structure.",
+    MathGt => "math.GT", "Geometric Topology",
+        "Manifolds, orbifolds, polyhedra, cell complexes, foliations, and geometric structures.",
+    MathHo => "math.HO", "History and Overview",
+        "Biographies, philosophy of mathematics, mathematics education, recreational mathematics, and communication of mathematics.",
+    MathIt => "math.IT", "Information Theory",
+        "An alias for cs.IT.",
+    MathKt => "math.KT", "K-Theory and Homology",
+        "Algebraic and topological K-theory, relations with topology, commutative algebra, and operator algebras.",
+    MathLo => "math.LO", "Logic",
+        "Logic, set theory, point-set topology, and formal mathematics.",
+    MathMg => "math.MG", "Metric Geometry",
+        "Euclidean, hyperbolic, discrete, convex, and coarse geometry, comparisons in Riemannian geometry, and symmetric spaces.",
+    MathMp => "math.MP", "Mathematical Physics",
+        "An alias for math-ph.",
+    MathNa => "math.NA", "Numerical Analysis",
+        "Numerical algorithms for problems in analysis and algebra, and scientific computation.",
+    MathNt => "math.NT", "Number Theory",
+        "Prime numbers, diophantine equations, analytic number theory, algebraic number theory, arithmetic geometry, and Galois theory.",
+    MathOa => "math.OA", "Operator Algebras",
+        "Algebras of operators on Hilbert space, C*-algebras, von Neumann algebras, and non-commutative geometry.",
+    MathOc => "math.OC", "Optimization and Control",
+        "Operations research, linear programming, control theory, systems theory, optimal control, and game theory.",
+    MathPr => "math.PR", "Probability",
+        "Theory and applications of probability and stochastic processes, e.g. central limit theorems, large deviations, stochastic differential equations, and models from statistical mechanics and queuing theory.",
+    MathQa => "math.QA", "Quantum Algebra",
+        "Quantum groups, skein theories, operadic and diagrammatic algebra, and quantum field theory.",
+    MathRa => "math.RA", "Rings and Algebras",
+        "Non-commutative rings and algebras, non-associative algebras, universal algebra and lattice theory, linear algebra, and semigroups.",
+    MathRt => "math.RT", "Representation Theory",
+        "Linear representations of algebras and groups, Lie theory, associative algebras, and multilinear algebra.",
+    MathSg => "math.SG", "Symplectic Geometry",
+        "Hamiltonian systems, symplectic flows, and classical integrable systems.",
+    MathSp => "math.SP", "Spectral Theory",
+        "Schrodinger operators, operators on manifolds, general differential operators, numerical studies, integral operators, and discrete models.",
+    MathSt => "math.ST", "Statistics Theory",
+        "Applied, computational and theoretical statistics, e.g. statistical inference, regression, time series, multivariate analysis, data analysis, and Markov chain Monte Carlo.",
+    AstroPhCo => "astro-ph.CO", "Cosmology and Nongalactic Astrophysics",
+        "Phenomenology of early universe, cosmic microwave background, cosmological parameters, primordial element abundances, large-scale structure, and dark energy and matter.",
+    AstroPhEp => "astro-ph.EP", "Earth and Planetary Astrophysics",
+        "Interplanetary medium, planetary physics, planetary astrobiology, extrasolar planets, comets, asteroids, and meteorites.",
+    AstroPhGa => "astro-ph.GA", "Astrophysics of Galaxies",
+        "Phenomena pertaining to galaxies or the Milky Way, including star clusters, interstellar medium, galactic structure, and active galactic nuclei.",
+    AstroPhHe => "astro-ph.HE", "High Energy Astrophysical Phenomena",
+        "Cosmic ray production, gamma ray astronomy, supernovae, neutron stars, black holes, gamma ray bursts, and gravitational radiation.",
+    AstroPhIm => "astro-ph.IM", "Instrumentation and Methods for Astrophysics",
+        "Detector and telescope design, experiment proposals, laboratory astrophysics, and methods for data analysis, statistical methods, and software.",
+    AstroPhSr => "astro-ph.SR", "Solar and Stellar Astrophysics",
+        "White dwarfs, brown dwarfs, stellar evolution, stellar populations, the Sun and the heliosphere, and variable stars.",
+    CondMatDisNn => "cond-mat.dis-nn", "Disordered Systems and Neural Networks",
+        "Glasses and spin glasses, properties of random, aperiodic and quasiperiodic systems, transport in disordered media, localization, and neural networks.",
+    CondMatMesHall => "cond-mat.mes-hall", "Mesoscale and Nanoscale Physics",
+        "Semiconducting nanostructures, quantum dots, wires, and wells, single electronics, spintronics, and the quantum Hall effect.",
+    CondMatMtrlSci => "cond-mat.mtrl-sci", "Materials Science",
+        "Techniques, synthesis, characterization, structure, structural phase transitions, mechanical properties, and phonons.",
+    CondMatOther => "cond-mat.other", "Other Condensed Matter",
+        "Work in condensed matter that does not fit into the other cond-mat classifications.",
+    CondMatQuantGas => "cond-mat.quant-gas", "Quantum Gases",
+        "Ultracold atomic and molecular gases, Bose-Einstein condensation, Feshbach resonances, and quantum simulation with cold atoms and molecules.",
+    CondMatSoft => "cond-mat.soft", "Soft Condensed Matter",
+        "Membranes, polymers, liquid crystals, glasses, colloids, and granular matter.",
+    CondMatStatMech => "cond-mat.stat-mech", "Statistical Mechanics",
+        "Phase transitions, thermodynamics, field theory, non-equilibrium phenomena, renormalization group and scaling, and integrable models.",
+    CondMatStrEl => "cond-mat.str-el", "Strongly Correlated Electrons",
+        "Quantum magnetism, non-Fermi liquids, spin liquids, quantum criticality, charge density waves, and metal-insulator transitions.",
+    CondMatSuprCon => "cond-mat.supr-con", "Superconductivity",
+        "Superconductivity: theory, models, experiment, and superflow in helium.",
+    GrQc => "gr-qc", "General Relativity and Quantum Cosmology",
+        "Gravitational physics, including experiments and observations related to the detection and interpretation of gravitational waves, and quantum gravity.",
+    HepEx => "hep-ex", "High Energy Physics - Experiment",
+        "Results from high-energy and particle physics experiments and prospects for future experimental results.",
+    HepLat => "hep-lat", "High Energy Physics - Lattice",
+        "Lattice field theory, phenomenology from lattice field theory, algorithms for lattice field theory, and hardware for lattice field theory.",
+    HepPh => "hep-ph", "High Energy Physics - Phenomenology",
+        "Theoretical particle physics and its interrelation with experiment.",
+    HepTh => "hep-th", "High Energy Physics - Theory",
+        "Formal aspects of quantum field theory, string theory, supersymmetry, and supergravity.",
+    MathPh => "math-ph", "Mathematical Physics",
+        "Articles in which mathematics is applied to physics or mathematical methods are developed for such applications.",
+    NlinAo => "nlin.AO", "Adaptation and Self-Organizing Systems",
+        "Adaptation, self-organizing systems, statistical physics, fluctuating systems, stochastic processes, interacting particle systems, and machine learning.",
+    NlinCd => "nlin.CD", "Chaotic Dynamics",
+        "Dynamical systems, chaos, quantum chaos, topological dynamics, cycle expansions, turbulence, and propagation.",
+    NlinCg => "nlin.CG", "Cellular Automata and Lattice Gases",
+        "Computational methods, time series analysis, signal processing, wavelets, and lattice gases.",
+    NlinPs => "nlin.PS", "Pattern Formation and Solitons",
+        "Pattern formation, coherent structures, and solitons.",
+    NlinSi => "nlin.SI", "Exactly Solvable and Integrable Systems",
+        "Exactly solvable systems, integrable PDEs, integrable ODEs, Painleve analysis, integrable discrete maps, and solvable lattice models.",
+    NuclEx => "nucl-ex", "Nuclear Experiment",
+        "Results from experimental nuclear physics including the areas of fundamental interactions, nuclear structure, and heavy-ion collisions.",
+    NuclTh => "nucl-th", "Nuclear Theory",
+        "Theory of nuclear structure covering wave functions, nuclear forces, nuclear astrophysics, and heavy-ion collisions.",
+    PhysicsAccPh => "physics.acc-ph", "Accelerator Physics",
+        "Accelerator theory and simulation, accelerator technology, and accelerator experiments.",
+    PhysicsAoPh => "physics.ao-ph", "Atmospheric and Oceanic Physics",
+        "Atmospheric and oceanic physics and physical chemistry, biogeophysics, and climate science.",
+    PhysicsAppPh => "physics.app-ph", "Applied Physics",
+        "Applications of physics to new technology, including electronic devices, optics, photonics, microwaves, and materials applications.",
+    PhysicsAtmClus => "physics.atm-clus", "Atomic and Molecular Clusters",
+        "Atomic and molecular clusters, nanoparticles, and their geometric, electronic, and optical properties.",
+    PhysicsAtomPh => "physics.atom-ph", "Atomic Physics",
+        "Atomic and molecular structure, spectra, collisions, and data, and atoms and molecules in external fields.",
+    PhysicsBioPh => "physics.bio-ph", "Biological Physics",
+        "Molecular biophysics, cellular biophysics, neurological biophysics, and membrane biophysics.",
+    PhysicsChemPh => "physics.chem-ph", "Chemical Physics",
+        "Experimental, computational, and theoretical physics of atoms, molecules, and clusters.",
+    PhysicsClassPh => "physics.class-ph", "Classical Physics",
+        "Newtonian and relativistic dynamics, many particle systems, classical electromagnetism, thermodynamics, and fluids.",
+    PhysicsCompPh => "physics.comp-ph", "Computational Physics",
+        "All aspects of computational science applied to physics.",
+    PhysicsDataAn => "physics.data-an", "Data Analysis, Statistics and Probability",
+        "Methods, software and hardware for physics data analysis, and statistical and probabilistic methods for physics.",
+    PhysicsEdPh => "physics.ed-ph", "Physics Education",
+        "Report of results of a research study, laboratory experience, assessment or classroom practice that advances teaching and learning of physics.",
+    PhysicsFluDyn => "physics.flu-dyn", "Fluid Dynamics",
+        "Turbulence, instabilities, incompressible and compressible flows, boundary layers, and multiphase and granular flows.",
+    PhysicsGenPh => "physics.gen-ph", "General Physics",
+        "Work in physics that does not fit into the other physics classifications.",
+    PhysicsGeoPh => "physics.geo-ph", "Geophysics",
+        "Atmospheric physics, biogeosciences, computational geophysics, geodesy, seismology, and space plasma physics.",
+    PhysicsHistPh => "physics.hist-ph", "History and Philosophy of Physics",
+        "History and philosophy of all branches of physics, astrophysics, and cosmology, including appreciations of physicists.",
+    PhysicsInsDet => "physics.ins-det", "Instrumentation and Detectors",
+        "Instrumentation and detectors for research in natural science, including optical, molecular, atomic, nuclear, and particle physics instrumentation.",
+    PhysicsMedPh => "physics.med-ph", "Medical Physics",
+        "Radiation therapy, radiation dosimetry, biomedical imaging modelling, reconstruction, processing, and analysis.",
+    PhysicsOptics => "physics.optics", "Optics",
+        "Adaptive optics, astronomical optics, atmospheric optics, biomedical optics, fiber optics, nonlinear optics, optoelectronics, and photonics.",
+    PhysicsPlasmPh => "physics.plasm-ph", "Plasma Physics",
+        "Fundamental plasma physics, magnetically and inertially confined plasmas, astrophysical plasmas, and plasma applications.",
+    PhysicsPopPh => "physics.pop-ph", "Popular Physics",
+        "Physics written for a general audience.",
+    PhysicsSocPh => "physics.soc-ph", "Physics and Society",
+        "Structure, dynamics and collective behavior of societies and groups, and the physics of social systems and networks.",
+    PhysicsSpacePh => "physics.space-ph", "Space Physics",
+        "Space plasma physics, heliophysics, space weather, planetary magnetospheres, ionospheres and magnetotail, and auroras.",
+    QuantPh => "quant-ph", "Quantum Physics",
+        "Quantum mechanics, quantum information, and quantum computation.",
+    QBioBm => "q-bio.BM", "Biomolecules",
+        "DNA, RNA, proteins, lipids, etc., and molecular structures and folding kinetics.",
+    QBioCb => "q-bio.CB", "Cell Behavior",
+        "Cell-cell signaling and interaction, morphogenesis and development, apoptosis, bacterial conjugation, and immunology.",
+    QBioGn => "q-bio.GN", "Genomics",
+        "DNA sequencing and assembly, gene and motif finding, RNA editing and alternative splicing, and genomic structure and processes.",
+    QBioMn => "q-bio.MN", "Molecular Networks",
+        "Gene regulation, signal transduction, proteomics, metabolomics, and gene and enzymatic networks.",
+    QBioNc => "q-bio.NC", "Neurons and Cognition",
+        "Synapse, cortex, neuronal dynamics, neural network, sensorimotor control, behavior, and attention.",
+    QBioOt => "q-bio.OT", "Other Quantitative Biology",
+        "Work in quantitative biology that does not fit into the other q-bio classifications.",
+    QBioPe => "q-bio.PE", "Populations and Evolution",
+        "Population dynamics, spatio-temporal and epidemiological models, dynamic speciation, co-evolution, and biodiversity.",
+    QBioQm => "q-bio.QM", "Quantitative Methods",
+        "All experimental, numerical, statistical and mathematical contributions of value to biology.",
+    QBioSc => "q-bio.SC", "Subcellular Processes",
+        "Assembly and control of subcellular structures, molecular motors, transport, and subcellular localization.",
+    QBioTo => "q-bio.TO", "Tissues and Organs",
+        "Blood flow in vessels, biomechanics of bones, electrical waves, endocrine system, and tumor growth.",
+    QFinCp => "q-fin.CP", "Computational Finance",
+        "Computational methods, including Monte Carlo, PDE, lattice and other numerical methods with applications to financial modeling.",
+    QFinEc => "q-fin.EC", "Economics",
+        "An alias for econ.GN.",
+    QFinGn => "q-fin.GN", "General Finance",
+        "Development of general quantitative methodologies with applications in finance.",
+    QFinMf => "q-fin.MF", "Mathematical Finance",
+        "Mathematical and analytical methods of finance, including stochastic, probabilistic and functional analysis, and optimization.",
+    QFinPm => "q-fin.PM", "Portfolio Management",
+        "Security selection and optimization, capital allocation, investment strategies, and performance measurement.",
+    QFinPr => "q-fin.PR", "Pricing of Securities",
+        "Valuation and hedging of financial securities, their derivatives, and structured products.",
+    QFinRm => "q-fin.RM", "Risk Management",
+        "Measurement and management of financial risks in trading, banking, insurance, corporate and other applications.",
+    QFinSt => "q-fin.ST", "Statistical Finance",
+        "Statistical, econometric and econophysics analyses with applications to financial markets and economic data.",
+    QFinTr => "q-fin.TR", "Trading and Market Microstructure",
+        "Market microstructure, liquidity, exchange and auction design, automated trading, agent-based modeling, and market-making.",
+    StatAp => "stat.AP", "Applications",
+        "Biology, education, epidemiology, engineering, environmental sciences, medical, physical sciences, quality control, and social sciences.",
+    StatCo => "stat.CO", "Computation",
+        "Algorithms, simulation, and visualization.",
+    StatMe => "stat.ME", "Methodology",
+        "Design, surveys, model selection, multiple testing, multivariate methods, signal and image processing, time series, smoothing, and spatial statistics.",
+    StatMl => "stat.ML", "Machine Learning",
+        "Covers machine learning papers with a statistical or theoretical grounding, e.g. supervised, unsupervised, and semi-supervised learning, graphical models, and high-dimensional inference.",
+    StatOt => "stat.OT", "Other Statistics",
+        "Work in statistics that does not fit into the other stat classifications.",
+    StatTh => "stat.TH", "Statistics Theory",
+        "Asymptotics, Bayesian inference, decision theory, estimation, foundations, inference, and testing.",
+}
+
+impl Category {
+    /// The archive the category belongs to, e.g. `math` for `math.AG`.
+    ///
+    /// Categories outside the taxonomy are matched by their prefix, so a legacy `astro-ph` is still in [`Archive::AstroPh`].
+    /// ```rust
+    /// # use arxiv_tools::{Archive, Category};
+    /// assert_eq!(Category::MathAg.archive(), Some(Archive::Math));
+    /// assert_eq!(Category::Other(String::from("cmp-lg")).archive(), None);
+    /// ```
+    pub fn archive(&self) -> Option<Archive> {
+        let code = self.as_str();
+        let prefix = code.split_once('.').map_or(code, |(archive, _)| archive);
+        return Archive::from_code(prefix);
+    }
+}
+
+impl Category {
+    /// The canonical form of an alias or a subsumed legacy category, e.g. `math-ph` for `math.MP`.
+    ///
+    /// Other categories are returned unchanged.
+    /// ```rust
+    /// # use arxiv_tools::Category;
+    /// assert_eq!(Category::CsNa.canonicalize(), Category::MathNa);
+    /// assert_eq!("solv-int".parse::<Category>().unwrap().canonicalize(), Category::NlinSi);
+    /// assert_eq!(Category::CsCl.canonicalize(), Category::CsCl);
+    /// ```
+    pub fn canonicalize(&self) -> Category {
+        let code = self.as_str();
+        return match ALIASES.iter().find(|(alias, _)| *alias == code) {
+            Some((_, canonical)) => canonical.parse().unwrap(),
+            None => self.clone(),
+        };
+    }
+    /// Whether both categories are the same once aliases are resolved.
+    pub fn is_equivalent(&self, other: &Category) -> bool {
+        return self.canonicalize() == other.canonicalize();
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.write_str(self.as_str());
+    }
+}
+
+/// A top-level archive of the taxonomy, grouping its categories, e.g. `cs` or `math`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Archive {
+    Cs,
+    Econ,
+    Eess,
+    Math,
+    AstroPh,
+    CondMat,
+    GrQc,
+    HepEx,
+    HepLat,
+    HepPh,
+    HepTh,
+    MathPh,
+    Nlin,
+    NuclEx,
+    NuclTh,
+    Physics,
+    QuantPh,
+    QBio,
+    QFin,
+    Stat,
+}
+
+impl Archive {
+    /// The arXiv code of the archive, e.g. `astro-ph`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Archive::Cs => return "cs",
+            Archive::Econ => return "econ",
+            Archive::Eess => return "eess",
+            Archive::Math => return "math",
+            Archive::AstroPh => return "astro-ph",
+            Archive::CondMat => return "cond-mat",
+            Archive::GrQc => return "gr-qc",
+            Archive::HepEx => return "hep-ex",
+            Archive::HepLat => return "hep-lat",
+            Archive::HepPh => return "hep-ph",
+            Archive::HepTh => return "hep-th",
+            Archive::MathPh => return "math-ph",
+            Archive::Nlin => return "nlin",
+            Archive::NuclEx => return "nucl-ex",
+            Archive::NuclTh => return "nucl-th",
+            Archive::Physics => return "physics",
+            Archive::QuantPh => return "quant-ph",
+            Archive::QBio => return "q-bio",
+            Archive::QFin => return "q-fin",
+            Archive::Stat => return "stat",
+        }
+    }
+    /// The archive with the code `code`, e.g. `Archive::AstroPh` for `astro-ph`.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "cs" => return Some(Archive::Cs),
+            "econ" => return Some(Archive::Econ),
+            "eess" => return Some(Archive::Eess),
+            "math" => return Some(Archive::Math),
+            "astro-ph" => return Some(Archive::AstroPh),
+            "cond-mat" => return Some(Archive::CondMat),
+            "gr-qc" => return Some(Archive::GrQc),
+            "hep-ex" => return Some(Archive::HepEx),
+            "hep-lat" => return Some(Archive::HepLat),
+            "hep-ph" => return Some(Archive::HepPh),
+            "hep-th" => return Some(Archive::HepTh),
+            "math-ph" => return Some(Archive::MathPh),
+            "nlin" => return Some(Archive::Nlin),
+            "nucl-ex" => return Some(Archive::NuclEx),
+            "nucl-th" => return Some(Archive::NuclTh),
+            "physics" => return Some(Archive::Physics),
+            "quant-ph" => return Some(Archive::QuantPh),
+            "q-bio" => return Some(Archive::QBio),
+            "q-fin" => return Some(Archive::QFin),
+            "stat" => return Some(Archive::Stat),
+            _ => return None,
+        }
+    }
+    /// The `cat:` term matching every category of the archive: `cs.*` for divided archives, the code itself otherwise.
+    pub(crate) fn pattern(&self) -> String {
+        match self {
+            Archive::Cs
+            | Archive::Econ
+            | Archive::Eess
+            | Archive::Math
+            | Archive::AstroPh
+            | Archive::CondMat
+            | Archive::Nlin
+            | Archive::Physics
+            | Archive::QBio
+            | Archive::QFin
+            | Archive::Stat => {
+                return format!("{}.*", self.as_str());
+            }
+            _ => return self.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Archive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.write_str(self.as_str());
+    }
+}
+
+/// Serialized as the arXiv code, e.g. `"cs.CL"`.
+impl Serialize for Category {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.serialize_str(self.as_str());
+    }
+}
+
+impl<'de> Deserialize<'de> for Category {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        return Ok(code.parse().unwrap());
+    }
+}
+
+/// A named bundle of categories, e.g. a subscription to a field.
+///
+/// Curated bundles are provided for common fields; others are built with [`CategorySet::new`].
+/// ```rust
+/// # use arxiv_tools::{CategorySet, Category, QueryParams};
+/// let args = QueryParams::category_set(&CategorySet::ml());
+/// assert_eq!(args.to_string(), "(cat:cs.LG OR cat:cs.AI OR cat:stat.ML)");
+///
+/// let theory = CategorySet::new("theory", vec![Category::CsCc, Category::CsDs]);
+/// assert!(theory.contains(&Category::CsDs));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CategorySet {
+    pub name: String,
+    pub categories: Vec<Category>,
+}
+
+impl CategorySet {
+    pub fn new(name: &str, categories: Vec<Category>) -> Self {
+        return CategorySet {
+            name: name.to_string(),
+            categories,
+        };
+    }
+    /// `cs.LG`, `cs.AI` and `stat.ML`.
+    pub fn ml() -> Self {
+        return CategorySet::from(Scope::MachineLearning);
+    }
+    /// `cs.CL`.
+    pub fn nlp() -> Self {
+        return CategorySet::from(Scope::NLP);
+    }
+    /// `cs.CV` and `eess.IV`.
+    pub fn vision() -> Self {
+        return CategorySet::from(Scope::ComputerVision);
+    }
+    /// `cs.RO`.
+    pub fn robotics() -> Self {
+        return CategorySet::from(Scope::Robotics);
+    }
+    /// `hep-ex`, `hep-lat`, `hep-ph` and `hep-th`.
+    pub fn hep() -> Self {
+        return CategorySet::new(
+            "hep",
+            vec![
+                Category::HepEx,
+                Category::HepLat,
+                Category::HepPh,
+                Category::HepTh,
+            ],
+        );
+    }
+    /// `quant-ph`, `cs.ET` and `cond-mat.quant-gas`.
+    pub fn quantum() -> Self {
+        return CategorySet::new(
+            "quantum",
+            vec![Category::QuantPh, Category::CsEt, Category::CondMatQuantGas],
+        );
+    }
+    /// Whether `category` or one of its aliases is in the set.
+    pub fn contains(&self, category: &Category) -> bool {
+        return self
+            .categories
+            .iter()
+            .any(|member| member.is_equivalent(category));
+    }
+    /// Whether `paper` is listed in any category of the set.
+    pub fn matches(&self, paper: &Paper) -> bool {
+        return paper
+            .parsed_categories()
+            .iter()
+            .any(|category| self.contains(category));
+    }
+}
+
+impl From<Scope> for CategorySet {
+    fn from(scope: Scope) -> Self {
+        let name = match scope {
+            Scope::MachineLearning => "ml",
+            Scope::NLP => "nlp",
+            Scope::ComputerVision => "vision",
+            Scope::Robotics => "robotics",
+        };
+        return CategorySet::new(name, scope.categories());
+    }
+}
+
+#[cfg(test)]
+mod tests;
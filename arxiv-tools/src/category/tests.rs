@@ -0,0 +1,117 @@
+use super::*;
+
+#[test]
+fn test_category_codes() {
+    assert_eq!(Category::CsAi.to_string(), "cs.AI");
+    assert_eq!(Category::MathAg.to_string(), "math.AG");
+    assert_eq!(Category::AstroPhCo.to_string(), "astro-ph.CO");
+    assert_eq!(Category::CondMatStatMech.to_string(), "cond-mat.stat-mech");
+    assert_eq!(Category::HepTh.to_string(), "hep-th");
+    assert_eq!(Category::PhysicsFluDyn.to_string(), "physics.flu-dyn");
+    assert_eq!(Category::QBioNc.to_string(), "q-bio.NC");
+    assert_eq!(Category::QFinSt.to_string(), "q-fin.ST");
+    assert_eq!(Category::EconEm.to_string(), "econ.EM");
+}
+
+#[test]
+fn test_category_from_str() {
+    for category in [
+        Category::CsCl,
+        Category::MathPh,
+        Category::CondMatMesHall,
+        Category::QuantPh,
+    ] {
+        assert_eq!(category.to_string().parse::<Category>().unwrap(), category);
+    }
+    assert_eq!(
+        "solv-int".parse::<Category>().unwrap(),
+        Category::Other(String::from("solv-int"))
+    );
+    assert_eq!(
+        Category::Other(String::from("solv-int")).to_string(),
+        "solv-int"
+    );
+}
+
+#[test]
+fn test_archive_pattern() {
+    assert_eq!(Archive::Math.pattern(), "math.*");
+    assert_eq!(Archive::CondMat.pattern(), "cond-mat.*");
+    assert_eq!(Archive::QuantPh.pattern(), "quant-ph");
+    assert_eq!(Archive::QBio.to_string(), "q-bio");
+}
+
+#[test]
+fn test_category_names() {
+    assert_eq!(Category::CsCl.name(), "Computation and Language");
+    assert_eq!(
+        Category::HepPh.name(),
+        "High Energy Physics - Phenomenology"
+    );
+    assert_eq!(Category::StatMl.name(), "Machine Learning");
+    assert!(Category::CsCl
+        .description()
+        .unwrap()
+        .contains("natural language processing"));
+
+    let other = Category::Other(String::from("cmp-lg"));
+    assert_eq!(other.name(), "cmp-lg");
+    assert_eq!(other.description(), None);
+}
+
+#[test]
+fn test_category_archive() {
+    assert_eq!(Category::CsCl.archive(), Some(Archive::Cs));
+    assert_eq!(Category::CondMatSoft.archive(), Some(Archive::CondMat));
+    assert_eq!(Category::GrQc.archive(), Some(Archive::GrQc));
+    assert_eq!(
+        Category::Other(String::from("astro-ph")).archive(),
+        Some(Archive::AstroPh)
+    );
+    assert_eq!(Category::Other(String::from("solv-int")).archive(), None);
+    assert_eq!(Archive::from_code("q-fin"), Some(Archive::QFin));
+}
+
+#[test]
+fn test_category_canonicalize() {
+    assert_eq!(Category::MathMp.canonicalize(), Category::MathPh);
+    assert_eq!(Category::MathPh.canonicalize(), Category::MathPh);
+    assert_eq!(Category::StatTh.canonicalize(), Category::MathSt);
+    assert_eq!(
+        Category::Other(String::from("cmp-lg")).canonicalize(),
+        Category::CsCl
+    );
+    assert!(Category::CsSy.is_equivalent(&Category::EessSy));
+    assert!(!Category::CsSy.is_equivalent(&Category::CsSe));
+
+    for (alias, canonical) in ALIASES {
+        assert!(
+            !matches!(canonical.parse().unwrap(), Category::Other(_)),
+            "{}",
+            alias
+        );
+    }
+}
+
+#[test]
+fn test_category_set() {
+    let set = CategorySet::ml();
+    assert_eq!(set.name, "ml");
+    assert_eq!(
+        set.categories,
+        vec![Category::CsLg, Category::CsAi, Category::StatMl]
+    );
+
+    let numerics = CategorySet::new("numerics", vec![Category::MathNa]);
+    assert!(numerics.contains(&Category::CsNa));
+    let paper = Paper {
+        primary_category: "cs.NA".into(),
+        categories: vec!["cs.NA".into()],
+        ..Default::default()
+    };
+    assert!(numerics.matches(&paper));
+    assert!(!CategorySet::hep().matches(&paper));
+
+    let payload = serde_json::to_string(&numerics).unwrap();
+    assert_eq!(payload, r#"{"name":"numerics","categories":["math.NA"]}"#);
+}
@@ -0,0 +1,132 @@
+//! # Circuit Breaker
+//! An optional guard against hammering arXiv while it is down. After `threshold` consecutive failed requests
+//! the circuit opens, and requests fail at once with [`ArxivError::CircuitOpen`] until `cooldown` has elapsed.
+//! Then a single trial request is let through: if it succeeds the circuit closes, otherwise it opens again.
+//! A trial request that is cancelled before it completes, e.g. because its future was dropped, lets the next
+//! request through as a trial instead.
+//!
+//! ```rust
+//! # use arxiv_tools::{ArxivSession, CircuitBreaker};
+//! # use std::time::Duration;
+//! let session = ArxivSession::new();
+//! session.set_circuit_breaker(CircuitBreaker::new(5, Duration::from_secs(600)));
+//! ```
+//!
+//! Only outages count as failures: network errors and `5xx` or `429` responses. Rejected queries don't.
+use crate::ArxivError;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+/// Opens after a number of consecutive failures. Clones share the same state.
+#[derive(Clone, Debug)]
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    failures: u32,
+    /// Time the circuit opened, `None` while it is closed.
+    opened_at: Option<Instant>,
+    /// Whether the trial request after the cool-down is in flight.
+    trial: bool,
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        return CircuitBreaker {
+            threshold: threshold.max(1),
+            cooldown,
+            state: Arc::new(Mutex::new(State::default())),
+        };
+    }
+
+    /// Whether requests are currently short-circuited.
+    pub fn is_open(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        return match state.opened_at {
+            Some(opened_at) => state.trial || opened_at.elapsed() < self.cooldown,
+            None => false,
+        };
+    }
+
+    /// Allow a request, or fail with [`ArxivError::CircuitOpen`] while the circuit is open.
+    ///
+    /// The outcome of the request is recorded with [`Permit::record`]. A permit dropped before that ends the trial
+    /// it may hold without recording an outcome.
+    pub(crate) fn check(&self) -> Result<Permit, ArxivError> {
+        let mut state = self.state.lock().unwrap();
+        let Some(opened_at) = state.opened_at else {
+            return Ok(Permit {
+                breaker: self.clone(),
+                trial: false,
+            });
+        };
+        let elapsed = opened_at.elapsed();
+        if state.trial || elapsed < self.cooldown {
+            return Err(ArxivError::CircuitOpen {
+                retry_after: self.cooldown.saturating_sub(elapsed),
+            });
+        }
+        state.trial = true;
+        return Ok(Permit {
+            breaker: self.clone(),
+            trial: true,
+        });
+    }
+
+    /// Record the outcome of a request allowed by [`CircuitBreaker::check`].
+    fn record<T>(&self, result: &Result<T, ArxivError>) {
+        let mut state = self.state.lock().unwrap();
+        state.trial = false;
+        match result {
+            Err(e) if is_outage(e) => {
+                state.failures += 1;
+                if state.opened_at.is_some() || state.failures >= self.threshold {
+                    state.opened_at = Some(Instant::now());
+                }
+            }
+            _ => {
+                state.failures = 0;
+                state.opened_at = None;
+            }
+        }
+    }
+}
+
+/// A request allowed by [`CircuitBreaker::check`], possibly the trial request after the cool-down.
+#[derive(Debug)]
+pub(crate) struct Permit {
+    breaker: CircuitBreaker,
+    trial: bool,
+}
+
+impl Permit {
+    /// Record the outcome of the request.
+    pub(crate) fn record<T>(mut self, result: &Result<T, ArxivError>) {
+        self.trial = false;
+        self.breaker.record(result);
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if self.trial {
+            self.breaker.state.lock().unwrap().trial = false;
+        }
+    }
+}
+
+/// Whether `error` suggests that arXiv is unavailable rather than that the request was wrong.
+fn is_outage(error: &ArxivError) -> bool {
+    match error {
+        ArxivError::Http(_) => return true,
+        ArxivError::Status(status) => return *status >= 500 || *status == 429,
+        _ => return false,
+    }
+}
@@ -0,0 +1,67 @@
+use super::*;
+
+#[test]
+fn test_opens_after_threshold() {
+    let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+    breaker
+        .check()
+        .unwrap()
+        .record::<()>(&Err(ArxivError::Status(503)));
+    assert!(!breaker.is_open());
+    breaker
+        .check()
+        .unwrap()
+        .record::<()>(&Err(ArxivError::Status(503)));
+    assert!(breaker.is_open());
+    assert!(matches!(
+        breaker.check(),
+        Err(ArxivError::CircuitOpen { retry_after }) if retry_after <= Duration::from_secs(60)
+    ));
+}
+
+#[test]
+fn test_success_resets_failures() {
+    let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+    breaker.record::<()>(&Err(ArxivError::Status(503)));
+    breaker.record(&Ok(()));
+    breaker.record::<()>(&Err(ArxivError::Status(503)));
+    assert!(!breaker.is_open());
+
+    // rejected queries are not outages
+    breaker.record::<()>(&Err(ArxivError::Api(String::from("bad id"))));
+    breaker.record::<()>(&Err(ArxivError::Status(400)));
+    assert!(!breaker.is_open());
+}
+
+#[test]
+fn test_trial_after_cooldown() {
+    let breaker = CircuitBreaker::new(1, Duration::ZERO);
+    breaker.record::<()>(&Err(ArxivError::Status(503)));
+
+    // a single trial request is let through
+    let trial = breaker.check().unwrap();
+    assert!(breaker.check().is_err());
+    trial.record::<()>(&Err(ArxivError::Status(503)));
+
+    breaker.check().unwrap().record(&Ok(()));
+    assert!(!breaker.is_open());
+    breaker.check().unwrap();
+    breaker.check().unwrap();
+}
+
+#[test]
+fn test_dropped_trial() {
+    let breaker = CircuitBreaker::new(1, Duration::ZERO);
+    breaker.record::<()>(&Err(ArxivError::Status(503)));
+
+    let trial = breaker.check().unwrap();
+    assert!(breaker.is_open());
+    assert!(breaker.check().is_err());
+
+    // the trial request was cancelled, so the next request is let through as a new trial
+    drop(trial);
+    let trial = breaker.check().unwrap();
+    assert!(breaker.check().is_err());
+    trial.record(&Ok(()));
+    assert!(!breaker.is_open());
+}
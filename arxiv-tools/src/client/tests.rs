@@ -0,0 +1,111 @@
+use super::*;
+
+#[test]
+fn test_default_timeouts() {
+    let config = ClientConfig::default();
+    assert_eq!(config.connect_timeout, Some(DEFAULT_CONNECT_TIMEOUT));
+    assert_eq!(config.read_timeout, Some(DEFAULT_READ_TIMEOUT));
+    assert_eq!(config.timeout, Some(DEFAULT_TIMEOUT));
+    assert!(config.build().is_ok());
+}
+
+#[test]
+fn test_timeouts_from_toml() {
+    let config: ClientConfig = toml::from_str("connect_timeout = 2.5\ntimeout = 30").unwrap();
+    assert_eq!(config.connect_timeout, Some(Duration::from_millis(2500)));
+    assert_eq!(config.read_timeout, Some(DEFAULT_READ_TIMEOUT));
+    assert_eq!(config.timeout, Some(Duration::from_secs(30)));
+
+    let mut config = ClientConfig::default();
+    config.read_timeout(Duration::from_secs(5));
+    let payload = serde_json::to_string(&config).unwrap();
+    assert_eq!(
+        payload,
+        r#"{"connect_timeout":10.0,"read_timeout":5.0,"timeout":300.0,"system_proxy":true,"compression":true}"#
+    );
+}
+
+#[test]
+fn test_proxy() {
+    let config: ClientConfig = toml::from_str(
+        "proxy = \"http://proxy.example.com:3128\"\nno_proxy = \"localhost\"\nsystem_proxy = false",
+    )
+    .unwrap();
+    assert_eq!(
+        config.proxy.as_ref().map(Url::as_str),
+        Some("http://proxy.example.com:3128/")
+    );
+    assert_eq!(config.no_proxy.as_deref(), Some("localhost"));
+    assert!(!config.system_proxy);
+    assert!(config.build().is_ok());
+
+    let config: ClientConfig = toml::from_str("").unwrap();
+    assert_eq!(config, ClientConfig::default());
+
+    let mut config = ClientConfig::default();
+    config.proxy(Url::parse("socks5://proxy.example.com:1080").unwrap());
+    assert!(config.build().is_ok());
+}
+
+#[test]
+fn test_headers() {
+    let mut config = ClientConfig::default();
+    config
+        .header("x-trace-id", HeaderValue::from_static("42"))
+        .header("x-trace-id", HeaderValue::from_static("43"));
+    assert_eq!(config.headers.len(), 1);
+    assert_eq!(config.headers["x-trace-id"], "43");
+    assert!(config.build().is_ok());
+
+    let payload = toml::to_string(&config).unwrap();
+    let loaded: ClientConfig = toml::from_str(&payload).unwrap();
+    assert_eq!(loaded.headers, config.headers);
+
+    assert!(toml::from_str::<ClientConfig>("[headers]\n\"bad header\" = \"1\"").is_err());
+}
+
+/// Serve `body` gzip compressed to the first connection and return the request it received.
+async fn serve_gzip(listener: tokio::net::TcpListener, body: &[u8]) -> String {
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(body).unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    let (mut socket, _) = listener.accept().await.unwrap();
+    let mut request = vec![0; 4096];
+    let n = socket.read(&mut request).await.unwrap();
+    let header = format!(
+        "HTTP/1.1 200 OK\r\ncontent-encoding: gzip\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+        compressed.len()
+    );
+    socket.write_all(header.as_bytes()).await.unwrap();
+    socket.write_all(&compressed).await.unwrap();
+    return String::from_utf8_lossy(&request[..n]).to_lowercase();
+}
+
+#[tokio::test]
+async fn test_compressed_response() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = Url::parse(&format!(
+        "http://{}/api/query",
+        listener.local_addr().unwrap()
+    ))
+    .unwrap();
+    let server = tokio::spawn(async move {
+        return serve_gzip(listener, include_bytes!("../../fixtures/feed.xml")).await;
+    });
+
+    let mut config = ClientConfig::default();
+    config.without_system_proxy();
+    let session = crate::ArxivSession::with_config(&config).unwrap();
+    let mut arxiv = session.query(crate::QueryParams::title("attention"));
+    arxiv.with_base_url(url);
+    let results = arxiv.try_query().await.unwrap();
+    assert_eq!(results.papers.len(), 2);
+
+    let request = server.await.unwrap();
+    assert!(request.contains("accept-encoding: gzip,deflate"));
+}
@@ -0,0 +1,123 @@
+//! # Paper Diff
+//! The changes between two records of a paper, e.g. two of its versions or the same paper from two harvest runs,
+//! to notify about updated papers:
+//! ```rust
+//! # use arxiv_tools::Paper;
+//! let v1 = Paper::builder()
+//!     .id("2412.01234v1")
+//!     .title("Scaling Laws for Retrival")
+//!     .author("Jane Doe")
+//!     .category("cs.CL")
+//!     .build();
+//! let v2 = Paper::builder()
+//!     .id("2412.01234v2")
+//!     .title("Scaling Laws for Retrieval")
+//!     .author("Jane Doe")
+//!     .author("John Smith")
+//!     .category("cs.CL")
+//!     .category("cs.IR")
+//!     .build();
+//! let diff = v1.diff(&v2);
+//! assert_eq!(
+//!     diff.title,
+//!     Some((
+//!         String::from("Scaling Laws for Retrival"),
+//!         String::from("Scaling Laws for Retrieval")
+//!     ))
+//! );
+//! assert_eq!(&*diff.added_authors[0].name, "John Smith");
+//! assert_eq!(diff.added_categories, vec!["cs.IR".into()]);
+//! assert!(v2.diff(&v2).is_empty());
+//! ```
+//!
+//! Texts are compared with their runs of whitespace collapsed, so line breaks the feed wraps titles and abstracts with
+//! are not reported as changes. Authors are compared by name, ignoring affiliations.
+use crate::{Author, Paper};
+use std::sync::Arc;
+
+#[cfg(test)]
+mod tests;
+
+/// The changes from one record of a paper to another, created with [`Paper::diff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PaperDiff {
+    /// The old and the new title, if the title changed.
+    pub title: Option<(String, String)>,
+    /// The old and the new abstract, if the abstract changed.
+    pub abstract_text: Option<(String, String)>,
+    /// Authors of the new record missing from the old one, in the order of the new record.
+    pub added_authors: Vec<Author>,
+    /// Authors of the old record missing from the new one, in the order of the old record.
+    pub removed_authors: Vec<Author>,
+    /// Whether the authors both records share are listed in another order.
+    pub authors_reordered: bool,
+    /// The old and the new primary category, if the primary category changed.
+    pub primary_category: Option<(Arc<str>, Arc<str>)>,
+    /// Categories of the new record missing from the old one.
+    pub added_categories: Vec<Arc<str>>,
+    /// Categories of the old record missing from the new one.
+    pub removed_categories: Vec<Arc<str>>,
+}
+
+impl PaperDiff {
+    /// Whether none of the compared fields changed.
+    pub fn is_empty(&self) -> bool {
+        return *self == PaperDiff::default();
+    }
+}
+
+impl Paper {
+    /// The changes from this record of the paper to `other`, e.g. from an older version to a newer one.
+    /// See [`crate::diff`].
+    pub fn diff(&self, other: &Paper) -> PaperDiff {
+        let shared = |authors: &[Author], others: &[Author]| {
+            return authors
+                .iter()
+                .filter(|author| others.iter().any(|other| other.name == author.name))
+                .map(|author| author.name.clone())
+                .collect::<Vec<Arc<str>>>();
+        };
+        return PaperDiff {
+            title: changed_text(&self.title, &other.title),
+            abstract_text: changed_text(&self.abstract_text, &other.abstract_text),
+            added_authors: missing_authors(&other.authors, &self.authors),
+            removed_authors: missing_authors(&self.authors, &other.authors),
+            authors_reordered: shared(&self.authors, &other.authors)
+                != shared(&other.authors, &self.authors),
+            primary_category: (self.primary_category != other.primary_category).then(|| {
+                (
+                    self.primary_category.clone(),
+                    other.primary_category.clone(),
+                )
+            }),
+            added_categories: missing_categories(&other.categories, &self.categories),
+            removed_categories: missing_categories(&self.categories, &other.categories),
+        };
+    }
+}
+
+/// The old and the new text, if they differ in more than whitespace.
+fn changed_text(old: &str, new: &str) -> Option<(String, String)> {
+    if old.split_whitespace().eq(new.split_whitespace()) {
+        return None;
+    }
+    return Some((old.to_string(), new.to_string()));
+}
+
+/// The authors of `authors` whose name is not in `others`.
+fn missing_authors(authors: &[Author], others: &[Author]) -> Vec<Author> {
+    return authors
+        .iter()
+        .filter(|author| !others.iter().any(|other| other.name == author.name))
+        .cloned()
+        .collect();
+}
+
+/// The categories of `categories` that are not in `others`.
+fn missing_categories(categories: &[Arc<str>], others: &[Arc<str>]) -> Vec<Arc<str>> {
+    return categories
+        .iter()
+        .filter(|category| !others.contains(category))
+        .cloned()
+        .collect();
+}
@@ -0,0 +1,75 @@
+use super::*;
+
+fn paper() -> Paper {
+    return crate::test_fixtures::paper()
+        .id("2101.00001v1")
+        .abstract_text("We count lattice paths.")
+        .build();
+}
+
+#[test]
+fn test_diff_unchanged() {
+    let old = paper();
+    let mut new = old.clone();
+    new.id = String::from("http://arxiv.org/abs/2101.00001v2");
+    new.title = String::from("Counting\n  Lattice Paths");
+    new.authors[0]
+        .affiliations
+        .push(String::from("University of Vermont"));
+    new.updated = "2021-02-01T00:00:00Z".parse().ok();
+    assert!(old.diff(&new).is_empty());
+}
+
+#[test]
+fn test_diff_changed() {
+    let old = paper();
+    let new = Paper::builder()
+        .id("2101.00001v2")
+        .title("Counting Lattice Paths with Restricted Steps")
+        .abstract_text("We count lattice paths.")
+        .author("Jane Doe")
+        .author("J. D. van der Waals")
+        .primary_category("math.CO")
+        .category("math.PR")
+        .build();
+    let diff = old.diff(&new);
+    assert_eq!(
+        diff.title,
+        Some((
+            String::from("Counting Lattice Paths"),
+            String::from("Counting Lattice Paths with Restricted Steps")
+        ))
+    );
+    assert_eq!(diff.abstract_text, None);
+    assert_eq!(diff.added_authors, vec![Author::new("Jane Doe")]);
+    assert_eq!(
+        diff.removed_authors,
+        vec![Author::new("Adrian Del Maestro")]
+    );
+    assert!(!diff.authors_reordered);
+    assert_eq!(diff.primary_category, None);
+    assert_eq!(diff.added_categories, vec![Arc::<str>::from("math.PR")]);
+    assert_eq!(diff.removed_categories, vec![Arc::<str>::from("cs.NA")]);
+    assert!(!diff.is_empty());
+
+    let reverse = new.diff(&old);
+    assert_eq!(reverse.added_authors, diff.removed_authors);
+    assert_eq!(reverse.removed_categories, diff.added_categories);
+}
+
+#[test]
+fn test_diff_reordered() {
+    let old = paper();
+    let mut new = old.clone();
+    new.authors.reverse();
+    new.categories.reverse();
+    new.primary_category = "cs.NA".into();
+    let diff = old.diff(&new);
+    assert!(diff.authors_reordered);
+    assert!(diff.added_authors.is_empty() && diff.removed_authors.is_empty());
+    assert_eq!(
+        diff.primary_category,
+        Some(("math.CO".into(), "cs.NA".into()))
+    );
+    assert!(diff.added_categories.is_empty() && diff.removed_categories.is_empty());
+}
@@ -0,0 +1,93 @@
+//! # Endpoint
+//! Where query requests are sent. The default is the official API over HTTPS:
+//! ```rust
+//! # use arxiv_tools::endpoint::Endpoint;
+//! assert_eq!(Endpoint::default().url().as_str(), "https://export.arxiv.org/api/query");
+//! ```
+//!
+//! Plain HTTP stays available for environments that need it, e.g. a proxy that cannot tunnel TLS:
+//! ```rust
+//! # use arxiv_tools::endpoint::{Endpoint, Scheme};
+//! let endpoint = Endpoint {
+//!     scheme: Scheme::Http,
+//!     ..Endpoint::default()
+//! };
+//! assert_eq!(endpoint.url().as_str(), "http://export.arxiv.org/api/query");
+//! ```
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[cfg(test)]
+mod tests;
+
+/// Host of the official arXiv query API.
+pub const DEFAULT_HOST: &str = "export.arxiv.org";
+/// Path of the query API on [`DEFAULT_HOST`].
+pub const DEFAULT_PATH: &str = "/api/query";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scheme {
+    #[default]
+    Https,
+    /// Unencrypted HTTP. Only use it when HTTPS is not an option.
+    Http,
+}
+
+impl Scheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scheme::Https => "https",
+            Scheme::Http => "http",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Endpoint {
+    #[serde(default)]
+    pub scheme: Scheme,
+    pub host: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    pub path: String,
+}
+
+impl Default for Endpoint {
+    fn default() -> Self {
+        return Endpoint {
+            scheme: Scheme::default(),
+            host: DEFAULT_HOST.to_string(),
+            port: None,
+            path: DEFAULT_PATH.to_string(),
+        };
+    }
+}
+
+impl Endpoint {
+    /// The official API on `scheme`.
+    pub fn with_scheme(scheme: Scheme) -> Self {
+        return Endpoint {
+            scheme,
+            ..Endpoint::default()
+        };
+    }
+
+    /// The URL queries are appended to.
+    ///
+    /// # Panics
+    /// If `host` is not a valid host name.
+    pub fn url(&self) -> Url {
+        let authority = match self.port {
+            Some(port) => format!("{}:{}", self.host, port),
+            None => self.host.clone(),
+        };
+        let path = if self.path.starts_with('/') {
+            self.path.clone()
+        } else {
+            format!("/{}", self.path)
+        };
+        let url = format!("{}://{}{}", self.scheme.as_str(), authority, path);
+        return Url::parse(&url).expect("the endpoint host is a valid host name");
+    }
+}
@@ -0,0 +1,39 @@
+use super::*;
+
+#[test]
+fn test_default_endpoint_is_https() {
+    let endpoint = Endpoint::default();
+    assert_eq!(endpoint.scheme, Scheme::Https);
+    assert_eq!(
+        endpoint.url().as_str(),
+        "https://export.arxiv.org/api/query"
+    );
+}
+
+#[test]
+fn test_endpoint_url() {
+    let endpoint = Endpoint::with_scheme(Scheme::Http);
+    assert_eq!(endpoint.url().as_str(), "http://export.arxiv.org/api/query");
+
+    let endpoint = Endpoint {
+        scheme: Scheme::Http,
+        host: "localhost".to_string(),
+        port: Some(8080),
+        path: "api/query".to_string(),
+    };
+    assert_eq!(endpoint.url().as_str(), "http://localhost:8080/api/query");
+}
+
+#[test]
+fn test_endpoint_serde() {
+    let endpoint: Endpoint =
+        serde_json::from_str(r#"{"host":"mirror.example.org","path":"/api/query"}"#).unwrap();
+    assert_eq!(endpoint.scheme, Scheme::Https);
+    assert_eq!(endpoint.port, None);
+
+    let payload = serde_json::to_string(&Endpoint::with_scheme(Scheme::Http)).unwrap();
+    assert_eq!(
+        payload,
+        r#"{"scheme":"http","host":"export.arxiv.org","path":"/api/query"}"#
+    );
+}
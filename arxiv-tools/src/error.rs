@@ -0,0 +1,61 @@
+//! # Errors
+//! Errors returned when querying the arXiv API.
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ArxivError {
+    /// The request could not be sent or the response could not be read.
+    #[error("request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    /// The API answered with a non-success HTTP status and no error message in the feed.
+    #[error("unexpected HTTP status: {0}")]
+    Status(u16),
+    /// The API rejected the query, e.g. because of a malformed id.
+    #[error("arXiv API error: {0}")]
+    Api(String),
+    /// The response is not a well-formed Atom feed.
+    #[error("failed to parse the response: {0}")]
+    Xml(String),
+    /// A `submittedDate` bound is not a valid `YYYYMMDD` or `YYYYMMDDHHMM` date.
+    #[error("invalid submitted date: {0:?}")]
+    InvalidDate(String),
+    /// An arXiv identifier is neither in the `YYMM.NNNNN` nor in the legacy `archive/YYMMNNN` scheme.
+    #[error("invalid arXiv identifier: {0:?}")]
+    InvalidId(String),
+    /// The request was not sent because the circuit breaker of the session is open after repeated failures.
+    #[error("circuit breaker open, retry after {retry_after:?}")]
+    CircuitOpen { retry_after: std::time::Duration },
+    /// The default HTTP client could not be built, e.g. because the TLS backend failed to initialize.
+    #[error("failed to build the HTTP client: {0}")]
+    Client(String),
+    /// A request could not be recorded, or replayed because it wasn't recorded or its recorded request failed with
+    /// this error.
+    #[error("replay failed: {0}")]
+    Replay(String),
+    /// A [`Store`](crate::join::Store) failed to look papers up.
+    #[error("store lookup failed: {0}")]
+    Store(String),
+    /// A [listing page](crate::listing) could not be read, e.g. because its layout changed.
+    #[error("failed to read the listing page: {0}")]
+    Listing(String),
+}
+
+impl ArxivError {
+    /// Short name of the kind of error, e.g. to label metrics: `http`, `status`, `api`, `xml`, `invalid_date`,
+    /// `invalid_id`, `circuit_open`, `client`, `replay`, `store` or `listing`.
+    pub fn class(&self) -> &'static str {
+        match self {
+            ArxivError::Http(_) => return "http",
+            ArxivError::Status(_) => return "status",
+            ArxivError::Api(_) => return "api",
+            ArxivError::Xml(_) => return "xml",
+            ArxivError::InvalidDate(_) => return "invalid_date",
+            ArxivError::InvalidId(_) => return "invalid_id",
+            ArxivError::CircuitOpen { .. } => return "circuit_open",
+            ArxivError::Client(_) => return "client",
+            ArxivError::Replay(_) => return "replay",
+            ArxivError::Store(_) => return "store",
+            ArxivError::Listing(_) => return "listing",
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! # Export
+//! Writers of papers in the formats reference managers and other tools import:
+//! - [`bibtex`], BibTeX `@misc` entries with the `eprint` fields arXiv recommends;
+//! - [`ris`], RIS records for EndNote, Mendeley and Zotero;
+//! - [`csv`] and [`CsvWriter`], CSV with configurable columns for spreadsheets and data frames;
+//! - [`jsonl::Writer`], JSON Lines written paper by paper, optionally gzip compressed and stamped with a schema
+//!   version, for large dumps;
+//! - [`markdown`] and [`MarkdownReport`], a list of linked papers for discussions and newsletters;
+//! - [`html`] and [`HtmlReport`], a standalone page with a sortable table or cards, e.g. for daily digests;
+//! - [`org`], Org-mode headings with a `PROPERTIES` drawer, for reading lists kept in Emacs;
+//! - [`atom`], [`rss`] and [`Feed`], feeds of query results for feed readers.
+use crate::{Author, Paper};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+mod bibtex;
+mod csv;
+mod feed;
+mod html;
+pub mod jsonl;
+mod markdown;
+mod org;
+mod ris;
+
+pub use bibtex::bibtex;
+pub use csv::{csv, Column, CsvWriter, DEFAULT_COLUMNS};
+pub use feed::{atom, rss, Feed, FeedFormat};
+pub use html::{html, HtmlReport, Layout};
+pub use markdown::{markdown, MarkdownReport};
+pub use org::org;
+pub use ris::ris;
+
+/// How the papers of a report are grouped under headings, serialized in snake case, e.g. `"category"`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    /// A single list, in the order of the papers.
+    #[default]
+    None,
+    /// By primary category, in alphabetical order.
+    Category,
+    /// By day of submission, newest first.
+    Date,
+}
+
+impl GroupBy {
+    /// The headings and papers of the groups, the papers of a group in their original order. Papers without a primary
+    /// category or a submission date are grouped last, under `Uncategorized` or `Undated`. Without grouping, the
+    /// only group has an empty heading.
+    fn groups<'a>(&self, papers: &'a [Paper]) -> Vec<(String, Vec<&'a Paper>)> {
+        let (key, missing): (fn(&Paper) -> String, &str) = match self {
+            GroupBy::None => return vec![(String::new(), papers.iter().collect())],
+            GroupBy::Category => (|paper| paper.primary_category.to_string(), "Uncategorized"),
+            GroupBy::Date => (Paper::published_date, "Undated"),
+        };
+        let mut groups = BTreeMap::<String, Vec<&Paper>>::new();
+        for paper in papers {
+            groups.entry(key(paper)).or_default().push(paper);
+        }
+        let unknown = groups.remove("");
+        let mut groups = groups.into_iter().collect::<Vec<(String, Vec<&Paper>)>>();
+        if *self == GroupBy::Date {
+            groups.reverse();
+        }
+        groups.extend(unknown.map(|papers| (missing.to_string(), papers)));
+        return groups;
+    }
+}
+
+/// The page of the paper: its abstract page, or the identifier if it is a URL of another site.
+fn link(paper: &Paper) -> Option<String> {
+    return paper
+        .abs_url()
+        .or_else(|| Some(paper.id.clone()).filter(|id| id.starts_with("http")));
+}
+
+/// The surname of the first author folded to lowercase ASCII letters, e.g. `muller` for `Jörg Müller`, or an empty
+/// string for a paper without authors.
+fn first_surname(paper: &Paper) -> String {
+    let Some(author) = paper.authors.first() else {
+        return String::new();
+    };
+    return ascii_letters(author.family());
+}
+
+/// The name in the `Surname, Given` form reference managers read unambiguously, e.g. `van der Waals, Johannes`.
+fn inverted_name(author: &Author) -> String {
+    match author.given() {
+        "" => return author.family().to_string(),
+        given => return format!("{}, {}", author.family(), given),
+    }
+}
+
+/// `text` with its runs of whitespace, including the line breaks the feed wraps text with, collapsed to a space.
+fn one_line(text: &str) -> String {
+    return text.split_whitespace().collect::<Vec<&str>>().join(" ");
+}
+
+/// The lowercase ASCII letters and digits of `text`, with the accents of Latin letters removed.
+fn ascii_letters(text: &str) -> String {
+    return text
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(fold_accent)
+        .filter(char::is_ascii_alphanumeric)
+        .collect();
+}
+
+/// `c` without its accent, for the accented Latin letters of Western and Central European names.
+fn fold_accent(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => return 'a',
+        'ç' | 'ć' | 'č' => return 'c',
+        'ď' | 'đ' => return 'd',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' | 'ě' => return 'e',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' => return 'i',
+        'ł' | 'ľ' => return 'l',
+        'ñ' | 'ń' | 'ň' => return 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => return 'o',
+        'ř' => return 'r',
+        'ś' | 'š' | 'ş' => return 's',
+        'ť' | 'ţ' => return 't',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => return 'u',
+        'ý' | 'ÿ' => return 'y',
+        'ź' | 'ż' | 'ž' => return 'z',
+        _ => return c,
+    }
+}
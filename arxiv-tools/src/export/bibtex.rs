@@ -0,0 +1,174 @@
+use super::{ascii_letters, first_surname, inverted_name};
+use crate::Paper;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tests;
+
+/// Title words skipped when picking the word of a citation key.
+const KEY_STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "by", "for", "from", "in", "is", "of", "on", "the", "to",
+    "with",
+];
+
+/// BibTeX entries of `papers`, separated by blank lines. Papers sharing a citation key get the suffixes `a`, `b`,
+/// ... after the first one, e.g. `vaswani2017attention` and `vaswani2017attentiona`.
+/// ```rust
+/// # use arxiv_tools::{export, Paper};
+/// let paper = Paper::builder()
+///     .id("1706.03762v7")
+///     .title("Attention Is All You Need")
+///     .author("Ashish Vaswani")
+///     .author("Noam Shazeer")
+///     .category("cs.CL")
+///     .category("cs.LG")
+///     .published("2017-06-12T17:57:34Z".parse().unwrap())
+///     .build();
+/// assert_eq!(
+///     export::bibtex(&[paper]),
+///     r#"@misc{vaswani2017attention,
+///   title = {Attention Is All You Need},
+///   author = {Vaswani, Ashish and Shazeer, Noam},
+///   year = {2017},
+///   eprint = {1706.03762},
+///   archivePrefix = {arXiv},
+///   primaryClass = {cs.CL},
+///   url = {https://arxiv.org/abs/1706.03762},
+/// }
+/// "#
+/// );
+/// ```
+pub fn bibtex(papers: &[Paper]) -> String {
+    let mut keys = HashMap::<String, usize>::new();
+    return papers
+        .iter()
+        .map(|paper| {
+            let key = paper.bibtex_key();
+            let count = keys.entry(key.clone()).or_default();
+            *count += 1;
+            return entry(paper, &format!("{}{}", key, key_suffix(*count - 1)));
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+}
+
+impl Paper {
+    /// The BibTeX `@misc` entry of the paper, with the `eprint`, `archivePrefix` and `primaryClass` fields arXiv
+    /// recommends. See [`crate::export::bibtex`].
+    pub fn to_bibtex(&self) -> String {
+        return entry(self, &self.bibtex_key());
+    }
+    /// The citation key in the `lastname2024word` form: the surname of the first author, the year of publication
+    /// and the first significant word of the title, e.g. `vaswani2017attention`. Parts that are unknown are left out.
+    pub fn bibtex_key(&self) -> String {
+        let year = self
+            .published
+            .map(|published| published.format("%Y").to_string())
+            .unwrap_or_default();
+        let word = self
+            .title
+            .split(|c: char| c.is_whitespace() || c == '-')
+            .map(ascii_letters)
+            .find(|word| !word.is_empty() && !KEY_STOPWORDS.contains(&word.as_str()))
+            .unwrap_or_default();
+        let key = format!("{}{}{}", first_surname(self), year, word);
+        if key.is_empty() {
+            return ascii_letters(&self.id_without_version());
+        }
+        return key;
+    }
+}
+
+/// The suffix of the `n`th paper sharing a key after the first one: nothing for the first, then `a` to `z`, then
+/// the number itself.
+fn key_suffix(n: usize) -> String {
+    match n {
+        0 => return String::new(),
+        1..=26 => return char::from(b'a' + (n - 1) as u8).to_string(),
+        _ => return n.to_string(),
+    }
+}
+
+fn entry(paper: &Paper, key: &str) -> String {
+    let authors = paper
+        .authors
+        .iter()
+        .map(inverted_name)
+        .collect::<Vec<String>>()
+        .join(" and ");
+    let id = paper.arxiv_id();
+    let mut fields = vec![
+        ("title", escape(&paper.title)),
+        ("author", escape(&authors)),
+        (
+            "year",
+            paper
+                .published
+                .map(|published| published.format("%Y").to_string())
+                .unwrap_or_default(),
+        ),
+    ];
+    if let Some(id) = &id {
+        fields.push(("eprint", id.base().to_string()));
+        fields.push(("archivePrefix", String::from("arXiv")));
+        fields.push(("primaryClass", paper.primary_category.to_string()));
+    }
+    fields.push(("doi", paper.doi.clone().unwrap_or_default()));
+    fields.push((
+        "url",
+        match &id {
+            Some(id) => format!("https://arxiv.org/abs/{}", id.base()),
+            None if paper.id.starts_with("http") => paper.id.clone(),
+            None => String::new(),
+        },
+    ));
+    let mut entry = format!("@misc{{{},\n", key);
+    for (name, value) in fields.iter().filter(|(_, value)| !value.is_empty()) {
+        entry.push_str(&format!("  {} = {{{}}},\n", name, value));
+    }
+    entry.push_str("}\n");
+    return entry;
+}
+
+/// `text` as a BibTeX field value: `&`, `%` and `#` escaped, `_` escaped outside math, braces without a match
+/// removed, so LaTeX such as `$O(n^{2})$` or `G\"odel` is kept as it is.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut math = false;
+    let mut open = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                escaped.push(c);
+                if let Some(next) = chars.next() {
+                    escaped.push(next);
+                }
+            }
+            '$' => {
+                math = !math;
+                escaped.push(c);
+            }
+            '&' | '%' | '#' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '_' if !math => escaped.push_str("\\_"),
+            '{' => {
+                open.push(escaped.len());
+                escaped.push(c);
+            }
+            '}' => {
+                if open.pop().is_some() {
+                    escaped.push(c);
+                }
+            }
+            _ => escaped.push(c),
+        }
+    }
+    // from the last one backwards, so the positions of the others stay valid
+    for position in open.into_iter().rev() {
+        escaped.remove(position);
+    }
+    return escaped;
+}
@@ -0,0 +1,92 @@
+use super::*;
+
+fn paper() -> Paper {
+    return Paper::builder()
+        .id("http://arxiv.org/abs/hep-ph/0701001v2")
+        .title("The Müller Effect: Q&A on 50% of $\\mathcal{N}_2$ models")
+        .author("Jörg Müller")
+        .author("J. D. van der Waals")
+        .author("Plato")
+        .primary_category("hep-ph")
+        .category("hep-ex")
+        .doi("10.1103/PhysRevD.76.013009")
+        .published("2007-01-01T16:40:21Z".parse().unwrap())
+        .build();
+}
+
+#[test]
+fn test_bibtex_entry() {
+    assert_eq!(
+        paper().to_bibtex(),
+        r#"@misc{muller2007muller,
+  title = {The Müller Effect: Q\&A on 50\% of $\mathcal{N}_2$ models},
+  author = {Müller, Jörg and van der Waals, J. D. and Plato},
+  year = {2007},
+  eprint = {hep-ph/0701001},
+  archivePrefix = {arXiv},
+  primaryClass = {hep-ph},
+  doi = {10.1103/PhysRevD.76.013009},
+  url = {https://arxiv.org/abs/hep-ph/0701001},
+}
+"#
+    );
+
+    let other = Paper::builder()
+        .id("https://example.org/papers/1")
+        .title("On the snake_case Naming}")
+        .build();
+    assert_eq!(
+        other.to_bibtex(),
+        r#"@misc{snakecase,
+  title = {On the snake\_case Naming},
+  url = {https://example.org/papers/1},
+}
+"#
+    );
+}
+
+#[test]
+fn test_bibtex_keys() {
+    assert_eq!(paper().bibtex_key(), "muller2007muller");
+    let paper = Paper::builder()
+        .title("A Self-Attention Model")
+        .author("Johannes van der Waals")
+        .build();
+    assert_eq!(paper.bibtex_key(), "vanderwaalsself");
+    assert_eq!(
+        Paper::builder().id("2101.00001v1").build().bibtex_key(),
+        "210100001"
+    );
+    assert_eq!(key_suffix(0), "");
+    assert_eq!(key_suffix(1), "a");
+    assert_eq!(key_suffix(26), "z");
+    assert_eq!(key_suffix(27), "27");
+}
+
+#[test]
+fn test_bibtex_list() {
+    let papers = [paper(), paper(), paper()];
+    let entries = bibtex(&papers);
+    let keys = entries
+        .lines()
+        .filter_map(|line| line.strip_prefix("@misc{"))
+        .collect::<Vec<&str>>();
+    assert_eq!(
+        keys,
+        vec![
+            "muller2007muller,",
+            "muller2007mullera,",
+            "muller2007mullerb,"
+        ]
+    );
+    assert!(entries.contains("}\n\n@misc{"));
+    assert_eq!(bibtex(&[]), "");
+}
+
+#[test]
+fn test_bibtex_escape() {
+    assert_eq!(escape("{open"), "open");
+    assert_eq!(escape("a {b} c} {d"), "a {b} c d");
+    assert_eq!(escape(r#"G\"odel \{x\} \& y"#), r#"G\"odel \{x\} \& y"#);
+    assert_eq!(escape("$a_1$ and b_1 # 1"), r"$a_1$ and b\_1 \# 1");
+}
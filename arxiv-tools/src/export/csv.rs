@@ -0,0 +1,207 @@
+use crate::Paper;
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+
+#[cfg(test)]
+mod tests;
+
+/// A column of the CSV export, serialized as its header, e.g. `"primary_category"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    /// The identifier with its version, e.g. `1706.03762v7`. See [`Paper::canonical_id`].
+    Id,
+    Title,
+    Authors,
+    Abstract,
+    /// RFC 3339, e.g. `2017-06-12T17:57:34Z`.
+    Published,
+    /// RFC 3339, e.g. `2017-06-12T17:57:34Z`.
+    Updated,
+    PrimaryCategory,
+    Categories,
+    Doi,
+    Comment,
+    JournalRef,
+    AbsUrl,
+    PdfUrl,
+}
+
+impl Column {
+    /// The header of the column, e.g. `primary_category`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Column::Id => return "id",
+            Column::Title => return "title",
+            Column::Authors => return "authors",
+            Column::Abstract => return "abstract",
+            Column::Published => return "published",
+            Column::Updated => return "updated",
+            Column::PrimaryCategory => return "primary_category",
+            Column::Categories => return "categories",
+            Column::Doi => return "doi",
+            Column::Comment => return "comment",
+            Column::JournalRef => return "journal_ref",
+            Column::AbsUrl => return "abs_url",
+            Column::PdfUrl => return "pdf_url",
+        }
+    }
+
+    /// The values of the column for `paper`, several for multi-valued columns.
+    fn values(&self, paper: &Paper) -> Vec<String> {
+        match self {
+            Column::Id => return vec![paper.canonical_id()],
+            Column::Title => return vec![paper.title.clone()],
+            Column::Authors => {
+                return paper
+                    .authors
+                    .iter()
+                    .map(|author| author.name.to_string())
+                    .collect();
+            }
+            Column::Abstract => return vec![paper.abstract_text.clone()],
+            Column::Published => return vec![paper.published_rfc3339()],
+            Column::Updated => return vec![paper.updated_rfc3339()],
+            Column::PrimaryCategory => return vec![paper.primary_category.to_string()],
+            Column::Categories => {
+                return paper
+                    .categories
+                    .iter()
+                    .map(|category| category.to_string())
+                    .collect();
+            }
+            Column::Doi => return paper.doi.iter().cloned().collect(),
+            Column::Comment => return paper.comment.iter().cloned().collect(),
+            Column::JournalRef => return paper.journal_ref.iter().cloned().collect(),
+            Column::AbsUrl => return paper.abs_url().into_iter().collect(),
+            Column::PdfUrl => return paper.pdf_link().into_iter().collect(),
+        }
+    }
+}
+
+/// Columns of [`CsvWriter::default`].
+pub const DEFAULT_COLUMNS: [Column; 6] = [
+    Column::Id,
+    Column::Title,
+    Column::Authors,
+    Column::Published,
+    Column::PrimaryCategory,
+    Column::AbsUrl,
+];
+
+/// Writer of papers as CSV, one row per paper after a header row, with fields quoted as RFC 4180 requires.
+/// Fields missing from a configuration file keep their default.
+/// ```rust
+/// # use arxiv_tools::export::{Column, CsvWriter};
+/// # use arxiv_tools::Paper;
+/// let paper = Paper::builder()
+///     .id("1706.03762v7")
+///     .title("Attention Is All You Need")
+///     .author("Ashish Vaswani")
+///     .author("Noam Shazeer")
+///     .category("cs.CL")
+///     .category("cs.LG")
+///     .build();
+/// let mut writer = CsvWriter::default();
+/// writer
+///     .columns(&[Column::Id, Column::Title, Column::Authors, Column::Categories])
+///     .join_with("|");
+/// assert_eq!(
+///     writer.render(&[paper]),
+///     "id,title,authors,categories\r\n\
+///      1706.03762v7,Attention Is All You Need,Ashish Vaswani|Noam Shazeer,cs.CL|cs.LG\r\n"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CsvWriter {
+    /// The columns, in order.
+    pub columns: Vec<Column>,
+    /// Separator of the fields of a row.
+    pub delimiter: char,
+    /// Separator joining the values of multi-valued columns, such as authors and categories.
+    pub join_with: String,
+    /// Whether the first row names the columns.
+    pub header: bool,
+}
+
+impl Default for CsvWriter {
+    fn default() -> Self {
+        return CsvWriter {
+            columns: DEFAULT_COLUMNS.to_vec(),
+            delimiter: ',',
+            join_with: String::from("; "),
+            header: true,
+        };
+    }
+}
+
+impl CsvWriter {
+    pub fn columns(&mut self, columns: &[Column]) -> &mut Self {
+        self.columns = columns.to_vec();
+        return self;
+    }
+    /// Separate the fields with `delimiter`, e.g. `'\t'` or `';'` for spreadsheets of locales with decimal commas.
+    pub fn delimiter(&mut self, delimiter: char) -> &mut Self {
+        self.delimiter = delimiter;
+        return self;
+    }
+    pub fn join_with(&mut self, separator: &str) -> &mut Self {
+        self.join_with = separator.to_string();
+        return self;
+    }
+    /// Leave out the header row, e.g. to append to an existing file.
+    pub fn without_header(&mut self) -> &mut Self {
+        self.header = false;
+        return self;
+    }
+
+    /// Write the header row, if enabled, and a row per paper to `writer`.
+    pub fn write<W: Write>(&self, mut writer: W, papers: &[Paper]) -> io::Result<()> {
+        if self.header {
+            let names = self.columns.iter().map(|column| column.name().to_string());
+            writer.write_all(self.row(names).as_bytes())?;
+        }
+        for paper in papers {
+            let fields = self
+                .columns
+                .iter()
+                .map(|column| column.values(paper).join(&self.join_with));
+            writer.write_all(self.row(fields).as_bytes())?;
+        }
+        return writer.flush();
+    }
+
+    /// The header row, if enabled, and a row per paper.
+    pub fn render(&self, papers: &[Paper]) -> String {
+        let mut csv = Vec::new();
+        self.write(&mut csv, papers).unwrap();
+        return String::from_utf8(csv).unwrap();
+    }
+
+    fn row<I: Iterator<Item = String>>(&self, fields: I) -> String {
+        let mut row = fields
+            .map(|field| self.quote(field))
+            .collect::<Vec<String>>()
+            .join(&self.delimiter.to_string());
+        row.push_str("\r\n");
+        return row;
+    }
+
+    /// `field` in double quotes, with its double quotes doubled, if it contains the delimiter, a double quote or a
+    /// line break, or starts or ends with whitespace.
+    fn quote(&self, field: String) -> String {
+        let needs_quotes = field.contains([self.delimiter, '"', '\n', '\r'])
+            || field.starts_with(char::is_whitespace)
+            || field.ends_with(char::is_whitespace);
+        if !needs_quotes {
+            return field;
+        }
+        return format!("\"{}\"", field.replace('"', "\"\""));
+    }
+}
+
+/// The papers as CSV with the columns and settings of [`CsvWriter::default`].
+pub fn csv(papers: &[Paper]) -> String {
+    return CsvWriter::default().render(papers);
+}
@@ -0,0 +1,51 @@
+use super::*;
+
+fn paper() -> Paper {
+    return crate::test_fixtures::paper()
+        .title("Paths, \"Lattices\" and Steps")
+        .build();
+}
+
+#[test]
+fn test_csv_default() {
+    assert_eq!(
+        csv(&[paper(), Paper::default()]),
+        "id,title,authors,published,primary_category,abs_url\r\n\
+         2101.00001v2,\"Paths, \"\"Lattices\"\" and Steps\",Adrian Del Maestro; J. D. van der Waals,\
+         2021-01-01T09:15:00Z,math.CO,https://arxiv.org/abs/2101.00001v2\r\n\
+         ,,,,,\r\n"
+    );
+    assert_eq!(
+        csv(&[]),
+        "id,title,authors,published,primary_category,abs_url\r\n"
+    );
+}
+
+#[test]
+fn test_csv_configured() {
+    let mut writer = CsvWriter::default();
+    writer
+        .columns(&[Column::Abstract, Column::Categories, Column::Doi])
+        .delimiter('\t')
+        .join_with(" ")
+        .without_header();
+    assert_eq!(
+        writer.render(&[paper()]),
+        "\"We count\nlattice paths.\"\tmath.CO cs.NA\t\r\n"
+    );
+    assert_eq!(writer.quote(String::from(" padded")), "\" padded\"");
+    assert_eq!(writer.quote(String::from("a,b")), "a,b");
+
+    let config = serde_json::to_string(&writer).unwrap();
+    assert_eq!(
+        config,
+        r#"{"columns":["abstract","categories","doi"],"delimiter":"\t","join_with":" ","header":false}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<CsvWriter>(r#"{"columns":["journal_ref"]}"#).unwrap(),
+        CsvWriter {
+            columns: vec![Column::JournalRef],
+            ..Default::default()
+        }
+    );
+}
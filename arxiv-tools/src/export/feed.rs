@@ -0,0 +1,290 @@
+use super::{link, one_line};
+use crate::Paper;
+use chrono::{DateTime, SecondsFormat, Utc};
+use quick_xml::escape::escape;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[cfg(test)]
+mod tests;
+
+/// Namespace of the `arxiv:` elements of the API, e.g. `arxiv:primary_category`.
+const ARXIV_NAMESPACE: &str = "http://arxiv.org/schemas/atom";
+
+/// The format of a generated feed, serialized in lowercase, e.g. `"rss"`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedFormat {
+    /// Atom 1.0, with the `arxiv:` elements of the API, so [`crate::parse_atom`] reads the papers back.
+    #[default]
+    Atom,
+    /// RSS 2.0, with the authors as `dc:creator`.
+    Rss,
+}
+
+/// Settings of an Atom or RSS feed generated from papers, e.g. to subscribe with a feed reader to the results of a
+/// query arXiv has no feed for. Fields missing from a configuration file keep their default.
+/// ```rust
+/// # use arxiv_tools::export::{Feed, FeedFormat};
+/// # use arxiv_tools::{parse_atom, Paper};
+/// let paper = Paper::builder()
+///     .id("1706.03762v7")
+///     .title("Attention Is All You Need")
+///     .author("Ashish Vaswani")
+///     .category("cs.CL")
+///     .published("2017-06-12T17:57:34Z".parse().unwrap())
+///     .build();
+/// let mut feed = Feed::default();
+/// feed.title("Attention papers")
+///     .self_link("https://example.org/feeds/attention.xml");
+/// let atom = feed.render(&[paper.clone()]);
+/// assert_eq!(parse_atom(&atom).unwrap().papers, vec![paper.clone()]);
+///
+/// feed.format(FeedFormat::Rss);
+/// assert!(feed.render(&[paper]).starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <rss version="2.0""#));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Feed {
+    pub title: String,
+    /// The description of the feed, required by RSS.
+    pub description: String,
+    /// The id of an Atom feed, which feed readers tell feeds apart by. Keep it stable across updates of the feed.
+    pub id: String,
+    /// The URL the feed is published at, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub self_link: Option<String>,
+    pub format: FeedFormat,
+}
+
+impl Default for Feed {
+    fn default() -> Self {
+        return Feed {
+            title: String::from("arXiv papers"),
+            description: String::from("Papers from the arXiv API"),
+            id: String::from("urn:arxiv-tools:feed"),
+            self_link: None,
+            format: FeedFormat::default(),
+        };
+    }
+}
+
+impl Feed {
+    pub fn title(&mut self, title: &str) -> &mut Self {
+        self.title = title.to_string();
+        return self;
+    }
+    pub fn description(&mut self, description: &str) -> &mut Self {
+        self.description = description.to_string();
+        return self;
+    }
+    pub fn id(&mut self, id: &str) -> &mut Self {
+        self.id = id.to_string();
+        return self;
+    }
+    pub fn self_link(&mut self, self_link: &str) -> &mut Self {
+        self.self_link = Some(self_link.to_string());
+        return self;
+    }
+    pub fn format(&mut self, format: FeedFormat) -> &mut Self {
+        self.format = format;
+        return self;
+    }
+
+    /// The feed of `papers`, in their order. The feed is as recent as the most recently updated paper.
+    pub fn render(&self, papers: &[Paper]) -> String {
+        let updated = papers
+            .iter()
+            .filter_map(|paper| paper.updated.or(paper.published))
+            .max()
+            .unwrap_or_else(Utc::now);
+        match self.format {
+            FeedFormat::Atom => return self.atom(papers, updated),
+            FeedFormat::Rss => return self.rss(papers, updated),
+        }
+    }
+
+    fn atom(&self, papers: &[Paper], updated: DateTime<Utc>) -> String {
+        let mut xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <feed xmlns=\"http://www.w3.org/2005/Atom\" xmlns:arxiv=\"{}\">\n\
+             \x20 <title>{}</title>\n  <id>{}</id>\n  <updated>{}</updated>\n",
+            ARXIV_NAMESPACE,
+            escape(one_line(&self.title)),
+            escape(&self.id),
+            timestamp(updated)
+        );
+        if let Some(self_link) = &self.self_link {
+            xml.push_str(&format!(
+                "  <link href=\"{}\" rel=\"self\" type=\"application/atom+xml\"/>\n",
+                escape(self_link)
+            ));
+        }
+        // entries without authors take the author of the feed, which Atom requires either way
+        xml.push_str(
+            "  <author><name>arXiv</name></author>\n  <generator>arxiv-tools</generator>\n",
+        );
+        for paper in papers {
+            xml.push_str(&atom_entry(paper, updated));
+        }
+        xml.push_str("</feed>\n");
+        return xml;
+    }
+
+    fn rss(&self, papers: &[Paper], updated: DateTime<Utc>) -> String {
+        let channel_link = self.self_link.as_deref().unwrap_or("https://arxiv.org/");
+        let mut xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <rss version=\"2.0\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n<channel>\n\
+             \x20 <title>{}</title>\n  <link>{}</link>\n  <description>{}</description>\n\
+             \x20 <lastBuildDate>{}</lastBuildDate>\n  <generator>arxiv-tools</generator>\n",
+            escape(one_line(&self.title)),
+            escape(channel_link),
+            escape(one_line(&self.description)),
+            updated.to_rfc2822()
+        );
+        for paper in papers {
+            xml.push_str(&rss_item(paper));
+        }
+        xml.push_str("</channel>\n</rss>\n");
+        return xml;
+    }
+}
+
+/// An Atom feed of the papers with the settings of [`Feed::default`].
+pub fn atom(papers: &[Paper]) -> String {
+    return Feed::default().render(papers);
+}
+
+/// An RSS feed of the papers with the settings of [`Feed::default`].
+pub fn rss(papers: &[Paper]) -> String {
+    return Feed::default().format(FeedFormat::Rss).render(papers);
+}
+
+/// The `<entry>` of `paper`, in the shape the API sends. `updated` stands in for unknown dates, which Atom requires.
+fn atom_entry(paper: &Paper, updated: DateTime<Utc>) -> String {
+    // `atom:id` must be an IRI, so a bare identifier, e.g. `1706.03762v7`, is written as its abstract page
+    let id = match Url::parse(&paper.id) {
+        Ok(_) => paper.id.clone(),
+        Err(_) => link(paper).unwrap_or_else(|| paper.id.clone()),
+    };
+    let mut entry = format!(
+        "  <entry>\n    <id>{}</id>\n    <updated>{}</updated>\n",
+        escape(&id),
+        timestamp(paper.updated.or(paper.published).unwrap_or(updated))
+    );
+    if let Some(published) = paper.published {
+        entry.push_str(&format!(
+            "    <published>{}</published>\n",
+            timestamp(published)
+        ));
+    }
+    entry.push_str(&format!(
+        "    <title>{}</title>\n",
+        escape(one_line(&paper.title))
+    ));
+    entry.push_str(&format!(
+        "    <summary>{}</summary>\n",
+        escape(&paper.abstract_text)
+    ));
+    for author in &paper.authors {
+        entry.push_str(&format!(
+            "    <author><name>{}</name></author>\n",
+            escape(&*author.name)
+        ));
+    }
+    let optional = [
+        ("doi", &paper.doi),
+        ("comment", &paper.comment),
+        ("journal_ref", &paper.journal_ref),
+    ];
+    for (name, value) in optional {
+        if let Some(value) = value {
+            entry.push_str(&format!(
+                "    <arxiv:{0}>{1}</arxiv:{0}>\n",
+                name,
+                escape(value)
+            ));
+        }
+    }
+    if let Some(link) = link(paper) {
+        entry.push_str(&format!(
+            "    <link href=\"{}\" rel=\"alternate\" type=\"text/html\"/>\n",
+            escape(&link)
+        ));
+    }
+    if let Some(pdf_url) = paper.pdf_link() {
+        entry.push_str(&format!(
+            "    <link title=\"pdf\" href=\"{}\" rel=\"related\" type=\"application/pdf\"/>\n",
+            escape(&pdf_url)
+        ));
+    }
+    if !paper.primary_category.is_empty() {
+        entry.push_str(&format!(
+            "    <arxiv:primary_category term=\"{}\" scheme=\"{}\"/>\n",
+            escape(&*paper.primary_category),
+            ARXIV_NAMESPACE
+        ));
+    }
+    for category in &paper.categories {
+        entry.push_str(&format!(
+            "    <category term=\"{}\" scheme=\"{}\"/>\n",
+            escape(&**category),
+            ARXIV_NAMESPACE
+        ));
+    }
+    entry.push_str("  </entry>\n");
+    return entry;
+}
+
+/// The `<item>` of `paper`.
+fn rss_item(paper: &Paper) -> String {
+    let mut item = format!(
+        "  <item>\n    <title>{}</title>\n",
+        escape(one_line(&paper.title))
+    );
+    match link(paper) {
+        Some(link) => {
+            item.push_str(&format!("    <link>{}</link>\n", escape(&link)));
+            item.push_str(&format!(
+                "    <guid isPermaLink=\"true\">{}</guid>\n",
+                escape(&link)
+            ));
+        }
+        None => {
+            item.push_str(&format!(
+                "    <guid isPermaLink=\"false\">{}</guid>\n",
+                escape(&paper.id)
+            ));
+        }
+    }
+    item.push_str(&format!(
+        "    <description>{}</description>\n",
+        escape(one_line(&paper.abstract_text))
+    ));
+    for author in &paper.authors {
+        item.push_str(&format!(
+            "    <dc:creator>{}</dc:creator>\n",
+            escape(&*author.name)
+        ));
+    }
+    for category in &paper.categories {
+        item.push_str(&format!(
+            "    <category>{}</category>\n",
+            escape(&**category)
+        ));
+    }
+    if let Some(published) = paper.published {
+        item.push_str(&format!(
+            "    <pubDate>{}</pubDate>\n",
+            published.to_rfc2822()
+        ));
+    }
+    item.push_str("  </item>\n");
+    return item;
+}
+
+fn timestamp(instant: DateTime<Utc>) -> String {
+    return instant.to_rfc3339_opts(SecondsFormat::Secs, true);
+}
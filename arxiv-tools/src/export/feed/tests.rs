@@ -0,0 +1,95 @@
+use super::*;
+use crate::parse_atom;
+
+fn papers() -> Vec<Paper> {
+    return vec![
+        Paper::builder()
+            .id("http://arxiv.org/abs/hep-ph/0701001v2")
+            .title("Neutrino Masses & <Mixing>")
+            .abstract_text("We review\nneutrino masses.")
+            .author("A. Author")
+            .author("B. Author")
+            .primary_category("hep-ph")
+            .category("hep-ex")
+            .doi("10.1103/PhysRevD.76.013009")
+            .comment("12 pages")
+            .journal_ref("Phys.Rev.D76:013009,2007")
+            .published("2007-01-01T16:40:21Z".parse().unwrap())
+            .updated("2007-03-14T10:12:45Z".parse().unwrap())
+            .build(),
+        Paper::builder().id("urn:example:1").title("Other").build(),
+    ];
+}
+
+#[test]
+fn test_atom_round_trip() {
+    let papers = papers();
+    let mut feed = Feed::default();
+    feed.title("Neutrinos")
+        .id("urn:example:neutrinos")
+        .self_link("https://example.org/neutrinos.xml");
+    let xml = feed.render(&papers);
+    let results = parse_atom(&xml).unwrap();
+    assert_eq!(results.meta.title, "Neutrinos");
+    assert_eq!(results.meta.id, "urn:example:neutrinos");
+    assert_eq!(results.meta.updated, "2007-03-14T10:12:45Z");
+    assert_eq!(
+        results.meta.self_link.as_deref(),
+        Some("https://example.org/neutrinos.xml")
+    );
+
+    let paper = &results.papers[0];
+    assert_eq!(paper.id, papers[0].id);
+    assert_eq!(paper.title, papers[0].title);
+    assert_eq!(paper.abstract_text, "We review neutrino masses.");
+    assert_eq!(paper.authors, papers[0].authors);
+    assert_eq!(paper.primary_category, papers[0].primary_category);
+    assert_eq!(paper.categories, papers[0].categories);
+    assert_eq!(paper.published, papers[0].published);
+    assert_eq!(paper.updated, papers[0].updated);
+    assert_eq!(paper.doi, papers[0].doi);
+    assert_eq!(paper.comment, papers[0].comment);
+    assert_eq!(paper.journal_ref, papers[0].journal_ref);
+
+    // an entry without dates is as recent as the feed
+    assert_eq!(results.papers[1].id, "urn:example:1");
+    assert_eq!(results.papers[1].updated, papers[0].updated);
+    assert_eq!(results.papers[1].published, None);
+    assert!(atom(&[]).contains("<feed"));
+}
+
+#[test]
+fn test_atom_entry_id_is_an_iri() {
+    let paper = Paper {
+        id: String::from("1706.03762v7"),
+        title: String::from("Attention Is All You Need"),
+        ..Default::default()
+    };
+    let xml = atom(std::slice::from_ref(&paper));
+    assert!(xml.contains("<id>https://arxiv.org/abs/1706.03762v7</id>"));
+    assert_eq!(parse_atom(&xml).unwrap().papers, vec![paper]);
+    assert!(atom(&papers()).contains("<id>http://arxiv.org/abs/hep-ph/0701001v2</id>"));
+}
+
+#[test]
+fn test_rss() {
+    let xml = rss(&papers());
+    assert!(xml.contains(
+        "<channel>\n  <title>arXiv papers</title>\n  <link>https://arxiv.org/</link>\n\
+         \x20 <description>Papers from the arXiv API</description>\n\
+         \x20 <lastBuildDate>Wed, 14 Mar 2007 10:12:45 +0000</lastBuildDate>\n"
+    ));
+    assert!(xml.contains(
+        "  <item>\n    <title>Neutrino Masses &amp; &lt;Mixing&gt;</title>\n\
+         \x20   <link>https://arxiv.org/abs/hep-ph/0701001v2</link>\n\
+         \x20   <guid isPermaLink=\"true\">https://arxiv.org/abs/hep-ph/0701001v2</guid>\n\
+         \x20   <description>We review neutrino masses.</description>\n\
+         \x20   <dc:creator>A. Author</dc:creator>\n    <dc:creator>B. Author</dc:creator>\n\
+         \x20   <category>hep-ph</category>\n    <category>hep-ex</category>\n\
+         \x20   <pubDate>Mon, 1 Jan 2007 16:40:21 +0000</pubDate>\n  </item>\n"
+    ));
+    assert!(xml.contains(
+        "  <item>\n    <title>Other</title>\n    <guid isPermaLink=\"false\">urn:example:1</guid>\n"
+    ));
+    assert!(xml.ends_with("</channel>\n</rss>\n"));
+}
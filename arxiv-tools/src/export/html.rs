@@ -0,0 +1,196 @@
+use super::{link, one_line, GroupBy};
+use crate::Paper;
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// Styles of the page, kept short so the page stays readable without them.
+const STYLE: &str = "body{font-family:sans-serif;max-width:60rem;margin:auto;padding:1rem}\
+table{border-collapse:collapse;width:100%}th,td{border-bottom:1px solid #ddd;padding:.4rem;text-align:left;\
+vertical-align:top}th{cursor:pointer}article{border:1px solid #ddd;border-radius:.4rem;margin:.8rem 0;\
+padding:.8rem}.meta{color:#555}";
+
+/// Sorts the rows of a table by the column of the clicked header, toggling the direction on repeated clicks. The
+/// column is the index of the header in its own row, as a grouped page has a table per group.
+const SORT_SCRIPT: &str =
+    "document.querySelectorAll('th').forEach(th=>th.addEventListener('click',()=>{const i=th.cellIndex;\
+const body=th.closest('table').tBodies[0];const asc=th.dataset.asc!=='true';th.dataset.asc=asc;\
+[...body.rows].sort((a,b)=>a.cells[i].textContent.localeCompare(b.cells[i].textContent)*(asc?1:-1))\
+.forEach(row=>body.appendChild(row));}));";
+
+/// How the papers are laid out on the page, serialized in snake case, e.g. `"cards"`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Layout {
+    /// A table sorted by clicking a column header.
+    #[default]
+    Table,
+    /// A card per paper, with its abstract.
+    Cards,
+}
+
+/// Settings of a standalone HTML page of papers, with links to their abstract pages and PDFs, e.g. for a daily
+/// digest published by a watcher job. Fields missing from a configuration file keep their default.
+/// ```rust
+/// # use arxiv_tools::export::{HtmlReport, Layout};
+/// # use arxiv_tools::Paper;
+/// let paper = Paper::builder()
+///     .id("1706.03762v7")
+///     .title("Attention Is All You Need")
+///     .author("Ashish Vaswani")
+///     .category("cs.CL")
+///     .build();
+/// let mut report = HtmlReport::default();
+/// report.title("Daily digest").layout(Layout::Cards);
+/// let html = report.render(&[paper]);
+/// assert!(html.starts_with("<!DOCTYPE html>"));
+/// assert!(html.contains(r#"<a href="https://arxiv.org/abs/1706.03762v7">Attention Is All You Need</a>"#));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HtmlReport {
+    /// Title and top-level heading of the page.
+    pub title: String,
+    pub layout: Layout,
+    pub group_by: GroupBy,
+    /// Whether to leave out the abstracts, for a shorter page.
+    pub without_abstracts: bool,
+}
+
+impl Default for HtmlReport {
+    fn default() -> Self {
+        return HtmlReport {
+            title: String::from("arXiv papers"),
+            layout: Layout::default(),
+            group_by: GroupBy::default(),
+            without_abstracts: false,
+        };
+    }
+}
+
+impl HtmlReport {
+    pub fn title(&mut self, title: &str) -> &mut Self {
+        self.title = title.to_string();
+        return self;
+    }
+    pub fn layout(&mut self, layout: Layout) -> &mut Self {
+        self.layout = layout;
+        return self;
+    }
+    pub fn group_by(&mut self, group_by: GroupBy) -> &mut Self {
+        self.group_by = group_by;
+        return self;
+    }
+    pub fn without_abstracts(&mut self) -> &mut Self {
+        self.without_abstracts = true;
+        return self;
+    }
+
+    /// The page of `papers`.
+    pub fn render(&self, papers: &[Paper]) -> String {
+        let title = escape(&self.title);
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+             <title>{0}</title>\n<style>{1}</style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+            title, STYLE
+        );
+        for (heading, papers) in self.group_by.groups(papers) {
+            if !heading.is_empty() {
+                html.push_str(&format!("<h2>{}</h2>\n", escape(&heading)));
+            }
+            match self.layout {
+                Layout::Table => html.push_str(&self.table(&papers)),
+                Layout::Cards => papers
+                    .iter()
+                    .for_each(|paper| html.push_str(&self.card(paper))),
+            }
+        }
+        if self.layout == Layout::Table {
+            html.push_str(&format!("<script>{}</script>\n", SORT_SCRIPT));
+        }
+        html.push_str("</body>\n</html>\n");
+        return html;
+    }
+
+    fn table(&self, papers: &[&Paper]) -> String {
+        let mut table = String::from(
+            "<table>\n<thead><tr><th>Title</th><th>Authors</th><th>Categories</th><th>Submitted</th>\
+             <th>PDF</th></tr></thead>\n<tbody>\n",
+        );
+        for paper in papers {
+            let mut title = linked_title(paper);
+            if !self.without_abstracts && !paper.abstract_text.trim().is_empty() {
+                title.push_str(&format!(
+                    "<details><summary>Abstract</summary>{}</details>",
+                    escape(&paper.abstract_text)
+                ));
+            }
+            table.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                title,
+                escape(&paper.author_list()),
+                escape(&paper.categories.join(", ")),
+                paper.published_date(),
+                pdf_anchor(paper)
+            ));
+        }
+        table.push_str("</tbody>\n</table>\n");
+        return table;
+    }
+
+    fn card(&self, paper: &Paper) -> String {
+        let meta = [
+            escape(&paper.author_list()),
+            escape(&paper.categories.join(", ")),
+            paper.published_date(),
+            pdf_anchor(paper),
+        ]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<String>>()
+        .join(" · ");
+        let mut card = format!(
+            "<article>\n<h3>{}</h3>\n<p class=\"meta\">{}</p>\n",
+            linked_title(paper),
+            meta
+        );
+        if !self.without_abstracts && !paper.abstract_text.trim().is_empty() {
+            card.push_str(&format!("<p>{}</p>\n", escape(&paper.abstract_text)));
+        }
+        card.push_str("</article>\n");
+        return card;
+    }
+}
+
+/// A standalone HTML page of the papers with the settings of [`HtmlReport::default`].
+pub fn html(papers: &[Paper]) -> String {
+    return HtmlReport::default().render(papers);
+}
+
+/// The title, linked to the page of the paper if it has one.
+fn linked_title(paper: &Paper) -> String {
+    let title = escape(&paper.title);
+    match link(paper) {
+        Some(link) => return format!("<a href=\"{}\">{}</a>", escape(&link), title),
+        None => return title,
+    }
+}
+
+fn pdf_anchor(paper: &Paper) -> String {
+    match paper.pdf_link() {
+        Some(pdf_url) => return format!("<a href=\"{}\">PDF</a>", escape(&pdf_url)),
+        None => return String::new(),
+    }
+}
+
+/// `text` with the characters of HTML markup escaped and its runs of whitespace collapsed.
+fn escape(text: &str) -> String {
+    return one_line(text)
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;");
+}
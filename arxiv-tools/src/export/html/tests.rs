@@ -0,0 +1,59 @@
+use super::*;
+
+fn paper() -> Paper {
+    return crate::test_fixtures::paper()
+        .title("Paths <of> \"Length\" & More")
+        .build();
+}
+
+#[test]
+fn test_html_table() {
+    let html = html(&[paper()]);
+    assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"en\">"));
+    assert!(html.contains("<title>arXiv papers</title>"));
+    assert!(html.contains(
+        "<tr><td><a href=\"https://arxiv.org/abs/2101.00001v2\">Paths &lt;of&gt; &quot;Length&quot; &amp; More</a>\
+         <details><summary>Abstract</summary>We count lattice paths.</details></td>\
+         <td>Adrian Del Maestro, J. D. van der Waals</td><td>math.CO, cs.NA</td><td>2021-01-01</td>\
+         <td><a href=\"https://arxiv.org/pdf/2101.00001v2\">PDF</a></td></tr>\n"
+    ));
+    assert!(html.contains("<script>"));
+    assert!(html.ends_with("</body>\n</html>\n"));
+}
+
+#[test]
+fn test_html_grouped_tables() {
+    let other = Paper::builder().id("urn:example:1").title("Other").build();
+    let mut report = HtmlReport::default();
+    report.group_by(GroupBy::Category);
+    let html = report.render(&[paper(), other]);
+    // every group has its own table, sorted by the index of the header in its row
+    assert_eq!(html.matches("<table>\n").count(), 2);
+    assert_eq!(html.matches("<thead><tr><th>").count(), 2);
+    assert!(html.contains("th.cellIndex"));
+    assert!(!html.contains("forEach((th,i)"));
+}
+
+#[test]
+fn test_html_cards() {
+    let other = Paper::builder().id("urn:example:1").title("Other").build();
+    let mut report = HtmlReport::default();
+    report
+        .title("Digest <today>")
+        .layout(Layout::Cards)
+        .group_by(GroupBy::Category)
+        .without_abstracts();
+    let html = report.render(&[paper(), other]);
+    assert!(html.contains("<h1>Digest &lt;today&gt;</h1>"));
+    assert!(html.contains(
+        "<h2>math.CO</h2>\n<article>\n<h3><a href=\"https://arxiv.org/abs/2101.00001v2\">\
+         Paths &lt;of&gt; &quot;Length&quot; &amp; More</a></h3>\n\
+         <p class=\"meta\">Adrian Del Maestro, J. D. van der Waals · math.CO, cs.NA · 2021-01-01 · \
+         <a href=\"https://arxiv.org/pdf/2101.00001v2\">PDF</a></p>\n</article>\n"
+    ));
+    assert!(html.contains(
+        "<h2>Uncategorized</h2>\n<article>\n<h3>Other</h3>\n<p class=\"meta\"></p>\n</article>\n"
+    ));
+    assert!(!html.contains("lattice"));
+    assert!(!html.contains("<script>"));
+}
@@ -0,0 +1,196 @@
+//! # JSON Lines
+//! A writer of one JSON object per line that takes papers as they arrive, e.g. from [`ArXiv::stream`], so dumps of
+//! large corpora never hold more than one paper in memory:
+//! ```rust,no_run
+//! # use arxiv_tools::{ArXiv, Profile, QueryParams};
+//! # use arxiv_tools::export::jsonl::Writer;
+//! # use futures::TryStreamExt;
+//! # #[tokio::main]
+//! # async fn main() {
+//! let arxiv = ArXiv::from_args(QueryParams::title("transformer"));
+//! let mut writer = Writer::create("transformer.jsonl.gz").unwrap();
+//! writer.with_profile(Profile::Compact);
+//! let mut papers = std::pin::pin!(arxiv.stream());
+//! while let Some(paper) = papers.try_next().await.unwrap() {
+//!     writer.write(&paper).unwrap();
+//! }
+//! writer.finish().unwrap();
+//! # }
+//! ```
+//!
+//! Every dump starts with a [`Header`] line stamping the [`SCHEMA_VERSION`] and profile of its records, see
+//! [`crate::schema`]. [`Writer::append`] refuses to add records to a dump of another version, so a harvest kept over
+//! years never mixes incompatible records.
+//!
+//! [`ArXiv::stream`]: crate::ArXiv::stream
+use crate::schema::{self, Header, SCHEMA_VERSION};
+use crate::{Paper, Profile};
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+/// Writer of papers as JSON Lines, optionally gzip compressed, in the [`Profile`] set with
+/// [`Writer::with_profile`], the full one by default.
+///
+/// The [`Header`] is written before the first paper, or by [`Writer::finish`] if there is none.
+#[derive(Debug)]
+pub struct Writer<W: Write> {
+    sink: Sink<W>,
+    profile: Profile,
+    /// The header of the dump, once written.
+    header: Option<Header>,
+    count: u64,
+}
+
+#[derive(Debug)]
+enum Sink<W: Write> {
+    Plain(W),
+    Gzip(GzEncoder<W>),
+}
+
+impl<W: Write> Writer<W> {
+    /// A writer of uncompressed lines to `writer`.
+    pub fn new(writer: W) -> Self {
+        return Writer {
+            sink: Sink::Plain(writer),
+            profile: Profile::default(),
+            header: None,
+            count: 0,
+        };
+    }
+    /// A writer of gzip compressed lines to `writer`.
+    pub fn gzip(writer: W) -> Self {
+        return Writer {
+            sink: Sink::Gzip(GzEncoder::new(writer, Compression::default())),
+            profile: Profile::default(),
+            header: None,
+            count: 0,
+        };
+    }
+    pub fn with_profile(&mut self, profile: Profile) -> &mut Self {
+        self.profile = profile;
+        return self;
+    }
+
+    /// Write `paper` as a line.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if the profile was changed after the header was written.
+    pub fn write(&mut self, paper: &Paper) -> io::Result<()> {
+        self.write_header()?;
+        let mut line = serde_json::to_vec(&self.profile.apply(paper))?;
+        line.push(b'\n');
+        self.write_line(&line)?;
+        self.count += 1;
+        return Ok(());
+    }
+    /// Write every paper of `papers` as a line.
+    pub fn write_all<'a, I: IntoIterator<Item = &'a Paper>>(
+        &mut self,
+        papers: I,
+    ) -> io::Result<()> {
+        for paper in papers {
+            self.write(paper)?;
+        }
+        return Ok(());
+    }
+    /// Number of papers written so far.
+    pub fn count(&self) -> u64 {
+        return self.count;
+    }
+
+    /// The header of the dump, once written.
+    pub fn header(&self) -> Option<Header> {
+        return self.header;
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        let profile = self.header.map(|header| header.profile.unwrap_or_default());
+        match profile {
+            Some(profile) if profile != self.profile => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "the dump holds records of the {:?} profile, not {:?}",
+                        profile, self.profile
+                    ),
+                ));
+            }
+            Some(_) => return Ok(()),
+            None => {
+                let header = Header {
+                    schema_version: SCHEMA_VERSION,
+                    profile: Some(self.profile),
+                };
+                let mut line = serde_json::to_vec(&header)?;
+                line.push(b'\n');
+                self.write_line(&line)?;
+                self.header = Some(header);
+                return Ok(());
+            }
+        }
+    }
+
+    fn write_line(&mut self, line: &[u8]) -> io::Result<()> {
+        match &mut self.sink {
+            Sink::Plain(writer) => return writer.write_all(line),
+            Sink::Gzip(encoder) => return encoder.write_all(line),
+        }
+    }
+
+    /// Write the header if no paper was written, complete the gzip stream, if compressed, flush and return the
+    /// underlying writer. Dropping the writer instead ignores the errors of doing so.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_header()?;
+        let mut writer = match self.sink {
+            Sink::Plain(writer) => writer,
+            Sink::Gzip(encoder) => encoder.finish()?,
+        };
+        writer.flush()?;
+        return Ok(writer);
+    }
+}
+
+impl Writer<BufWriter<File>> {
+    /// A writer to a new file at `path`, truncating an existing one, gzip compressed if the name ends with `.gz`.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let file = BufWriter::new(File::create(path)?);
+        if path.extension().is_some_and(|extension| extension == "gz") {
+            return Ok(Writer::gzip(file));
+        }
+        return Ok(Writer::new(file));
+    }
+
+    /// A writer appending to the dump at `path`, created if needed, gzip compressed if the name ends with `.gz`.
+    ///
+    /// The papers are written in the profile of the existing dump. Fails with [`io::ErrorKind::InvalidData`] if the
+    /// dump has no header or another [`SCHEMA_VERSION`]; [`schema::migrate`] it first.
+    pub fn append<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let gzip = path.extension().is_some_and(|extension| extension == "gz");
+        let header = match File::open(path) {
+            Ok(file) if gzip => schema::read_header(MultiGzDecoder::new(file))?,
+            Ok(file) => schema::read_header(file)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+        let file = BufWriter::new(OpenOptions::new().create(true).append(true).open(path)?);
+        let mut writer = if gzip {
+            Writer::gzip(file)
+        } else {
+            Writer::new(file)
+        };
+        if let Some(header) = header {
+            header.check()?;
+            writer.profile = header.profile.unwrap_or_default();
+            writer.header = Some(header);
+        }
+        return Ok(writer);
+    }
+}
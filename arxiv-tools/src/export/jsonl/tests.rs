@@ -0,0 +1,151 @@
+use super::*;
+use flate2::read::GzDecoder;
+use std::io::Read;
+
+fn papers() -> Vec<Paper> {
+    return ["2101.00001v1", "2101.00002v3"]
+        .iter()
+        .map(|id| crate::test_fixtures::paper().id(id).build())
+        .collect();
+}
+
+#[test]
+fn test_jsonl_plain() {
+    let papers = papers();
+    let mut writer = Writer::new(Vec::new());
+    writer.write_all(&papers).unwrap();
+    assert_eq!(writer.count(), 2);
+    let jsonl = String::from_utf8(writer.finish().unwrap()).unwrap();
+    let lines = jsonl.lines().collect::<Vec<&str>>();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], r#"{"schema_version":3,"profile":"full"}"#);
+    assert!(jsonl.ends_with('\n'));
+    for (line, paper) in lines[1..].iter().zip(&papers) {
+        assert_eq!(line, &serde_json::to_string(paper).unwrap());
+    }
+}
+
+#[test]
+fn test_jsonl_gzip_compact() {
+    let papers = papers();
+    let mut writer = Writer::gzip(Vec::new());
+    writer.with_profile(Profile::Compact);
+    for paper in &papers {
+        writer.write(paper).unwrap();
+    }
+    let compressed = writer.finish().unwrap();
+    let mut jsonl = String::new();
+    GzDecoder::new(compressed.as_slice())
+        .read_to_string(&mut jsonl)
+        .unwrap();
+    assert_eq!(
+        jsonl,
+        format!(
+            "{{\"schema_version\":3,\"profile\":\"compact\"}}\n{}\n{}\n",
+            serde_json::to_string(&papers[0].compact()).unwrap(),
+            serde_json::to_string(&papers[1].compact()).unwrap()
+        )
+    );
+}
+
+#[test]
+fn test_jsonl_create() {
+    let dir = std::env::temp_dir().join(format!("arxiv-tools-jsonl-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for name in ["papers.jsonl", "papers.jsonl.gz"] {
+        let path = dir.join(name);
+        let mut writer = Writer::create(&path).unwrap();
+        writer.write_all(&papers()).unwrap();
+        writer.finish().unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        // the gzip magic number
+        assert_eq!(bytes.starts_with(&[0x1f, 0x8b]), name.ends_with(".gz"));
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_jsonl_empty_dump_is_stamped() {
+    let writer = Writer::new(Vec::new());
+    assert_eq!(
+        String::from_utf8(writer.finish().unwrap()).unwrap(),
+        "{\"schema_version\":3,\"profile\":\"full\"}\n"
+    );
+
+    let mut writer = Writer::new(Vec::new());
+    writer.write(&papers()[0]).unwrap();
+    writer.with_profile(Profile::Compact);
+    assert_eq!(
+        writer.write(&papers()[1]).unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+}
+
+#[test]
+fn test_jsonl_append() {
+    let dir = std::env::temp_dir().join(format!("arxiv-tools-jsonl-append-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for name in ["papers.jsonl", "papers.jsonl.gz"] {
+        let path = dir.join(name);
+        let mut writer = Writer::create(&path).unwrap();
+        writer.with_profile(Profile::Compact);
+        writer.write(&papers()[0]).unwrap();
+        writer.finish().unwrap();
+
+        // the appended records keep the profile of the dump and aren't stamped again
+        let mut writer = Writer::append(&path).unwrap();
+        assert_eq!(
+            writer.header(),
+            Some(Header {
+                schema_version: SCHEMA_VERSION,
+                profile: Some(Profile::Compact)
+            })
+        );
+        writer.write(&papers()[1]).unwrap();
+        writer.finish().unwrap();
+        let mut jsonl = String::new();
+        let file = std::fs::File::open(&path).unwrap();
+        if name.ends_with(".gz") {
+            MultiGzDecoder::new(file)
+                .read_to_string(&mut jsonl)
+                .unwrap();
+        } else {
+            io::BufReader::new(file).read_to_string(&mut jsonl).unwrap();
+        }
+        assert_eq!(jsonl.lines().count(), 3);
+        assert_eq!(jsonl.matches("schema_version").count(), 1);
+    }
+
+    // unstamped dumps and dumps of another version are refused
+    let path = dir.join("unstamped.jsonl");
+    std::fs::write(&path, serde_json::to_string(&papers()[0]).unwrap() + "\n").unwrap();
+    assert_eq!(
+        Writer::append(&path).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+    let path = dir.join("older.jsonl");
+    std::fs::write(&path, "{\"schema_version\":2,\"profile\":\"full\"}\n").unwrap();
+    assert_eq!(
+        Writer::append(&path).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+
+    // a migrated dump is appended to in the full profile
+    let mut migrated = std::fs::File::create(&path).unwrap();
+    let unstamped = serde_json::to_string(&papers()[0]).unwrap();
+    schema::migrate(unstamped.as_bytes(), &mut migrated).unwrap();
+    drop(migrated);
+    let mut writer = Writer::append(&path).unwrap();
+    writer.write(&papers()[1]).unwrap();
+    writer.finish().unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 3);
+
+    // a new dump is created
+    let path = dir.join("new.jsonl");
+    Writer::append(&path).unwrap().finish().unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "{\"schema_version\":3,\"profile\":\"full\"}\n"
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
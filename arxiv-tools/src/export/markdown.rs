@@ -0,0 +1,122 @@
+use super::{link, one_line, GroupBy};
+use crate::Paper;
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// Settings of a Markdown report: a list of papers with linked titles and their authors, optionally grouped under
+/// headings, with the abstracts folded in `<details>` elements as GitHub renders them. Fields missing from a
+/// configuration file keep their default.
+/// ```rust
+/// # use arxiv_tools::export::{GroupBy, MarkdownReport};
+/// # use arxiv_tools::Paper;
+/// let paper = Paper::builder()
+///     .id("1706.03762v7")
+///     .title("Attention Is All You Need")
+///     .abstract_text("The dominant sequence transduction models are based on...")
+///     .author("Ashish Vaswani")
+///     .category("cs.CL")
+///     .published("2017-06-12T17:57:34Z".parse().unwrap())
+///     .build();
+/// let mut report = MarkdownReport::default();
+/// report.title("Reading list").group_by(GroupBy::Category);
+/// assert_eq!(
+///     report.render(&[paper]),
+///     "# Reading list\n\n## cs.CL\n\n\
+///      - [Attention Is All You Need](https://arxiv.org/abs/1706.03762v7) — Ashish Vaswani (2017-06-12) · \
+///      [PDF](https://arxiv.org/pdf/1706.03762v7)\n\
+///      \n  <details><summary>Abstract</summary>\n\
+///      \n  The dominant sequence transduction models are based on...\n\
+///      \n  </details>\n"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarkdownReport {
+    /// Top-level heading of the report, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub group_by: GroupBy,
+    /// Whether to leave out the abstracts, for a shorter list.
+    pub without_abstracts: bool,
+}
+
+impl MarkdownReport {
+    pub fn title(&mut self, title: &str) -> &mut Self {
+        self.title = Some(title.to_string());
+        return self;
+    }
+    pub fn group_by(&mut self, group_by: GroupBy) -> &mut Self {
+        self.group_by = group_by;
+        return self;
+    }
+    pub fn without_abstracts(&mut self) -> &mut Self {
+        self.without_abstracts = true;
+        return self;
+    }
+
+    /// The report of `papers`.
+    pub fn render(&self, papers: &[Paper]) -> String {
+        let mut blocks = Vec::new();
+        if let Some(title) = &self.title {
+            blocks.push(format!("# {}\n", escape(title)));
+        }
+        for (heading, papers) in self.group_by.groups(papers) {
+            if !heading.is_empty() {
+                blocks.push(format!("## {}\n", escape(&heading)));
+            }
+            blocks.extend(papers.into_iter().map(|paper| self.item(paper)));
+        }
+        return blocks.join("\n");
+    }
+
+    /// The list item of `paper`.
+    fn item(&self, paper: &Paper) -> String {
+        let title = escape(&one_line(&paper.title));
+        let mut item = match link(paper) {
+            Some(link) => format!("- [{}]({})", title, link),
+            None => format!("- {}", title),
+        };
+        if !paper.authors.is_empty() {
+            item.push_str(&format!(" — {}", escape(&paper.author_list())));
+        }
+        let date = paper.published_date();
+        if !date.is_empty() {
+            item.push_str(&format!(" ({})", date));
+        }
+        if let Some(pdf_url) = paper.pdf_link() {
+            item.push_str(&format!(" · [PDF]({})", pdf_url));
+        }
+        item.push('\n');
+        if !self.without_abstracts && !paper.abstract_text.trim().is_empty() {
+            item.push_str(&format!(
+                "\n  <details><summary>Abstract</summary>\n\n  {}\n\n  </details>\n",
+                escape(&one_line(&paper.abstract_text))
+            ));
+        }
+        return item;
+    }
+}
+
+/// The papers as a Markdown list with the settings of [`MarkdownReport::default`].
+pub fn markdown(papers: &[Paper]) -> String {
+    return MarkdownReport::default().render(papers);
+}
+
+/// `text` with the characters that would start a link, emphasis, code or an HTML tag escaped. Underscores and
+/// backslashes are kept, so LaTeX such as `$x_i$` still renders as math.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '[' | ']' | '*' | '`' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '<' => escaped.push_str("&lt;"),
+            _ => escaped.push(c),
+        }
+    }
+    return escaped;
+}
@@ -0,0 +1,87 @@
+use super::*;
+
+fn paper(id: &str, category: &str, published: &str) -> Paper {
+    return Paper::builder()
+        .id(id)
+        .title(&format!("Paper {}", id))
+        .category(category)
+        .published(published.parse().unwrap())
+        .build();
+}
+
+#[test]
+fn test_markdown_item() {
+    let paper = Paper::builder()
+        .id("2101.00001v2")
+        .title("Lattice [Paths] with\n  *Steps* of <size> $x_i$")
+        .abstract_text("We count\nlattice paths.")
+        .authors(["A", "B", "C", "D"].map(crate::Author::new))
+        .build();
+    assert_eq!(
+        markdown(std::slice::from_ref(&paper)),
+        "- [Lattice \\[Paths\\] with \\*Steps\\* of &lt;size> $x_i$](https://arxiv.org/abs/2101.00001v2) \
+         — A, B, C et al. · [PDF](https://arxiv.org/pdf/2101.00001v2)\n\
+         \n  <details><summary>Abstract</summary>\n\
+         \n  We count lattice paths.\n\
+         \n  </details>\n"
+    );
+
+    let mut report = MarkdownReport::default();
+    report.without_abstracts();
+    let other = Paper::builder().id("urn:example:1").title("Other").build();
+    assert_eq!(
+        report.render(&[paper, other]),
+        "- [Lattice \\[Paths\\] with \\*Steps\\* of &lt;size> $x_i$](https://arxiv.org/abs/2101.00001v2) \
+         — A, B, C et al. · [PDF](https://arxiv.org/pdf/2101.00001v2)\n\
+         \n\
+         - Other\n"
+    );
+}
+
+#[test]
+fn test_markdown_groups() {
+    let papers = [
+        paper("2101.00001", "math.CO", "2021-01-01T09:15:00Z"),
+        paper("2101.00002", "cs.CL", "2021-01-02T09:15:00Z"),
+        paper("2101.00003", "math.CO", "2021-01-02T10:15:00Z"),
+        Paper::builder()
+            .id("2101.00004")
+            .title("Paper 2101.00004")
+            .build(),
+    ];
+    let mut report = MarkdownReport::default();
+    report.without_abstracts().group_by(GroupBy::Category);
+    let headings = |markdown: String| {
+        return markdown
+            .lines()
+            .filter(|line| line.starts_with('#') || line.starts_with('-'))
+            .map(|line| line.split(']').next().unwrap().to_string())
+            .collect::<Vec<String>>();
+    };
+    assert_eq!(
+        headings(report.render(&papers)),
+        vec![
+            "## cs.CL",
+            "- [Paper 2101.00002",
+            "## math.CO",
+            "- [Paper 2101.00001",
+            "- [Paper 2101.00003",
+            "## Uncategorized",
+            "- [Paper 2101.00004",
+        ]
+    );
+    report.group_by(GroupBy::Date);
+    assert_eq!(
+        headings(report.render(&papers)),
+        vec![
+            "## 2021-01-02",
+            "- [Paper 2101.00002",
+            "- [Paper 2101.00003",
+            "## 2021-01-01",
+            "- [Paper 2101.00001",
+            "## Undated",
+            "- [Paper 2101.00004",
+        ]
+    );
+    assert_eq!(markdown(&[]), "");
+}
@@ -0,0 +1,85 @@
+use super::{link, one_line};
+use crate::Paper;
+
+#[cfg(test)]
+mod tests;
+
+/// Org-mode entries of `papers`, one top-level heading each, for reading lists kept in Emacs.
+/// ```rust
+/// # use arxiv_tools::{export, Paper};
+/// let paper = Paper::builder()
+///     .id("1706.03762v7")
+///     .title("Attention Is All You Need")
+///     .abstract_text("The dominant sequence transduction models\nare based on...")
+///     .author("Ashish Vaswani")
+///     .author("Noam Shazeer")
+///     .category("cs.CL")
+///     .category("cs.LG")
+///     .published("2017-06-12T17:57:34Z".parse().unwrap())
+///     .build();
+/// assert_eq!(
+///     export::org(&[paper]),
+///     "* Attention Is All You Need\n\
+///      :PROPERTIES:\n\
+///      :ID: 1706.03762v7\n\
+///      :AUTHORS: Ashish Vaswani, Noam Shazeer\n\
+///      :CATEGORIES: cs.CL cs.LG\n\
+///      :PUBLISHED: [2017-06-12 Mon]\n\
+///      :URL: https://arxiv.org/abs/1706.03762v7\n\
+///      :PDF: https://arxiv.org/pdf/1706.03762v7\n\
+///      :END:\n\
+///      The dominant sequence transduction models are based on...\n"
+/// );
+/// ```
+pub fn org(papers: &[Paper]) -> String {
+    return papers.iter().map(Paper::to_org).collect();
+}
+
+impl Paper {
+    /// The Org-mode entry of the paper: a top-level heading with the title, a `PROPERTIES` drawer with the
+    /// identifier, authors, categories, date, DOI and links, and the abstract as the body.
+    /// See [`crate::export::org`].
+    pub fn to_org(&self) -> String {
+        let properties = [
+            ("ID", self.canonical_id()),
+            (
+                "AUTHORS",
+                self.authors
+                    .iter()
+                    .map(|author| author.name.as_ref())
+                    .collect::<Vec<&str>>()
+                    .join(", "),
+            ),
+            ("CATEGORIES", self.categories.join(" ")),
+            (
+                "PUBLISHED",
+                self.published
+                    .map(|published| published.format("[%Y-%m-%d %a]").to_string())
+                    .unwrap_or_default(),
+            ),
+            ("DOI", self.doi.clone().unwrap_or_default()),
+            ("JOURNAL_REF", self.journal_ref.clone().unwrap_or_default()),
+            ("URL", link(self).unwrap_or_default()),
+            ("PDF", self.pdf_link().unwrap_or_default()),
+        ];
+        // headings and properties span a single line
+        let mut entry = format!("* {}\n:PROPERTIES:\n", one_line(&self.title));
+        for (name, value) in properties {
+            let value = one_line(&value);
+            if !value.is_empty() {
+                entry.push_str(&format!(":{}: {}\n", name, value));
+            }
+        }
+        entry.push_str(":END:\n");
+        let abstract_text = one_line(&self.abstract_text);
+        if !abstract_text.is_empty() {
+            // a line starting with an asterisk would be read as a heading
+            if abstract_text.starts_with('*') {
+                entry.push(' ');
+            }
+            entry.push_str(&abstract_text);
+            entry.push('\n');
+        }
+        return entry;
+    }
+}
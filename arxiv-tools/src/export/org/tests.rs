@@ -0,0 +1,44 @@
+use super::*;
+
+#[test]
+fn test_org_entry() {
+    let paper = Paper::builder()
+        .id("http://arxiv.org/abs/hep-ph/0701001v2")
+        .title("Neutrino\n  Masses")
+        .abstract_text("*Neutrino* masses are reviewed.")
+        .author("A. Author")
+        .primary_category("hep-ph")
+        .doi("10.1103/PhysRevD.76.013009")
+        .journal_ref("Phys.Rev.D76:013009,2007")
+        .published("2007-01-01T16:40:21Z".parse().unwrap())
+        .build();
+    assert_eq!(
+        paper.to_org(),
+        "* Neutrino Masses\n\
+         :PROPERTIES:\n\
+         :ID: hep-ph/0701001v2\n\
+         :AUTHORS: A. Author\n\
+         :CATEGORIES: hep-ph\n\
+         :PUBLISHED: [2007-01-01 Mon]\n\
+         :DOI: 10.1103/PhysRevD.76.013009\n\
+         :JOURNAL_REF: Phys.Rev.D76:013009,2007\n\
+         :URL: https://arxiv.org/abs/hep-ph/0701001v2\n\
+         :PDF: https://arxiv.org/pdf/hep-ph/0701001v2\n\
+         :END:\n \
+         *Neutrino* masses are reviewed.\n"
+    );
+}
+
+#[test]
+fn test_org_list() {
+    let paper = Paper::builder().id("urn:example:1").title("Other").build();
+    assert_eq!(
+        paper.to_org(),
+        "* Other\n:PROPERTIES:\n:ID: urn:example:1\n:END:\n"
+    );
+    assert_eq!(
+        org(&[paper.clone(), paper.clone()]),
+        paper.to_org().repeat(2)
+    );
+    assert_eq!(org(&[]), "");
+}
@@ -0,0 +1,88 @@
+use super::inverted_name;
+use crate::Paper;
+
+#[cfg(test)]
+mod tests;
+
+/// RIS records of `papers`, separated by blank lines, for EndNote, Mendeley and Zotero. Lines end with `\r\n` as the
+/// format requires.
+/// ```rust
+/// # use arxiv_tools::{export, Paper};
+/// let paper = Paper::builder()
+///     .id("1706.03762v7")
+///     .title("Attention Is All You Need")
+///     .abstract_text("The dominant sequence transduction models\nare based on...")
+///     .author("Ashish Vaswani")
+///     .author("Noam Shazeer")
+///     .category("cs.CL")
+///     .published("2017-06-12T17:57:34Z".parse().unwrap())
+///     .build();
+/// assert_eq!(
+///     export::ris(&[paper]).lines().collect::<Vec<&str>>(),
+///     vec![
+///         "TY  - UNPB",
+///         "TI  - Attention Is All You Need",
+///         "AU  - Vaswani, Ashish",
+///         "AU  - Shazeer, Noam",
+///         "AB  - The dominant sequence transduction models are based on...",
+///         "PY  - 2017",
+///         "DA  - 2017/06/12",
+///         "UR  - https://arxiv.org/abs/1706.03762",
+///         "KW  - cs.CL",
+///         "ER  - ",
+///     ]
+/// );
+/// ```
+pub fn ris(papers: &[Paper]) -> String {
+    return papers
+        .iter()
+        .map(Paper::to_ris)
+        .collect::<Vec<String>>()
+        .join("\r\n");
+}
+
+impl Paper {
+    /// The RIS record of the paper, as an unpublished work (`UNPB`), or a journal article (`JOUR`) if it has a
+    /// journal reference. See [`crate::export::ris`].
+    pub fn to_ris(&self) -> String {
+        let mut tags = vec![(
+            "TY",
+            match self.journal_ref {
+                Some(_) => String::from("JOUR"),
+                None => String::from("UNPB"),
+            },
+        )];
+        tags.push(("TI", self.title.clone()));
+        for author in &self.authors {
+            tags.push(("AU", inverted_name(author)));
+        }
+        tags.push(("AB", self.abstract_text.clone()));
+        if let Some(published) = self.published {
+            tags.push(("PY", published.format("%Y").to_string()));
+            tags.push(("DA", published.format("%Y/%m/%d").to_string()));
+        }
+        tags.push(("DO", self.doi.clone().unwrap_or_default()));
+        tags.push(("JO", self.journal_ref.clone().unwrap_or_default()));
+        tags.push((
+            "UR",
+            match self.arxiv_id() {
+                Some(id) => format!("https://arxiv.org/abs/{}", id.base()),
+                None => self.id.clone(),
+            },
+        ));
+        tags.push(("N1", self.comment.clone().unwrap_or_default()));
+        for category in &self.categories {
+            tags.push(("KW", category.to_string()));
+        }
+        let mut record = String::new();
+        for (tag, value) in tags {
+            // a value spans a single line
+            let value = value.split_whitespace().collect::<Vec<&str>>().join(" ");
+            if !value.is_empty() {
+                record.push_str(&format!("{}  - {}\r\n", tag, value));
+            }
+        }
+        record.push_str("ER  - \r\n");
+        return record;
+    }
+}
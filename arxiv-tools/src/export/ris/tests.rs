@@ -0,0 +1,49 @@
+use super::*;
+
+#[test]
+fn test_ris_record() {
+    let paper = Paper::builder()
+        .id("http://arxiv.org/abs/hep-ph/0701001v2")
+        .title("Neutrino Masses")
+        .abstract_text("We review\n  neutrino masses.")
+        .author("Johannes van der Waals")
+        .author("Plato")
+        .primary_category("hep-ph")
+        .category("hep-ex")
+        .doi("10.1103/PhysRevD.76.013009")
+        .journal_ref("Phys.Rev.D76:013009,2007")
+        .comment("12 pages")
+        .published("2007-01-01T16:40:21Z".parse().unwrap())
+        .build();
+    assert_eq!(
+        paper.to_ris(),
+        "TY  - JOUR\r\n\
+         TI  - Neutrino Masses\r\n\
+         AU  - van der Waals, Johannes\r\n\
+         AU  - Plato\r\n\
+         AB  - We review neutrino masses.\r\n\
+         PY  - 2007\r\n\
+         DA  - 2007/01/01\r\n\
+         DO  - 10.1103/PhysRevD.76.013009\r\n\
+         JO  - Phys.Rev.D76:013009,2007\r\n\
+         UR  - https://arxiv.org/abs/hep-ph/0701001\r\n\
+         N1  - 12 pages\r\n\
+         KW  - hep-ph\r\n\
+         KW  - hep-ex\r\n\
+         ER  - \r\n"
+    );
+}
+
+#[test]
+fn test_ris_list() {
+    let paper = Paper::builder().id("urn:example:1").build();
+    assert_eq!(
+        paper.to_ris(),
+        "TY  - UNPB\r\nUR  - urn:example:1\r\nER  - \r\n"
+    );
+    assert_eq!(
+        ris(&[paper.clone(), paper.clone()]),
+        format!("{0}\r\n{0}", paper.to_ris())
+    );
+    assert_eq!(ris(&[]), "");
+}
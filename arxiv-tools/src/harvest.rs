@@ -0,0 +1,147 @@
+//! # Incremental Harvesting
+//! An [`IncrementalHarvester`] fetches only the papers that are new since its previous run.
+//! The newest timestamp seen, the high-water mark, is kept in a state file between runs, with the papers seen at that
+//! timestamp, so that papers sharing it are neither dropped nor harvested twice.
+//!
+//! ```rust,no_run
+//! # use arxiv_tools::{ArXiv, QueryParams, Category};
+//! # use arxiv_tools::harvest::IncrementalHarvester;
+//! # #[tokio::main]
+//! # async fn main() {
+//! let query = ArXiv::from_args(QueryParams::subject_category(Category::CsCl));
+//! let mut harvester = IncrementalHarvester::new(query, "cs_cl.state.json").unwrap();
+//!
+//! // the first run fetches everything matching the query; bound it with `limit_total` if needed
+//! let papers = harvester.run().await.unwrap();
+//! # }
+//! ```
+use crate::{load_file, save_file, ArXiv, Paper, SortBy, SortOrder};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// State kept between the runs of an [`IncrementalHarvester`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarvestState {
+    /// The newest timestamp of the papers harvested so far.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub high_water_mark: Option<DateTime<Utc>>,
+    /// Canonical identifiers of the papers harvested so far whose timestamp is the high-water mark.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub seen_at_mark: Vec<String>,
+    /// When the last run finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+pub struct IncrementalHarvester {
+    query: ArXiv,
+    path: PathBuf,
+    by: SortBy,
+    pub state: HarvestState,
+}
+
+impl IncrementalHarvester {
+    /// Harvest `query`, keeping the state in the TOML (`.toml`) or JSON (`.json`) file at `path`.
+    ///
+    /// The state is loaded from `path` if the file exists.
+    pub fn new<P: AsRef<Path>>(query: ArXiv, path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let state = if path.exists() {
+            load_file(&path)?
+        } else {
+            HarvestState::default()
+        };
+        return Ok(IncrementalHarvester {
+            query,
+            path,
+            by: SortBy::LastUpdatedDate,
+            state,
+        });
+    }
+
+    /// Track the last updated date (the default) or the submission date of the papers.
+    ///
+    /// [`SortBy::Relevance`] has no timestamp and is treated as [`SortBy::LastUpdatedDate`].
+    pub fn by(&mut self, by: SortBy) -> &mut Self {
+        self.by = match by {
+            SortBy::SubmittedDate => SortBy::SubmittedDate,
+            _ => SortBy::LastUpdatedDate,
+        };
+        return self;
+    }
+
+    /// Fetch the papers not harvested yet, newest first, then advance the high-water mark and save the state.
+    pub async fn run(&mut self) -> Result<Vec<Paper>> {
+        let mut query = self.query.clone();
+        query
+            .sort_by(self.by.clone())
+            .sort_order(SortOrder::Descending);
+
+        let mut papers = Vec::new();
+        let mut newest = self.state.high_water_mark;
+        let mut seen_at_newest = self.state.seen_at_mark.clone();
+        let mut stream = std::pin::pin!(query.stream());
+        while let Some(paper) = stream.try_next().await? {
+            let timestamp = self.timestamp(&paper);
+            if self.is_older(timestamp) {
+                break;
+            }
+            if !self.is_new(&paper) {
+                continue;
+            }
+            match (newest, timestamp) {
+                (Some(newest), Some(timestamp)) if timestamp < newest => (),
+                (_, Some(timestamp)) if newest == Some(timestamp) => {
+                    seen_at_newest.push(paper.canonical_id());
+                }
+                (_, Some(timestamp)) => {
+                    newest = Some(timestamp);
+                    seen_at_newest = vec![paper.canonical_id()];
+                }
+                (_, None) => (),
+            }
+            papers.push(paper);
+        }
+
+        self.state.high_water_mark = newest;
+        self.state.seen_at_mark = seen_at_newest;
+        self.state.last_run = Some(Utc::now());
+        save_file(&self.state, &self.path)?;
+        return Ok(papers);
+    }
+
+    fn timestamp(&self, paper: &Paper) -> Option<DateTime<Utc>> {
+        match self.by {
+            SortBy::SubmittedDate => return paper.published,
+            _ => return paper.updated,
+        }
+    }
+
+    /// Whether `timestamp` is older than the high-water mark, so that the papers after it were harvested before.
+    fn is_older(&self, timestamp: Option<DateTime<Utc>>) -> bool {
+        return matches!(
+            (self.state.high_water_mark, timestamp),
+            (Some(mark), Some(timestamp)) if timestamp < mark
+        );
+    }
+
+    /// Whether `paper` is newer than the high-water mark, or at the mark but not harvested yet.
+    ///
+    /// Papers without a readable timestamp are kept, so a malformed entry does not end the run early.
+    fn is_new(&self, paper: &Paper) -> bool {
+        match (self.state.high_water_mark, self.timestamp(paper)) {
+            (Some(mark), Some(timestamp)) if timestamp == mark => {
+                return !self.state.seen_at_mark.contains(&paper.canonical_id());
+            }
+            (Some(mark), Some(timestamp)) => return timestamp > mark,
+            _ => return true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,113 @@
+use super::*;
+use crate::transport::{async_trait, Transport};
+use crate::{ArxivError, ArxivSession, QueryParams};
+use bytes::Bytes;
+use url::Url;
+
+#[test]
+fn test_high_water_mark() {
+    let path = std::env::temp_dir().join("arxiv_tools_harvest_state.json");
+    let state = HarvestState {
+        high_water_mark: Some("2024-12-01T12:00:00Z".parse().unwrap()),
+        seen_at_mark: Vec::new(),
+        last_run: None,
+    };
+    save_file(&state, &path).unwrap();
+
+    let query = ArXiv::from_args(QueryParams::title("llm"));
+    let mut harvester = IncrementalHarvester::new(query, &path).unwrap();
+    assert_eq!(harvester.state, state);
+
+    let paper = Paper {
+        published: "2024-11-30T09:00:00Z".parse().ok(),
+        updated: "2024-12-02T09:00:00Z".parse().ok(),
+        ..Default::default()
+    };
+    assert!(harvester.is_new(&paper));
+    harvester.by(SortBy::SubmittedDate);
+    assert!(!harvester.is_new(&paper));
+    assert!(harvester.is_older(harvester.timestamp(&paper)));
+    assert!(harvester.is_new(&Paper::default()));
+}
+
+/// Serves the papers `2412.00001v1` to `2412.00004v1`, newest first: two updated at noon, then one at 11:00 and one
+/// at 10:00.
+#[derive(Debug)]
+struct Updates;
+
+#[async_trait]
+impl Transport for Updates {
+    async fn get(&self, url: &Url) -> Result<Bytes, ArxivError> {
+        let start = url
+            .query_pairs()
+            .find(|(key, _)| key == "start")
+            .map(|(_, value)| value.parse::<usize>().unwrap())
+            .unwrap();
+        let entries = ["12", "12", "11", "10"]
+            .iter()
+            .enumerate()
+            .skip(start)
+            .map(|(i, hour)| {
+                return format!(
+                    "<entry><id>http://arxiv.org/abs/2412.0000{}v1</id>\
+                     <updated>2024-12-01T{}:00:00Z</updated><title>Paper {}</title></entry>",
+                    i + 1,
+                    hour,
+                    i + 1
+                );
+            })
+            .collect::<String>();
+        return Ok(Bytes::from(format!(
+            "<feed xmlns=\"http://www.w3.org/2005/Atom\" xmlns:opensearch=\"http://a9.com/-/spec/opensearch/1.1/\">\
+             <opensearch:totalResults>4</opensearch:totalResults>{}</feed>",
+            entries
+        )));
+    }
+}
+
+#[tokio::test]
+async fn test_papers_at_the_mark() {
+    let path = std::env::temp_dir().join(format!(
+        "arxiv_tools_harvest_mark_{}.json",
+        std::process::id()
+    ));
+    // the previous run ended at noon after seeing only the first paper updated then
+    let state = HarvestState {
+        high_water_mark: Some("2024-12-01T12:00:00Z".parse().unwrap()),
+        seen_at_mark: vec![String::from("2412.00001v1")],
+        last_run: None,
+    };
+    save_file(&state, &path).unwrap();
+
+    let session = ArxivSession::with_transport(Updates);
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::ZERO);
+    let query = session.query(QueryParams::title("llm"));
+    let mut harvester = IncrementalHarvester::new(query.clone(), &path).unwrap();
+    let papers = harvester.run().await.unwrap();
+    assert_eq!(
+        papers
+            .iter()
+            .map(|paper| paper.title.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["Paper 2"]
+    );
+    assert_eq!(harvester.state.high_water_mark, state.high_water_mark);
+    assert_eq!(
+        harvester.state.seen_at_mark,
+        vec![String::from("2412.00001v1"), String::from("2412.00002v1")]
+    );
+
+    // nothing is harvested twice
+    let mut harvester = IncrementalHarvester::new(query.clone(), &path).unwrap();
+    assert!(harvester.run().await.unwrap().is_empty());
+
+    // a first run advances the mark to the newest papers
+    std::fs::remove_file(&path).unwrap();
+    let mut harvester = IncrementalHarvester::new(query, &path).unwrap();
+    assert_eq!(harvester.run().await.unwrap().len(), 4);
+    assert_eq!(harvester.state.high_water_mark, state.high_water_mark);
+    assert_eq!(harvester.state.seen_at_mark.len(), 2);
+    std::fs::remove_file(&path).unwrap();
+}
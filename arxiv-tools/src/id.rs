@@ -0,0 +1,144 @@
+//! # Identifiers
+//! arXiv identifiers, in the current `YYMM.NNNNN` scheme or the legacy `archive/YYMMNNN` one, with an optional
+//! version. They are parsed from bare identifiers, `arXiv:` references and abs or pdf URLs such as [`Paper::id`]:
+//! ```rust
+//! # use arxiv_tools::ArxivId;
+//! let id: ArxivId = "http://arxiv.org/abs/2412.01234v2".parse().unwrap();
+//! assert_eq!(id.base(), "2412.01234");
+//! assert_eq!(id.version(), Some(2));
+//! assert_eq!(id.to_string(), "2412.01234v2");
+//!
+//! let legacy: ArxivId = "arXiv:math/0309136".parse().unwrap();
+//! assert_eq!(legacy.base(), "math/0309136");
+//! assert_eq!(legacy.version(), None);
+//! assert!(legacy.is_legacy());
+//! ```
+//!
+//! [`Paper::id`]: crate::Paper::id
+use crate::ArxivError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(test)]
+mod tests;
+
+/// A parsed arXiv identifier.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ArxivId {
+    base: String,
+    version: Option<u32>,
+}
+
+impl ArxivId {
+    /// The identifier without its version, e.g. `2412.01234` or `math/0309136`.
+    pub fn base(&self) -> &str {
+        return &self.base;
+    }
+    /// The version, e.g. `2` for `2412.01234v2`, or `None` if the identifier has none.
+    pub fn version(&self) -> Option<u32> {
+        return self.version;
+    }
+    /// Whether the identifier is in the scheme used before April 2007, e.g. `math/0309136`.
+    pub fn is_legacy(&self) -> bool {
+        return self.base.contains('/');
+    }
+    /// The abstract page, e.g. `https://arxiv.org/abs/2412.01234v2`.
+    pub fn abs_url(&self) -> String {
+        return format!("https://arxiv.org/abs/{}", self);
+    }
+    /// The PDF, e.g. `https://arxiv.org/pdf/2412.01234v2`.
+    pub fn pdf_url(&self) -> String {
+        return format!("https://arxiv.org/pdf/{}", self);
+    }
+    /// The HTML rendering on ar5iv, e.g. `https://ar5iv.labs.arxiv.org/html/2412.01234`.
+    ///
+    /// ar5iv renders the latest version only, so the version is left out.
+    pub fn html_url(&self) -> String {
+        return format!("https://ar5iv.labs.arxiv.org/html/{}", self.base);
+    }
+}
+
+impl fmt::Display for ArxivId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.version {
+            Some(version) => return write!(f, "{}v{}", self.base, version),
+            None => return f.write_str(&self.base),
+        }
+    }
+}
+
+impl FromStr for ArxivId {
+    type Err = ArxivError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || return ArxivError::InvalidId(text.to_string());
+        let mut id = text.trim();
+        if let Some(start) = ["/abs/", "/pdf/"].iter().find_map(|path| id.find(path)) {
+            id = &id[start + 5..];
+            id = id.strip_suffix(".pdf").unwrap_or(id);
+        }
+        if id
+            .get(..6)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("arxiv:"))
+        {
+            id = &id[6..];
+        }
+
+        let (base, version) = match id.rsplit_once('v') {
+            Some((base, version)) if is_digits(version) => {
+                (base, Some(version.parse().map_err(|_| invalid())?))
+            }
+            _ => (id, None),
+        };
+        let valid = match base.split_once('/') {
+            Some((archive, number)) => {
+                is_archive(archive) && number.len() == 7 && is_digits(number)
+            }
+            None => match base.split_once('.') {
+                Some((month, number)) => {
+                    month.len() == 4
+                        && is_digits(month)
+                        && (4..=5).contains(&number.len())
+                        && is_digits(number)
+                }
+                None => false,
+            },
+        };
+        if !valid {
+            return Err(invalid());
+        }
+        return Ok(ArxivId {
+            base: base.to_string(),
+            version,
+        });
+    }
+}
+
+impl Serialize for ArxivId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.collect_str(self);
+    }
+}
+
+impl<'de> Deserialize<'de> for ArxivId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        return id.parse().map_err(serde::de::Error::custom);
+    }
+}
+
+fn is_digits(text: &str) -> bool {
+    return !text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit());
+}
+
+/// Whether `archive` is a legacy archive, optionally with a subject class, e.g. `hep-th` or `math.CO`.
+fn is_archive(archive: &str) -> bool {
+    let (name, class) = archive.split_once('.').unwrap_or((archive, "AA"));
+    return !name.is_empty()
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_lowercase() || byte == b'-')
+        && class.len() == 2
+        && class.bytes().all(|byte| byte.is_ascii_uppercase());
+}
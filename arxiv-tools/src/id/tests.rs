@@ -0,0 +1,90 @@
+use super::*;
+
+#[test]
+fn test_parse_id() {
+    let cases = [
+        ("2412.01234v2", "2412.01234", Some(2)),
+        ("2412.01234", "2412.01234", None),
+        ("0704.0001v1", "0704.0001", Some(1)),
+        ("http://arxiv.org/abs/1706.03762v7", "1706.03762", Some(7)),
+        (
+            "https://arxiv.org/pdf/1706.03762v7.pdf",
+            "1706.03762",
+            Some(7),
+        ),
+        ("https://arxiv.org/pdf/1706.03762", "1706.03762", None),
+        ("arXiv:1706.03762", "1706.03762", None),
+        (" arxiv:1706.03762v12 ", "1706.03762", Some(12)),
+        ("math/0309136", "math/0309136", None),
+        (
+            "http://arxiv.org/abs/hep-th/9901001v3",
+            "hep-th/9901001",
+            Some(3),
+        ),
+        ("math.GT/0309136v1", "math.GT/0309136", Some(1)),
+    ];
+    for (text, base, version) in cases {
+        let id: ArxivId = text.parse().unwrap();
+        assert_eq!(id.base(), base, "{}", text);
+        assert_eq!(id.version(), version, "{}", text);
+        assert_eq!(id.is_legacy(), base.contains('/'), "{}", text);
+    }
+}
+
+#[test]
+fn test_invalid_id() {
+    for text in [
+        "",
+        "llm",
+        "ärxiv:1706.03762",
+        "2412.123",
+        "2412.123456",
+        "24120.1234",
+        "2412.01234v",
+        "2412.01234vx",
+        "Math/0309136",
+        "math/030913",
+        "math.gt/0309136",
+        "http://arxiv.org/list/cs.CL/recent",
+    ] {
+        match text.parse::<ArxivId>() {
+            Err(ArxivError::InvalidId(invalid)) => assert_eq!(invalid, text),
+            other => panic!("{:?} parsed as {:?}", text, other),
+        }
+    }
+}
+
+#[test]
+fn test_id_display_and_serde() {
+    let id: ArxivId = "http://arxiv.org/abs/2412.01234v2".parse().unwrap();
+    assert_eq!(id.to_string(), "2412.01234v2");
+    assert_eq!(id.to_string().parse::<ArxivId>().unwrap(), id);
+
+    let legacy: ArxivId = "arXiv:math/0309136".parse().unwrap();
+    assert_eq!(legacy.to_string(), "math/0309136");
+
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, "\"2412.01234v2\"");
+    assert_eq!(serde_json::from_str::<ArxivId>(&json).unwrap(), id);
+    assert!(serde_json::from_str::<ArxivId>("\"llm\"").is_err());
+}
+
+#[test]
+fn test_id_urls() {
+    let id: ArxivId = "2412.01234v2".parse().unwrap();
+    assert_eq!(id.abs_url(), "https://arxiv.org/abs/2412.01234v2");
+    assert_eq!(id.pdf_url(), "https://arxiv.org/pdf/2412.01234v2");
+    assert_eq!(
+        id.html_url(),
+        "https://ar5iv.labs.arxiv.org/html/2412.01234"
+    );
+
+    let legacy: ArxivId = "hep-th/9901001".parse().unwrap();
+    assert_eq!(legacy.abs_url(), "https://arxiv.org/abs/hep-th/9901001");
+    assert_eq!(legacy.pdf_url(), "https://arxiv.org/pdf/hep-th/9901001");
+    assert_eq!(
+        legacy.html_url(),
+        "https://ar5iv.labs.arxiv.org/html/hep-th/9901001"
+    );
+    assert_eq!(legacy.pdf_url().parse::<ArxivId>().unwrap(), legacy);
+}
@@ -0,0 +1,70 @@
+//! # Interning
+//! Long-running collectors parse the same category codes and author names over and over. An [`Interner`] keeps
+//! one shared copy of each of them, so every paper listing `cs.CL` points to the same allocation:
+//! ```rust
+//! # use arxiv_tools::{ArxivSession, Interner};
+//! # use std::sync::Arc;
+//! let interner = Interner::new();
+//! let session = ArxivSession::new();
+//! session.set_interner(interner.clone());
+//!
+//! let first = interner.intern("cs.CL");
+//! let second = interner.intern("cs.CL");
+//! assert!(Arc::ptr_eq(&first, &second));
+//! assert_eq!(interner.len(), 1);
+//! ```
+//! Without an interner, every paper owns its own copies.
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+#[cfg(test)]
+mod tests;
+
+/// A pool of shared strings. Clones share the same pool.
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    pool: Arc<Mutex<HashSet<Arc<str>>>>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        return Interner::default();
+    }
+
+    /// The pooled copy of `text`, added to the pool if it is not there yet.
+    pub fn intern(&self, text: &str) -> Arc<str> {
+        let mut pool = self.pool.lock().unwrap();
+        if let Some(interned) = pool.get(text) {
+            return interned.clone();
+        }
+        let interned: Arc<str> = Arc::from(text);
+        pool.insert(interned.clone());
+        return interned;
+    }
+
+    /// The number of distinct strings in the pool.
+    pub fn len(&self) -> usize {
+        return self.pool.lock().unwrap().len();
+    }
+
+    /// Whether nothing was interned yet.
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// Drop the strings no paper refers to anymore, e.g. after a batch of papers was written out.
+    pub fn shrink(&self) {
+        self.pool
+            .lock()
+            .unwrap()
+            .retain(|interned| Arc::strong_count(interned) > 1);
+    }
+}
+
+/// `text` from `interner` if there is one, else a new copy.
+pub(crate) fn intern(interner: Option<&Interner>, text: &str) -> Arc<str> {
+    match interner {
+        Some(interner) => return interner.intern(text),
+        None => return Arc::from(text),
+    }
+}
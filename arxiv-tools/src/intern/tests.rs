@@ -0,0 +1,42 @@
+use super::*;
+use crate::{parse_atom, ArxivSession, QueryParams};
+
+#[test]
+fn test_intern() {
+    let interner = Interner::new();
+    assert!(interner.is_empty());
+
+    let first = interner.intern("cs.CL");
+    let second = interner.clone().intern("cs.CL");
+    let other = interner.intern("cs.LG");
+    assert!(Arc::ptr_eq(&first, &second));
+    assert!(!Arc::ptr_eq(&first, &other));
+    assert_eq!(interner.len(), 2);
+
+    drop(other);
+    interner.shrink();
+    assert_eq!(interner.len(), 1);
+    assert!(Arc::ptr_eq(&first, &interner.intern("cs.CL")));
+}
+
+#[test]
+fn test_parse_with_interner() {
+    let xml = include_str!("../../fixtures/journal_ref.xml");
+    let session = ArxivSession::new();
+    session.set_interner(Interner::new());
+    let arxiv = session.query(QueryParams::title("test"));
+
+    let first = arxiv.parse_xml(xml.to_string()).unwrap().papers;
+    let second = arxiv.parse_xml(xml.to_string()).unwrap().papers;
+    assert!(Arc::ptr_eq(
+        &first[0].primary_category,
+        &second[0].primary_category
+    ));
+    assert!(Arc::ptr_eq(
+        &first[0].authors[0].name,
+        &second[0].authors[0].name
+    ));
+    let plain = parse_atom(xml).unwrap().papers;
+    assert_eq!(first[0].categories, plain[0].categories);
+    assert_eq!(first[0].authors, plain[0].authors);
+}
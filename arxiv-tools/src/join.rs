@@ -0,0 +1,161 @@
+//! # Store Join
+//! Fresh query results joined against a store of papers kept from earlier runs, e.g. a harvest database, to tell
+//! which papers are new, which changed and which didn't:
+//! ```rust,no_run
+//! # use arxiv_tools::{ArXiv, Paper, QueryParams};
+//! # use arxiv_tools::join::Change;
+//! # use futures::TryStreamExt;
+//! # use std::collections::HashMap;
+//! # #[tokio::main]
+//! # async fn main() {
+//! let store: HashMap<String, Paper> = HashMap::new();
+//! let arxiv = ArXiv::from_args(QueryParams::title("transformer"));
+//! let mut changes = std::pin::pin!(arxiv.join(&store));
+//! while let Some(change) = changes.try_next().await.unwrap() {
+//!     match change {
+//!         Change::Insert(paper) => println!("new: {}", paper.title),
+//!         Change::Update { new, diff, .. } => println!("changed: {} {:?}", new.title, diff),
+//!         Change::Unchanged(_) => (),
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! The results are joined page by page: the papers of a page are looked up in the store at once, and only one page
+//! is held in memory, whatever the size of the results or of the store.
+use crate::{ArXiv, ArxivError, Pager, Paper, PaperDiff};
+use async_trait::async_trait;
+use futures::stream::{self, Stream};
+use std::collections::{HashMap, VecDeque};
+
+#[cfg(test)]
+mod tests;
+
+/// Papers kept from earlier runs, looked up by [`Paper::id_without_version`], so that every version of a paper
+/// joins its stored record.
+#[async_trait]
+pub trait Store: Send + Sync {
+    /// The stored record of the paper `id`, if any.
+    async fn get(&self, id: &str) -> Result<Option<Paper>, ArxivError>;
+
+    /// The stored records of the papers `ids`, by id. Papers that aren't stored are missing.
+    ///
+    /// The default implementation looks the papers up one by one; stores that can look up many papers at once, e.g.
+    /// with a single database query, override it.
+    async fn get_many(&self, ids: &[String]) -> Result<HashMap<String, Paper>, ArxivError> {
+        let mut papers = HashMap::new();
+        for id in ids {
+            if let Some(paper) = self.get(id).await? {
+                papers.insert(id.clone(), paper);
+            }
+        }
+        return Ok(papers);
+    }
+}
+
+/// A store in memory, keyed by [`Paper::id_without_version`].
+#[async_trait]
+impl Store for HashMap<String, Paper> {
+    async fn get(&self, id: &str) -> Result<Option<Paper>, ArxivError> {
+        return Ok(HashMap::get(self, id).cloned());
+    }
+}
+
+/// A fresh paper joined against its stored record.
+#[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum Change {
+    /// The paper isn't stored yet.
+    Insert(Paper),
+    /// The paper is stored, but its version, its `updated` date or a field compared by [`Paper::diff`] changed.
+    Update {
+        old: Paper,
+        new: Paper,
+        diff: PaperDiff,
+    },
+    /// The paper is stored as it is.
+    Unchanged(Paper),
+}
+
+impl Change {
+    fn of(old: Option<Paper>, new: Paper) -> Self {
+        let Some(old) = old else {
+            return Change::Insert(new);
+        };
+        let diff = old.diff(&new);
+        if diff.is_empty() && old.updated == new.updated && old.version() == new.version() {
+            return Change::Unchanged(new);
+        }
+        return Change::Update { old, new, diff };
+    }
+
+    /// The fresh paper.
+    pub fn paper(&self) -> &Paper {
+        match self {
+            Change::Insert(paper) | Change::Unchanged(paper) => return paper,
+            Change::Update { new, .. } => return new,
+        }
+    }
+}
+
+/// The changes of the papers of a page, looked up in `store` at once.
+async fn join_page<S: Store + ?Sized>(
+    store: &S,
+    papers: Vec<Paper>,
+) -> Result<Vec<Change>, ArxivError> {
+    let ids = papers
+        .iter()
+        .map(|paper| paper.id_without_version())
+        .collect::<Vec<String>>();
+    let stored = store.get_many(&ids).await?;
+    return Ok(papers
+        .into_iter()
+        .zip(&ids)
+        .map(|(paper, id)| Change::of(stored.get(id).cloned(), paper))
+        .collect());
+}
+
+impl ArXiv {
+    /// Stream the papers of the query, paged as by [`ArXiv::pager`], joined against `store`. See [`crate::join`].
+    ///
+    /// The stream ends after the last page or the first error, of the query or of the store.
+    pub fn join<'a, S: Store + ?Sized>(
+        &self,
+        store: &'a S,
+    ) -> impl Stream<Item = Result<Change, ArxivError>> + 'a {
+        let state = Join {
+            pager: Some(self.pager()),
+            store,
+            buffer: VecDeque::new(),
+        };
+        return stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(change) = state.buffer.pop_front() {
+                    return Some((Ok(change), state));
+                }
+                let page = match state.pager.as_mut()?.next_page().await {
+                    Some(Ok(page)) => page,
+                    Some(Err(e)) => {
+                        state.pager = None;
+                        return Some((Err(e), state));
+                    }
+                    None => return None,
+                };
+                match join_page(state.store, page.papers).await {
+                    Ok(changes) => state.buffer.extend(changes),
+                    Err(e) => {
+                        state.pager = None;
+                        return Some((Err(e), state));
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// State of [`ArXiv::join`]: the pager of the query, `None` after an error, and the changes of the current page.
+struct Join<'a, S: ?Sized> {
+    pager: Option<Pager>,
+    store: &'a S,
+    buffer: VecDeque<Change>,
+}
@@ -0,0 +1,119 @@
+use super::*;
+use crate::transport::Transport;
+use crate::{ArxivSession, QueryParams};
+use bytes::Bytes;
+use futures::StreamExt;
+use url::Url;
+
+/// Serves the papers `2101.00001v2` to `2101.00005v2`, titled `Paper 1` to `Paper 5`, paged by `start`.
+#[derive(Debug)]
+struct Feed;
+
+#[async_trait]
+impl Transport for Feed {
+    async fn get(&self, url: &Url) -> Result<Bytes, ArxivError> {
+        let param = |name: &str| {
+            return url
+                .query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.parse::<usize>().unwrap())
+                .unwrap();
+        };
+        let entries = (1..=5)
+            .skip(param("start"))
+            .take(param("max_results"))
+            .map(|i| {
+                return format!(
+                    "<entry><id>http://arxiv.org/abs/2101.0000{}v2</id><title>Paper {}</title></entry>",
+                    i, i
+                );
+            })
+            .collect::<String>();
+        return Ok(Bytes::from(format!(
+            "<feed xmlns=\"http://www.w3.org/2005/Atom\" xmlns:opensearch=\"http://a9.com/-/spec/opensearch/1.1/\">\
+             <opensearch:totalResults>5</opensearch:totalResults>{}</feed>",
+            entries
+        )));
+    }
+}
+
+/// A store recording the lookups it answers.
+#[derive(Debug, Default)]
+struct Recording {
+    papers: HashMap<String, Paper>,
+    lookups: std::sync::Mutex<Vec<Vec<String>>>,
+}
+
+#[async_trait]
+impl Store for Recording {
+    async fn get(&self, id: &str) -> Result<Option<Paper>, ArxivError> {
+        return Store::get(&self.papers, id).await;
+    }
+
+    async fn get_many(&self, ids: &[String]) -> Result<HashMap<String, Paper>, ArxivError> {
+        self.lookups.lock().unwrap().push(ids.to_vec());
+        if ids.iter().any(|id| id == "2101.00005") {
+            return Err(ArxivError::Store(String::from("connection lost")));
+        }
+        return self.papers.get_many(ids).await;
+    }
+}
+
+#[tokio::test]
+async fn test_join() {
+    let session = ArxivSession::with_transport(Feed);
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::ZERO);
+    let mut arxiv = session.query(QueryParams::title("paper"));
+    arxiv.max_results(2);
+
+    let stored = |id: &str, title: &str| {
+        let paper = Paper::builder().id(id).title(title).build();
+        return (paper.id_without_version(), paper);
+    };
+    let store = Recording {
+        papers: HashMap::from([
+            stored("2101.00001v2", "Paper 1"),
+            stored("2101.00003v1", "An Older Title"),
+            stored("2101.00004v1", "Paper 4"),
+        ]),
+        ..Default::default()
+    };
+    let changes = arxiv.join(&store).collect::<Vec<_>>().await;
+    assert_eq!(changes.len(), 5);
+    assert!(matches!(&changes[0], Ok(Change::Unchanged(paper)) if paper.title == "Paper 1"));
+    assert!(matches!(&changes[1], Ok(Change::Insert(paper)) if paper.title == "Paper 2"));
+    match &changes[2] {
+        Ok(Change::Update { old, new, diff }) => {
+            assert_eq!(old.title, "An Older Title");
+            assert_eq!(new.title, "Paper 3");
+            assert_eq!(
+                diff.title,
+                Some((String::from("An Older Title"), String::from("Paper 3")))
+            );
+        }
+        change => panic!("unexpected change {:?}", change),
+    }
+    // a new version is an update, even if none of the compared fields changed
+    match &changes[3] {
+        Ok(Change::Update { old, new, diff }) => {
+            assert_eq!((old.version(), new.version()), (Some(1), Some(2)));
+            assert!(diff.is_empty());
+        }
+        change => panic!("unexpected change {:?}", change),
+    }
+    assert_eq!(changes[3].as_ref().unwrap().paper().title, "Paper 4");
+    // the store fails on the last page, which ends the stream
+    assert!(matches!(changes[4], Err(ArxivError::Store(_))));
+
+    // one lookup per page
+    assert_eq!(
+        *store.lookups.lock().unwrap(),
+        vec![
+            vec![String::from("2101.00001"), String::from("2101.00002")],
+            vec![String::from("2101.00003"), String::from("2101.00004")],
+            vec![String::from("2101.00005")],
+        ]
+    );
+}
@@ -0,0 +1,234 @@
+//! # LaTeX
+//! Opt-in post-processing of the LaTeX in abstracts, for plain-text displays and search indexes:
+//! ```rust
+//! # use arxiv_tools::Latex;
+//! let text = r#"A G\"odel numbering of paths of length $n \leq 10$, with error $O(n^{2})$"#;
+//! assert_eq!(Latex::Raw.apply(text), text);
+//! assert_eq!(
+//!     Latex::StripDelimiters.apply(text),
+//!     r#"A G\"odel numbering of paths of length n \leq 10, with error O(n^{2})"#
+//! );
+//! assert_eq!(
+//!     Latex::Unicode.apply(text),
+//!     "A Gödel numbering of paths of length n ≤ 10, with error O(n^2)"
+//! );
+//! ```
+//! Commands without a Unicode equivalent in the table are kept as they are.
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// How the LaTeX of abstracts is post-processed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Latex {
+    /// As sent by the API.
+    #[default]
+    Raw,
+    /// Without the math delimiters `$`, `$$`, `\(`, `\)`, `\[` and `\]`; `\$` becomes `$`.
+    StripDelimiters,
+    /// Without math delimiters, with common symbols (`\alpha`, `\leq`, `\infty`, ...) and accents (`\"o`, `\'e`, ...)
+    /// converted to Unicode, escaped characters (`\%`, `\&`, ...) unescaped and grouping braces removed.
+    Unicode,
+}
+
+impl Latex {
+    /// `text` post-processed according to `self`.
+    pub fn apply(&self, text: &str) -> String {
+        match self {
+            Latex::Raw => return text.to_string(),
+            Latex::StripDelimiters => return strip_delimiters(text),
+            Latex::Unicode => return to_unicode(&strip_delimiters(text)),
+        }
+    }
+}
+
+fn strip_delimiters(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '$' => {}
+            '\\' => match chars.peek() {
+                Some('$') => {
+                    stripped.push('$');
+                    chars.next();
+                }
+                Some('(' | ')' | '[' | ']') => {
+                    chars.next();
+                }
+                _ => stripped.push('\\'),
+            },
+            _ => stripped.push(c),
+        }
+    }
+    return stripped;
+}
+
+fn to_unicode(text: &str) -> String {
+    let mut converted = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' => {}
+            '\\' => match chars.peek().copied() {
+                Some(escaped @ ('%' | '&' | '_' | '#' | '{' | '}' | '$')) => {
+                    converted.push(escaped);
+                    chars.next();
+                }
+                Some(accent @ ('"' | '\'' | '`' | '^' | '~')) => {
+                    chars.next();
+                    let braced = chars.next_if_eq(&'{').is_some();
+                    let letter = chars.next();
+                    if braced {
+                        chars.next_if_eq(&'}');
+                    }
+                    match letter.and_then(|letter| accented(accent, letter)) {
+                        Some(accented) => converted.push(accented),
+                        None => {
+                            converted.push('\\');
+                            converted.push(accent);
+                            converted.extend(letter);
+                        }
+                    }
+                }
+                Some(letter) if letter.is_ascii_alphabetic() => {
+                    let mut name = String::new();
+                    while let Some(letter) = chars.next_if(|c| c.is_ascii_alphabetic()) {
+                        name.push(letter);
+                    }
+                    match symbol(&name) {
+                        Some(symbol) => converted.push(symbol),
+                        None => {
+                            converted.push('\\');
+                            converted.push_str(&name);
+                        }
+                    }
+                }
+                _ => converted.push('\\'),
+            },
+            _ => converted.push(c),
+        }
+    }
+    return converted;
+}
+
+/// The Unicode character of `letter` with the accent command `accent`, e.g. `ö` for `\"o`.
+fn accented(accent: char, letter: char) -> Option<char> {
+    let accented = match (accent, letter) {
+        ('"', 'a') => 'ä',
+        ('"', 'e') => 'ë',
+        ('"', 'i') => 'ï',
+        ('"', 'o') => 'ö',
+        ('"', 'u') => 'ü',
+        ('"', 'A') => 'Ä',
+        ('"', 'O') => 'Ö',
+        ('"', 'U') => 'Ü',
+        ('\'', 'a') => 'á',
+        ('\'', 'e') => 'é',
+        ('\'', 'i') => 'í',
+        ('\'', 'o') => 'ó',
+        ('\'', 'u') => 'ú',
+        ('\'', 'E') => 'É',
+        ('`', 'a') => 'à',
+        ('`', 'e') => 'è',
+        ('`', 'i') => 'ì',
+        ('`', 'o') => 'ò',
+        ('`', 'u') => 'ù',
+        ('^', 'a') => 'â',
+        ('^', 'e') => 'ê',
+        ('^', 'i') => 'î',
+        ('^', 'o') => 'ô',
+        ('^', 'u') => 'û',
+        ('~', 'a') => 'ã',
+        ('~', 'n') => 'ñ',
+        ('~', 'o') => 'õ',
+        ('~', 'N') => 'Ñ',
+        _ => return None,
+    };
+    return Some(accented);
+}
+
+/// The Unicode character of the command `\name`, e.g. `α` for `\alpha`.
+fn symbol(name: &str) -> Option<char> {
+    let symbol = match name {
+        "alpha" => 'α',
+        "beta" => 'β',
+        "gamma" => 'γ',
+        "delta" => 'δ',
+        "epsilon" | "varepsilon" => 'ε',
+        "zeta" => 'ζ',
+        "eta" => 'η',
+        "theta" | "vartheta" => 'θ',
+        "iota" => 'ι',
+        "kappa" => 'κ',
+        "lambda" => 'λ',
+        "mu" => 'μ',
+        "nu" => 'ν',
+        "xi" => 'ξ',
+        "pi" => 'π',
+        "rho" => 'ρ',
+        "sigma" => 'σ',
+        "tau" => 'τ',
+        "upsilon" => 'υ',
+        "phi" | "varphi" => 'φ',
+        "chi" => 'χ',
+        "psi" => 'ψ',
+        "omega" => 'ω',
+        "Gamma" => 'Γ',
+        "Delta" => 'Δ',
+        "Theta" => 'Θ',
+        "Lambda" => 'Λ',
+        "Xi" => 'Ξ',
+        "Pi" => 'Π',
+        "Sigma" => 'Σ',
+        "Phi" => 'Φ',
+        "Psi" => 'Ψ',
+        "Omega" => 'Ω',
+        "leq" | "le" => '≤',
+        "geq" | "ge" => '≥',
+        "neq" | "ne" => '≠',
+        "ll" => '≪',
+        "gg" => '≫',
+        "approx" => '≈',
+        "sim" => '∼',
+        "simeq" => '≃',
+        "equiv" => '≡',
+        "propto" => '∝',
+        "times" => '×',
+        "cdot" => '·',
+        "circ" => '∘',
+        "pm" => '±',
+        "mp" => '∓',
+        "infty" => '∞',
+        "partial" => '∂',
+        "nabla" => '∇',
+        "sum" => '∑',
+        "prod" => '∏',
+        "int" => '∫',
+        "sqrt" => '√',
+        "ell" => 'ℓ',
+        "hbar" => 'ℏ',
+        "in" => '∈',
+        "notin" => '∉',
+        "subset" => '⊂',
+        "subseteq" => '⊆',
+        "cup" => '∪',
+        "cap" => '∩',
+        "emptyset" => '∅',
+        "forall" => '∀',
+        "exists" => '∃',
+        "to" | "rightarrow" => '→',
+        "leftarrow" => '←',
+        "leftrightarrow" => '↔',
+        "Rightarrow" | "implies" => '⇒',
+        "Leftrightarrow" | "iff" => '⇔',
+        "langle" => '⟨',
+        "rangle" => '⟩',
+        "ldots" | "dots" => '…',
+        "cdots" => '⋯',
+        _ => return None,
+    };
+    return Some(symbol);
+}
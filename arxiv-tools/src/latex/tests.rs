@@ -0,0 +1,40 @@
+use super::*;
+
+#[test]
+fn test_strip_delimiters() {
+    assert_eq!(
+        Latex::StripDelimiters.apply(r"inline $x^2$, display $$\sum_i x_i$$, \(a\) and \[b\]"),
+        r"inline x^2, display \sum_i x_i, a and b"
+    );
+    assert_eq!(
+        Latex::StripDelimiters.apply(r"costs \$5 per \alpha"),
+        r"costs $5 per \alpha"
+    );
+}
+
+#[test]
+fn test_unicode() {
+    assert_eq!(
+        Latex::Unicode.apply(r"$\alpha \to \infty$ with $\Delta \geq 0$"),
+        "α → ∞ with Δ ≥ 0"
+    );
+    assert_eq!(
+        Latex::Unicode.apply(r#"Schr\"odinger, Poincar\'{e} and {\~n}"#),
+        "Schrödinger, Poincaré and ñ"
+    );
+    assert_eq!(
+        Latex::Unicode.apply(r"50\% of R\&D, $\{x\}$ and $x_{ij}$"),
+        "50% of R&D, {x} and x_ij"
+    );
+    // commands without an equivalent are kept
+    assert_eq!(
+        Latex::Unicode.apply(r#"\mathcal{O} and \"z"#),
+        r#"\mathcalO and \"z"#
+    );
+}
+
+#[test]
+fn test_raw() {
+    let text = r#"$\alpha$ and {\"o}"#;
+    assert_eq!(Latex::Raw.apply(text), text);
+}
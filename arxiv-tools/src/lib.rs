@@ -31,7 +31,7 @@
 //!         QueryParams::subject_category(Category::CsAi),
 //!         QueryParams::subject_category(Category::CsLg),
 //!     ])]),
-//!     QueryParams::SubmittedDate(String::from("202412010000"), String::from("202412012359")),
+//!     QueryParams::submitted_date("20241201", "20241201").unwrap(),
 //! ]);
 //! let mut arxiv = ArXiv::from_args(args);
 //!
@@ -48,62 +48,113 @@
 //! assert!(response.len() > 0);
 //! # }
 //! ```
-use chrono::{DateTime, Utc};
-use quick_xml::events::Event;
-use quick_xml::reader::Reader;
-use reqwest as request;
+//!
+//! ## Query Definitions in Files
+//! A whole query, including the optional parameters, can be saved to and loaded from a TOML or JSON file.
+//! The format is chosen by the file extension.
+//! ```rust
+//! # use arxiv_tools::{ArXiv, QueryParams, SortBy};
+//! let mut arxiv = ArXiv::from_args(QueryParams::title("attention is all you need"));
+//! arxiv.max_results(10);
+//! arxiv.sort_by(SortBy::SubmittedDate);
+//!
+//! let path = std::env::temp_dir().join("arxiv_tools_doc_query.toml");
+//! arxiv.to_file(&path).unwrap();
+//!
+//! let loaded = ArXiv::from_file(&path).unwrap();
+//! assert_eq!(loaded.max_resutls, Some(10));
+//! ```
+#![allow(clippy::needless_return, clippy::inherent_to_string)]
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+compile_error!("enable a TLS backend: the `native-tls` (default) or the `rustls` feature");
+
+pub mod atom;
+pub mod builder;
+pub mod cache;
+pub mod category;
+pub mod circuit;
+pub mod client;
+pub mod diff;
+pub mod endpoint;
+pub mod error;
+pub mod export;
+pub mod harvest;
+pub mod id;
+pub mod intern;
+pub mod join;
+pub mod latex;
+pub mod listing;
+pub mod matching;
+pub mod middleware;
+pub mod pager;
+pub mod partition;
+pub mod profile;
+pub mod rate_limit;
+pub mod recency;
+pub mod replay;
+pub mod retry;
+pub mod schema;
+pub mod session;
+pub mod similar;
+pub mod stats;
+pub mod transport;
+pub mod versions;
+
+use anyhow::{bail, Result};
+use atom::FeedReader;
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use pager::ProgressHook;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Arc;
+use transport::BodyStream;
+use url::Url;
 use urlencoding::encode;
 
-pub enum Category {
-    CsAi,
-    CsCl,
-    CsLg,
-    CsGt,
-    CsCv,
-    CsCr,
-    CsCc,
-    CsCe,
-    CsCy,
-    CsDs,
-    CsDm,
-    CsDc,
-    CsEt,
-    CsFl,
-    CsGl,
-    CsGr,
-    CsAr,
-    CsHc,
-    CsIr,
-}
-
-impl Category {
-    pub fn to_string(&self) -> String {
+pub use atom::{parse_atom, parse_atom_with, FeedMeta, Whitespace};
+pub use builder::PaperBuilder;
+pub use category::{Archive, Category, CategorySet};
+pub use circuit::CircuitBreaker;
+pub use client::ClientConfig;
+pub use diff::PaperDiff;
+pub use endpoint::{Endpoint, Scheme};
+pub use error::ArxivError;
+pub use id::ArxivId;
+pub use intern::Interner;
+pub use latex::Latex;
+pub use pager::{Checkpoint, Pager, Progress};
+pub use profile::Profile;
+pub use rate_limit::RateLimiter;
+pub use retry::{RetryOn, RetryPolicy};
+pub use session::ArxivSession;
+pub use stats::ClientStats;
+
+/// Curated search scopes, each expanding to the categories commonly searched for the field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Scope {
+    MachineLearning,
+    NLP,
+    ComputerVision,
+    Robotics,
+}
+
+impl Scope {
+    pub fn categories(&self) -> Vec<Category> {
         match self {
-            Category::CsAi => String::from("cs.AI"),
-            Category::CsCl => String::from("cs.CL"),
-            Category::CsLg => String::from("cs.LG"),
-            Category::CsGt => String::from("cs.GT"),
-            Category::CsCv => String::from("cs.CV"),
-            Category::CsCr => String::from("cs.CR"),
-            Category::CsCc => String::from("cs.CC"),
-            Category::CsCe => String::from("cs.CE"),
-            Category::CsCy => String::from("cs.CY"),
-            Category::CsDs => String::from("cs.DS"),
-            Category::CsDm => String::from("cs.DM"),
-            Category::CsDc => String::from("cs.DC"),
-            Category::CsEt => String::from("cs.ET"),
-            Category::CsFl => String::from("cs.FL"),
-            Category::CsGl => String::from("cs.GL"),
-            Category::CsGr => String::from("cs.GR"),
-            Category::CsAr => String::from("cs.AR"),
-            Category::CsHc => String::from("cs.HC"),
-            Category::CsIr => String::from("cs.IR"),
+            Scope::MachineLearning => vec![Category::CsLg, Category::CsAi, Category::StatMl],
+            Scope::NLP => vec![Category::CsCl],
+            Scope::ComputerVision => vec![Category::CsCv, Category::EessIv],
+            Scope::Robotics => vec![Category::CsRo],
         }
     }
 }
 
-#[derive(Clone, Debug)]
+/// A query expression. Terms are stored as given and only encoded when the request URL is built.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum QueryParams {
     Title(String),
     Author(String),
@@ -114,10 +165,10 @@ pub enum QueryParams {
     ReportNumber(String),
     Id(String),
     All(String),
-    And(String),
-    Or(String),
-    AndNot(String),
-    Group(String),
+    And(Vec<QueryParams>),
+    Or(Vec<QueryParams>),
+    AndNot(Vec<QueryParams>),
+    Group(Vec<QueryParams>),
     SubmittedDate(String, String),
 }
 
@@ -127,7 +178,9 @@ impl Default for QueryParams {
     }
 }
 
-#[derive(Clone, Debug, Default)]
+/// Serialized as the arXiv parameter value, e.g. `"lastUpdatedDate"`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum SortBy {
     #[default]
     Relevance,
@@ -145,7 +198,9 @@ impl SortBy {
     }
 }
 
-#[derive(Clone, Debug, Default)]
+/// Serialized as the arXiv parameter value, e.g. `"descending"`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SortOrder {
     #[default]
     Ascending,
@@ -161,158 +216,1186 @@ impl SortOrder {
     }
 }
 
+/// Maximum number of results the API returns for a single request.
+pub const MAX_PAGE_SIZE: u64 = 2000;
+/// Number of results the API returns when `max_results` is not set.
+pub const DEFAULT_MAX_RESULTS: u64 = 10;
+/// Endpoint of the arXiv query API, i.e. the URL of the default [`Endpoint`].
+pub const DEFAULT_BASE_URL: &str = "https://export.arxiv.org/api/query";
+/// Default interval of the [`RateLimiter`], following arXiv's request to wait 3 seconds between calls.
+pub const REQUEST_DELAY: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Tokens used to render a query, either human-readable or in the request URL.
+struct Syntax {
+    encode: bool,
+    and: &'static str,
+    or: &'static str,
+    and_not: &'static str,
+    open: &'static str,
+    close: &'static str,
+    space: &'static str,
+}
+
+const READABLE_SYNTAX: Syntax = Syntax {
+    encode: false,
+    and: " AND ",
+    or: " OR ",
+    and_not: " ANDNOT ",
+    open: "(",
+    close: ")",
+    space: " ",
+};
+
+const URL_SYNTAX: Syntax = Syntax {
+    encode: true,
+    and: "+AND+",
+    or: "+OR+",
+    and_not: "+ANDNOT+",
+    open: "%28",
+    close: "%29",
+    space: "+",
+};
+
 impl QueryParams {
     pub fn title(arg: &str) -> Self {
-        return QueryParams::Title(format!("ti:\"{}\"", encode(arg)));
+        return QueryParams::Title(arg.to_string());
     }
     pub fn author(arg: &str) -> Self {
-        return QueryParams::Author(format!("au:\"{}\"", encode(arg)));
+        return QueryParams::Author(arg.to_string());
+    }
+    /// Search by surname in the `surname_f` form used by the `au:` field, e.g. `au:del_maestro_a`.
+    /// ```rust
+    /// # use arxiv_tools::QueryParams;
+    /// let args = QueryParams::author_surname_initial("Del Maestro", "A.");
+    /// assert_eq!(args.to_string(), "au:Del_Maestro_A");
+    /// ```
+    pub fn author_surname_initial(surname: &str, initial: &str) -> Self {
+        let initial = initial.trim().trim_end_matches('.');
+        let name = format!("{}_{}", author_key(surname), author_key(initial));
+        return QueryParams::Author(name);
+    }
+    /// Search by surname only, matching any given name.
+    pub fn author_surname(surname: &str) -> Self {
+        return QueryParams::Author(author_key(surname));
+    }
+    /// Search by a full name such as `"Ashish Vaswani"` or `"Vaswani, Ashish"`, formatted as `surname_f`.
+    ///
+    /// Without a comma the last word is taken as the surname, with lowercase particles such as `van` or `de`
+    /// before it, e.g. `van_der_Waals_J` for `"Johannes van der Waals"`.
+    pub fn author_full_name(name: &str) -> Self {
+        let (given, surname) = split_name(name);
+        return match given.chars().next() {
+            Some(initial) => QueryParams::author_surname_initial(surname, &initial.to_string()),
+            None => QueryParams::author_surname(surname),
+        };
     }
     pub fn abstract_text(arg: &str) -> Self {
-        return QueryParams::Abstract(format!("abs:\"{}\"", encode(arg)));
+        return QueryParams::Abstract(arg.to_string());
     }
     pub fn comment(arg: &str) -> Self {
-        return QueryParams::Comment(format!("co:\"{}\"", encode(arg)));
+        return QueryParams::Comment(arg.to_string());
     }
     pub fn journal_ref(arg: &str) -> Self {
-        return QueryParams::JournalRef(format!("jr:\"{}\"", encode(arg)));
+        return QueryParams::JournalRef(arg.to_string());
     }
     pub fn subject_category(arg: Category) -> Self {
-        return QueryParams::SubjectCategory(format!("cat:\"{}\"", encode(&arg.to_string())));
+        return QueryParams::SubjectCategory(arg.to_string());
     }
     pub fn report_number(arg: &str) -> Self {
-        return QueryParams::ReportNumber(format!("rn:\"{}\"", encode(arg)));
+        return QueryParams::ReportNumber(arg.to_string());
     }
     pub fn id(id: &str) -> Self {
-        return QueryParams::Id(format!("id:\"{}\"", encode(id)));
+        return QueryParams::Id(id.to_string());
     }
     pub fn all(arg: &str) -> Self {
-        return QueryParams::All(format!("all:\"{}\"", encode(arg)));
+        return QueryParams::All(arg.to_string());
     }
+    /// Match papers submitted between `from` and `to`, both inclusive and in GMT.
+    ///
+    /// Bounds are `YYYYMMDDHHMM` or `YYYYMMDD`; a day is expanded to `0000` for `from` and `2359` for `to`.
+    /// ```rust
+    /// # use arxiv_tools::QueryParams;
+    /// let args = QueryParams::submitted_date("20241201", "202412011200").unwrap();
+    /// assert_eq!(args.to_string(), "submittedDate:[202412010000 TO 202412011200]");
+    ///
+    /// assert!(QueryParams::submitted_date("2024-12-01", "20241201").is_err());
+    /// ```
+    pub fn submitted_date(from: &str, to: &str) -> Result<Self, ArxivError> {
+        return Ok(QueryParams::SubmittedDate(
+            submitted_date_bound(from, "0000")?,
+            submitted_date_bound(to, "2359")?,
+        ));
+    }
+    /// Render the query in a human-readable form, e.g. `ti:"attention is all you need" AND cat:cs.CL`.
     pub fn to_string(&self) -> String {
+        return self.render(&READABLE_SYNTAX);
+    }
+    /// Render the query as it is sent in the `search_query` parameter, encoding every term once.
+    fn to_query(&self) -> String {
+        return self.render(&URL_SYNTAX);
+    }
+    fn render(&self, syntax: &Syntax) -> String {
         match self {
-            QueryParams::Title(arg) => arg.to_string(),
-            QueryParams::Author(arg) => arg.to_string(),
-            QueryParams::Abstract(arg) => arg.to_string(),
-            QueryParams::Comment(arg) => arg.to_string(),
-            QueryParams::JournalRef(arg) => arg.to_string(),
-            QueryParams::SubjectCategory(arg) => arg.to_string(),
-            QueryParams::ReportNumber(arg) => arg.to_string(),
-            QueryParams::Id(arg) => arg.to_string(),
-            QueryParams::All(arg) => arg.to_string(),
-            QueryParams::And(arg) => arg.to_string(),
-            QueryParams::Or(arg) => arg.to_string(),
-            QueryParams::AndNot(arg) => arg.to_string(),
-            QueryParams::Group(arg) => arg.to_string(),
+            QueryParams::Title(arg) => return render_term("ti", arg, syntax),
+            QueryParams::Author(arg) => return render_term("au", arg, syntax),
+            QueryParams::Abstract(arg) => return render_term("abs", arg, syntax),
+            QueryParams::Comment(arg) => return render_term("co", arg, syntax),
+            QueryParams::JournalRef(arg) => return render_term("jr", arg, syntax),
+            QueryParams::SubjectCategory(arg) => return render_term("cat", arg, syntax),
+            QueryParams::ReportNumber(arg) => return render_term("rn", arg, syntax),
+            QueryParams::Id(arg) => return render_term("id", arg, syntax),
+            QueryParams::All(arg) => return render_term("all", arg, syntax),
+            QueryParams::And(args) => return render_operands(args, syntax.and, syntax),
+            QueryParams::Or(args) => return render_operands(args, syntax.or, syntax),
+            QueryParams::AndNot(args) => return render_operands(args, syntax.and_not, syntax),
+            QueryParams::Group(args) => {
+                return format!(
+                    "{}{}{}",
+                    syntax.open,
+                    render_args(args, "", syntax),
+                    syntax.close
+                );
+            }
+            QueryParams::SubmittedDate(from, to) => {
+                return format!(
+                    "submittedDate:[{}{}TO{}{}]",
+                    from, syntax.space, syntax.space, to
+                );
+            }
+        }
+    }
+    /// Describe the boolean structure of the query as an indented tree, one node per line.
+    /// ```rust
+    /// # use arxiv_tools::{QueryParams, Category};
+    /// let args = QueryParams::and(vec![
+    ///     QueryParams::title("attention"),
+    ///     QueryParams::group(vec![QueryParams::or(vec![
+    ///         QueryParams::subject_category(Category::CsAi),
+    ///         QueryParams::subject_category(Category::CsLg),
+    ///     ])]),
+    /// ]);
+    /// assert_eq!(
+    ///     args.explain(),
+    ///     "AND\n  title: \"attention\"\n  GROUP\n    OR\n      category: \"cs.AI\"\n      category: \"cs.LG\"\n"
+    /// );
+    /// ```
+    pub fn explain(&self) -> String {
+        let mut lines = String::new();
+        self.explain_into(&mut lines, 0);
+        return lines;
+    }
+    fn explain_into(&self, lines: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        let (label, args) = match self {
+            QueryParams::Title(arg) => (format!("title: {:?}", arg), None),
+            QueryParams::Author(arg) => (format!("author: {:?}", arg), None),
+            QueryParams::Abstract(arg) => (format!("abstract: {:?}", arg), None),
+            QueryParams::Comment(arg) => (format!("comment: {:?}", arg), None),
+            QueryParams::JournalRef(arg) => (format!("journal ref: {:?}", arg), None),
+            QueryParams::SubjectCategory(arg) => (format!("category: {:?}", arg), None),
+            QueryParams::ReportNumber(arg) => (format!("report number: {:?}", arg), None),
+            QueryParams::Id(arg) => (format!("id: {:?}", arg), None),
+            QueryParams::All(arg) => (format!("all fields: {:?}", arg), None),
             QueryParams::SubmittedDate(from, to) => {
-                format!("submittedDate:[{}+TO+{}]", from, to)
+                (format!("submitted date: {} to {}", from, to), None)
             }
+            QueryParams::And(args) => (String::from("AND"), Some(args)),
+            QueryParams::Or(args) => (String::from("OR"), Some(args)),
+            QueryParams::AndNot(args) => (String::from("ANDNOT"), Some(args)),
+            QueryParams::Group(args) => (String::from("GROUP"), Some(args)),
+        };
+        lines.push_str(&format!("{}{}\n", indent, label));
+        for arg in args.into_iter().flatten() {
+            arg.explain_into(lines, depth + 1);
         }
     }
+    /// Join `args` with `AND`.
+    ///
+    /// Operators nested inside another operator are parenthesized when rendered, so no explicit group is needed.
+    /// ```rust
+    /// # use arxiv_tools::QueryParams;
+    /// let args = QueryParams::and(vec![
+    ///     QueryParams::title("attention"),
+    ///     QueryParams::or(vec![QueryParams::author("vaswani"), QueryParams::author("shazeer")]),
+    /// ]);
+    /// assert_eq!(args.to_string(), "ti:attention AND (au:vaswani OR au:shazeer)");
+    /// ```
     pub fn and(args: Vec<QueryParams>) -> Self {
-        let args = args
-            .iter()
-            .map(|arg| arg.to_string())
-            .collect::<Vec<String>>();
-        let query = args.join("+AND+");
-        return QueryParams::And(query);
+        return QueryParams::And(args);
     }
     pub fn or(args: Vec<QueryParams>) -> Self {
-        let args = args
-            .iter()
-            .map(|arg| arg.to_string())
-            .collect::<Vec<String>>();
-        let query = args.join("+OR+");
-        return QueryParams::Or(query);
+        return QueryParams::Or(args);
     }
     pub fn and_not(args: Vec<QueryParams>) -> Self {
-        let args = args
-            .iter()
-            .map(|arg| arg.to_string())
-            .collect::<Vec<String>>();
-        let query = args.join("+ANDNOT+");
-        return QueryParams::Or(query);
+        return QueryParams::AndNot(args);
     }
     pub fn group(args: Vec<QueryParams>) -> Self {
-        let mut args = args
+        return QueryParams::Group(args);
+    }
+    /// Match papers listed in any of `categories`.
+    ///
+    /// The `cat:` terms are joined with `OR` and grouped, so the result can be combined with other terms directly.
+    /// ```rust
+    /// # use arxiv_tools::{QueryParams, Category};
+    /// let args = QueryParams::categories(&[Category::CsAi, Category::CsLg]);
+    /// assert_eq!(args.to_string(), "(cat:cs.AI OR cat:cs.LG)");
+    /// ```
+    pub fn categories(categories: &[Category]) -> Self {
+        if categories.len() == 1 {
+            return QueryParams::subject_category(categories[0].clone());
+        }
+        let args = categories
             .iter()
-            .map(|arg| arg.to_string())
-            .collect::<Vec<String>>();
-        args.insert(0, String::from("%28"));
-        args.push(String::from("%29"));
-        let query = args.join("");
-        return QueryParams::Group(query);
+            .map(|category| QueryParams::subject_category(category.clone()))
+            .collect::<Vec<QueryParams>>();
+        return QueryParams::group(vec![QueryParams::or(args)]);
+    }
+    /// Match papers in any category of `archive`, e.g. everything in `math`.
+    /// ```rust
+    /// # use arxiv_tools::{QueryParams, Archive};
+    /// assert_eq!(QueryParams::archive(Archive::Cs).to_string(), "cat:cs.*");
+    /// assert_eq!(QueryParams::archive(Archive::HepTh).to_string(), "cat:hep-th");
+    /// ```
+    pub fn archive(archive: Archive) -> Self {
+        return QueryParams::SubjectCategory(archive.pattern());
+    }
+    /// Match papers in any category of `set`. See [`QueryParams::categories`].
+    pub fn category_set(set: &CategorySet) -> Self {
+        return QueryParams::categories(&set.categories);
+    }
+    /// Match papers in any category of the preset `scope`. See [`QueryParams::categories`].
+    pub fn scope(scope: Scope) -> Self {
+        return QueryParams::categories(&scope.categories());
+    }
+    /// Exclude papers listed in `category` from the results of this query.
+    ///
+    /// Compound queries are grouped first so that the `ANDNOT` applies to the whole query.
+    /// ```rust
+    /// # use arxiv_tools::{QueryParams, Category};
+    /// let args = QueryParams::title("diffusion").exclude_category(Category::CsCv);
+    /// assert_eq!(args.to_string(), "ti:diffusion ANDNOT cat:cs.CV");
+    /// ```
+    pub fn exclude_category(self, category: Category) -> Self {
+        return QueryParams::and_not(vec![
+            self.grouped(),
+            QueryParams::subject_category(category),
+        ]);
+    }
+    /// Wrap compound queries in a group so they can be used as a single operand.
+    fn grouped(self) -> Self {
+        match self {
+            QueryParams::And(_) | QueryParams::Or(_) | QueryParams::AndNot(_) => {
+                return QueryParams::group(vec![self]);
+            }
+            _ => return self,
+        }
     }
 }
 
+fn render_term(field: &str, term: &str, syntax: &Syntax) -> String {
+    let needs_quotes = term
+        .chars()
+        .any(|c| !(c.is_alphanumeric() || "_.-*".contains(c)));
+    let term = if syntax.encode {
+        encode(term).into_owned()
+    } else {
+        term.to_string()
+    };
+    if needs_quotes {
+        return format!("{}:\"{}\"", field, term);
+    }
+    return format!("{}:{}", field, term);
+}
+
+fn render_args(args: &[QueryParams], separator: &str, syntax: &Syntax) -> String {
+    return args
+        .iter()
+        .map(|arg| arg.render(syntax))
+        .collect::<Vec<String>>()
+        .join(separator);
+}
+
+/// Render the operands of a boolean operator, parenthesizing nested operators so precedence follows the tree.
+fn render_operands(args: &[QueryParams], separator: &str, syntax: &Syntax) -> String {
+    return args
+        .iter()
+        .map(|arg| match arg {
+            QueryParams::And(_) | QueryParams::Or(_) | QueryParams::AndNot(_) => {
+                format!("{}{}{}", syntax.open, arg.render(syntax), syntax.close)
+            }
+            _ => arg.render(syntax),
+        })
+        .collect::<Vec<String>>()
+        .join(separator);
+}
+
+/// Deserialize `path` as TOML (`.toml`) or JSON (`.json`).
+pub(crate) fn load_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let content = std::fs::read_to_string(path)?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => return Ok(toml::from_str(&content)?),
+        Some("json") => return Ok(serde_json::from_str(&content)?),
+        _ => bail!("unsupported file format: {}", path.display()),
+    }
+}
+
+/// Serialize `value` to `path` as TOML (`.toml`) or JSON (`.json`).
+///
+/// The content is written to a temporary file first and moved into place, so `path` is never left half-written.
+pub(crate) fn save_file<T: Serialize>(value: &T, path: &Path) -> Result<()> {
+    let content = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::to_string_pretty(value)?,
+        Some("json") => serde_json::to_string_pretty(value)?,
+        _ => bail!("unsupported file format: {}", path.display()),
+    };
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    std::fs::write(&partial, content)?;
+    std::fs::rename(&partial, path)?;
+    return Ok(());
+}
+
+/// Validate a `submittedDate` bound, appending `time` to dates given at day granularity.
+fn submitted_date_bound(date: &str, time: &str) -> Result<String, ArxivError> {
+    let bound = match date.len() {
+        8 => format!("{}{}", date, time),
+        12 => date.to_string(),
+        _ => return Err(ArxivError::InvalidDate(date.to_string())),
+    };
+    if !bound.chars().all(|c| c.is_ascii_digit())
+        || NaiveDateTime::parse_from_str(&bound, "%Y%m%d%H%M").is_err()
+    {
+        return Err(ArxivError::InvalidDate(date.to_string()));
+    }
+    return Ok(bound);
+}
+
+/// Normalize a name fragment for the `au:` field: periods are dropped and whitespace becomes `_`.
+fn author_key(name: &str) -> String {
+    return name
+        .replace('.', " ")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join("_");
+}
+
+/// Lowercase particles that belong to the surname when they precede it, e.g. `van` in `Johannes van der Waals`.
+const SURNAME_PARTICLES: [&str; 14] = [
+    "da", "das", "de", "del", "della", "der", "di", "dos", "du", "la", "le", "ten", "van", "von",
+];
+
+/// The given names and the surname of `name`, written `Given Surname` or `Surname, Given`.
+fn split_name(name: &str) -> (&str, &str) {
+    let name = name.trim();
+    if let Some((surname, given)) = name.split_once(',') {
+        return (given.trim(), surname.trim());
+    }
+    // byte offsets of the words of `name`
+    let starts = name
+        .char_indices()
+        .filter(|&(i, c)| {
+            !c.is_whitespace() && (i == 0 || name[..i].ends_with(char::is_whitespace))
+        })
+        .map(|(i, _)| i)
+        .collect::<Vec<usize>>();
+    let word = |k: usize| {
+        return name[starts[k]..]
+            .split_whitespace()
+            .next()
+            .unwrap_or_default();
+    };
+    let Some(last) = starts.len().checked_sub(1) else {
+        return ("", "");
+    };
+    let mut start = last;
+    while start > 1 && SURNAME_PARTICLES.contains(&word(start - 1)) {
+        start -= 1;
+    }
+    if start == 0 {
+        return ("", name);
+    }
+    let offset = starts[start];
+    return (name[..offset].trim(), &name[offset..]);
+}
+
+/// An author of a paper, with the affiliations given in the feed (`arxiv:affiliation`), if any.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "AuthorRepr")]
+pub struct Author {
+    pub name: Arc<str>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub affiliations: Vec<String>,
+}
+
+impl Author {
+    pub fn new(name: &str) -> Self {
+        return Author {
+            name: Arc::from(name),
+            affiliations: Vec::new(),
+        };
+    }
+    /// The given names, e.g. `Johannes Diderik` for `Johannes Diderik van der Waals`, or an empty string if the
+    /// name is a single word.
+    pub fn given(&self) -> &str {
+        return split_name(&self.name).0;
+    }
+    /// The surname, with its particles, e.g. `van der Waals` for `Johannes Diderik van der Waals`.
+    ///
+    /// Names are written `Given Surname` or `Surname, Given`; without a comma the last word is the surname.
+    pub fn family(&self) -> &str {
+        return split_name(&self.name).1;
+    }
+    /// The name in the lowercase `surname_f` form of the `au:` field, e.g. `van_der_waals_j`, to match authors
+    /// across papers and against [`QueryParams::author_surname_initial`]:
+    /// ```rust
+    /// # use arxiv_tools::Author;
+    /// assert_eq!(Author::new("Ashish Vaswani").key(), "vaswani_a");
+    /// assert_eq!(Author::new("Vaswani, A.").key(), "vaswani_a");
+    /// assert_eq!(Author::new("J. D. van der Waals").key(), "van_der_waals_j");
+    /// ```
+    pub fn key(&self) -> String {
+        let (given, family) = split_name(&self.name);
+        let key = match given.chars().next() {
+            Some(initial) => format!("{}_{}", author_key(family), initial),
+            None => author_key(family),
+        };
+        return key.to_lowercase();
+    }
+}
+
+impl std::fmt::Display for Author {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.write_str(&self.name);
+    }
+}
+
+/// Authors are read either as plain names, as saved before affiliations were parsed, or as [`Author`] tables.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AuthorRepr {
+    Name(String),
+    Author {
+        name: String,
+        #[serde(default)]
+        affiliations: Vec<String>,
+    },
+}
+
+impl From<AuthorRepr> for Author {
+    fn from(repr: AuthorRepr) -> Self {
+        match repr {
+            AuthorRepr::Name(name) => return Author::new(&name),
+            AuthorRepr::Author { name, affiliations } => {
+                return Author {
+                    name: Arc::from(name),
+                    affiliations,
+                }
+            }
+        }
+    }
+}
+
+/// Reads an optional field saved before it was an `Option`, when a missing value was an empty string.
+fn empty_as_none<'de, D>(deserializer: D) -> std::result::Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<String>::deserialize(deserializer)?;
+    return Ok(value.filter(|value| !value.is_empty()));
+}
+
+/// Reads a timestamp saved before it was parsed, when a missing value was an empty string.
+fn timestamp_or_empty<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let Some(value) = empty_as_none(deserializer)? else {
+        return Ok(None);
+    };
+    let timestamp = DateTime::parse_from_rfc3339(&value).map_err(serde::de::Error::custom)?;
+    return Ok(Some(timestamp.with_timezone(&Utc)));
+}
+
+/// Comments are read either as a string or as the list of comments saved before `comment` was an `Option`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CommentRepr {
+    Text(String),
+    List(Vec<String>),
+}
+
+fn deserialize_comment<'de, D>(deserializer: D) -> std::result::Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let comment = match Option::<CommentRepr>::deserialize(deserializer)? {
+        Some(CommentRepr::Text(text)) => text,
+        Some(CommentRepr::List(comments)) => comments.join("; "),
+        None => return Ok(None),
+    };
+    return Ok(Some(comment).filter(|comment| !comment.is_empty()));
+}
+
+/// Lowercase phrases of comments announcing a withdrawal, besides comments starting with `withdrawn`.
+pub const WITHDRAWAL_MARKERS: [&str; 5] = [
+    "has been withdrawn",
+    "is withdrawn",
+    "was withdrawn",
+    "withdrawn by",
+    "paper withdrawn",
+];
+
+/// A `<link>` of an entry, e.g. the `alternate` link to the abstract page or the `related` link to the PDF.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Link {
+    pub href: String,
+    /// `rel`, e.g. `alternate` or `related`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rel: Option<String>,
+    /// `title`, e.g. `pdf` or `doi`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// `type`, the media type of the target, e.g. `application/pdf`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Paper {
     pub id: String,
     pub title: String,
-    pub authors: Vec<String>,
+    pub authors: Vec<Author>,
     #[serde(rename = "abstract")]
     pub abstract_text: String,
-    pub published: String,
-    pub updated: String,
-    pub doi: String,
-    pub comment: Vec<String>,
-    pub journal_ref: String,
-    pub pdf_url: String,
-    pub primary_category: String,
-    pub categories: Vec<String>,
+    /// When the first version was submitted, `None` if the feed gave no valid RFC 3339 timestamp.
+    #[serde(default, deserialize_with = "timestamp_or_empty")]
+    pub published: Option<DateTime<Utc>>,
+    /// When the paper was last updated, `None` if the feed gave no valid RFC 3339 timestamp.
+    #[serde(default, deserialize_with = "timestamp_or_empty")]
+    pub updated: Option<DateTime<Utc>>,
+    /// The bare DOI, e.g. `10.1103/PhysRevD.76.013009`, from `arxiv:doi` or the DOI link.
+    #[serde(
+        default,
+        deserialize_with = "empty_as_none",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub doi: Option<String>,
+    /// `arxiv:comment`, e.g. `15 pages, 5 figures`.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_comment",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub comment: Option<String>,
+    /// `arxiv:journal_ref`, e.g. `Phys.Rev.D76:013009,2007`.
+    #[serde(
+        default,
+        deserialize_with = "empty_as_none",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub journal_ref: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "empty_as_none",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pdf_url: Option<String>,
+    /// Shared with the other papers of the session if it has an [`Interner`].
+    pub primary_category: Arc<str>,
+    /// Shared with the other papers of the session if it has an [`Interner`].
+    pub categories: Vec<Arc<str>>,
+    /// `arxiv:msc_class`, the Mathematics Subject Classification codes, e.g. `05A15; 05A16`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub msc_class: Option<String>,
+    /// `arxiv:acm_class`, the ACM Computing Classification codes, e.g. `F.2.2; G.2.2`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acm_class: Option<String>,
+    /// Every link of the entry, in feed order, including the ones `pdf_url` and `doi` are read from.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<Link>,
+    /// The original `<entry>` element, if the query retains it. See [`ArXiv::retain_raw_xml`].
+    #[cfg(feature = "raw-xml")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_xml: Option<String>,
 }
 
-impl Paper {
-    pub fn default() -> Self {
+/// Papers returned by a query, together with the paging metadata reported by the feed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchResults {
+    pub papers: Vec<Paper>,
+    /// `opensearch:totalResults`: the number of papers matching the query, across all pages.
+    pub total: Option<u64>,
+    /// `opensearch:startIndex`: the offset of the first paper of this page.
+    pub start: Option<u64>,
+    /// `opensearch:itemsPerPage`: the page size the API used.
+    pub per_page: Option<u64>,
+    /// The title, id, update time and links of the feed.
+    #[serde(default)]
+    pub meta: FeedMeta,
+}
+
+impl SearchResults {
+    /// `true` only if the feed itself reports that nothing matched the query.
+    pub fn is_definitely_empty(&self) -> bool {
+        return self.papers.is_empty() && self.total == Some(0);
+    }
+    /// Whether results beyond this page match the query, according to `total`.
+    pub fn has_more(&self) -> bool {
+        let end = self.start.unwrap_or(0) + self.papers.len() as u64;
+        return self.total.is_some_and(|total| end < total);
+    }
+}
+
+/// Papers are equal if their canonical identifiers are, see [`Paper::canonical_id`], so versions of a paper differ.
+impl PartialEq for Paper {
+    fn eq(&self, other: &Self) -> bool {
+        return self.canonical_id() == other.canonical_id();
+    }
+}
+
+impl Eq for Paper {}
+
+impl std::hash::Hash for Paper {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.canonical_id().hash(state);
+    }
+}
+
+/// Papers are ordered by submission date, oldest first, then by canonical identifier, e.g. to keep them in a
+/// `BTreeSet` in date order. Papers without a valid `published` date come first.
+///
+/// A canonical identifier, with its version, has a single `published` date, so papers ordered as equal are `==`.
+impl Ord for Paper {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        return self
+            .published
+            .cmp(&other.published)
+            .then_with(|| self.canonical_id().cmp(&other.canonical_id()));
+    }
+}
+
+impl PartialOrd for Paper {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl Default for Paper {
+    fn default() -> Self {
         return Paper {
             id: "".to_string(),
             title: "".to_string(),
             authors: Vec::new(),
             abstract_text: "".to_string(),
-            published: "".to_string(),
-            updated: "".to_string(),
-            doi: "".to_string(),
-            comment: Vec::new(),
-            journal_ref: "".to_string(),
-            pdf_url: "".to_string(),
-            primary_category: "".to_string(),
+            published: None,
+            updated: None,
+            doi: None,
+            comment: None,
+            journal_ref: None,
+            pdf_url: None,
+            primary_category: Arc::from(""),
             categories: Vec::new(),
+            msc_class: None,
+            acm_class: None,
+            links: Vec::new(),
+            #[cfg(feature = "raw-xml")]
+            raw_xml: None,
         };
     }
+}
 
+impl Paper {
+    /// A builder of a paper, e.g. for fixtures. See [`PaperBuilder`].
+    pub fn builder() -> PaperBuilder {
+        return PaperBuilder::new();
+    }
+    /// The identifier parsed from `id`, or `None` if it is not an arXiv abs URL or identifier.
+    /// ```rust
+    /// # use arxiv_tools::Paper;
+    /// let paper = Paper {
+    ///     id: String::from("http://arxiv.org/abs/2412.01234v2"),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(paper.arxiv_id().unwrap().to_string(), "2412.01234v2");
+    /// ```
+    pub fn arxiv_id(&self) -> Option<ArxivId> {
+        return self.id.parse().ok();
+    }
+    /// The identifier in canonical form, e.g. `2412.01234v2` for `http://arxiv.org/abs/2412.01234v2`, or `id` as is
+    /// if it is not an arXiv identifier. Papers are compared and hashed by it:
+    /// ```rust
+    /// # use arxiv_tools::Paper;
+    /// # use std::collections::HashSet;
+    /// let paper = Paper {
+    ///     id: String::from("http://arxiv.org/abs/2412.01234v2"),
+    ///     ..Default::default()
+    /// };
+    /// let same = Paper {
+    ///     id: String::from("https://arxiv.org/abs/2412.01234v2"),
+    ///     title: String::from("Fetched from another mirror"),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(paper.canonical_id(), "2412.01234v2");
+    /// assert_eq!(paper, same);
+    /// assert_eq!(HashSet::from([paper, same]).len(), 1);
+    /// ```
+    pub fn canonical_id(&self) -> String {
+        match self.arxiv_id() {
+            Some(id) => return id.to_string(),
+            None => return self.id.clone(),
+        }
+    }
+    /// The version of the paper, e.g. `2` for `http://arxiv.org/abs/2412.01234v2`.
+    pub fn version(&self) -> Option<u32> {
+        return self.arxiv_id()?.version();
+    }
+    /// The identifier without its version, the same for every version of the paper, e.g. to deduplicate:
+    /// ```rust
+    /// # use arxiv_tools::Paper;
+    /// let v1 = Paper {
+    ///     id: String::from("http://arxiv.org/abs/2412.01234v1"),
+    ///     ..Default::default()
+    /// };
+    /// let v2 = Paper {
+    ///     id: String::from("http://arxiv.org/abs/2412.01234v2"),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(v1.id_without_version(), "2412.01234");
+    /// assert_eq!(v1.id_without_version(), v2.id_without_version());
+    /// ```
+    /// An `id` that is not an arXiv identifier is returned unchanged.
+    pub fn id_without_version(&self) -> String {
+        match self.arxiv_id() {
+            Some(id) => return id.base().to_string(),
+            None => return self.id.clone(),
+        }
+    }
+    /// The abstract page of the paper, e.g. `https://arxiv.org/abs/2412.01234v2`. See [`ArxivId::abs_url`].
+    pub fn abs_url(&self) -> Option<String> {
+        return self.arxiv_id().map(|id| id.abs_url());
+    }
+    /// The PDF of the paper, built from the identifier even if the entry had no PDF link, e.g.
+    /// `https://arxiv.org/pdf/2412.01234v2`:
+    /// ```rust
+    /// # use arxiv_tools::Paper;
+    /// let paper = Paper {
+    ///     id: String::from("http://arxiv.org/abs/2412.01234v2"),
+    ///     pdf_url: None,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(paper.pdf_link().unwrap(), "https://arxiv.org/pdf/2412.01234v2");
+    /// ```
+    /// The `pdf_url` field is used if the identifier cannot be parsed.
+    pub fn pdf_link(&self) -> Option<String> {
+        return self
+            .arxiv_id()
+            .map(|id| id.pdf_url())
+            .or_else(|| self.pdf_url.clone());
+    }
+    /// The HTML rendering of the paper on ar5iv. See [`ArxivId::html_url`].
+    pub fn html_url(&self) -> Option<String> {
+        return self.arxiv_id().map(|id| id.html_url());
+    }
+    /// Whether the paper was updated after its first version was published.
+    pub fn is_revision(&self) -> bool {
+        return self.updated.is_some() && self.updated != self.published;
+    }
+    /// `primary_category` as a [`Category`], falling back to [`Category::Other`] for unknown codes.
+    pub fn parsed_primary_category(&self) -> Category {
+        return self.primary_category.parse().unwrap();
+    }
+    /// `categories` as [`Category`] values, falling back to [`Category::Other`] for unknown codes.
+    pub fn parsed_categories(&self) -> Vec<Category> {
+        return self
+            .categories
+            .iter()
+            .map(|category| category.parse().unwrap())
+            .collect();
+    }
+    /// Whether the paper is listed in `category`, treating aliases such as `cs.NA` and `math.NA` as equal.
+    pub fn has_category(&self, category: Category) -> bool {
+        return self
+            .parsed_categories()
+            .iter()
+            .any(|listed| listed.is_equivalent(&category));
+    }
+    /// Categories the paper is cross-listed in, i.e. every category except the primary one.
+    pub fn secondary_categories(&self) -> Vec<Category> {
+        let primary = self.parsed_primary_category();
+        return self
+            .parsed_categories()
+            .into_iter()
+            .filter(|category| !category.is_equivalent(&primary))
+            .collect();
+    }
+    /// Codes of the categories the paper is cross-listed in, as given in the feed, i.e. `categories` without the
+    /// primary category or its aliases. Use [`Paper::is_primarily`] to match the primary category only.
+    /// ```rust
+    /// # use arxiv_tools::Paper;
+    /// let paper = Paper::builder()
+    ///     .category("math.NA")
+    ///     .category("cs.NA")
+    ///     .category("cs.LG")
+    ///     .build();
+    /// assert_eq!(&*paper.primary_category, "math.NA");
+    /// assert_eq!(paper.cross_list_categories(), vec!["cs.LG"]);
+    /// ```
+    pub fn cross_list_categories(&self) -> Vec<&str> {
+        let primary = self.parsed_primary_category();
+        return self
+            .categories
+            .iter()
+            .filter(|code| !code.parse::<Category>().unwrap().is_equivalent(&primary))
+            .map(|code| &**code)
+            .collect();
+    }
+    /// Whether the paper is listed in any category besides its primary one.
+    pub fn is_cross_listed(&self) -> bool {
+        return !self.cross_list_categories().is_empty();
+    }
+    /// Whether `category` is the primary category of the paper, as opposed to a cross-listing.
+    /// ```rust
+    /// # use arxiv_tools::{Category, Paper};
+    /// let paper = Paper {
+    ///     primary_category: "cs.CL".into(),
+    ///     categories: vec!["cs.CL".into(), "cs.LG".into()],
+    ///     ..Default::default()
+    /// };
+    /// assert!(paper.is_primarily(Category::CsCl));
+    /// assert!(!paper.is_primarily(Category::CsLg));
+    /// assert_eq!(paper.secondary_categories(), vec![Category::CsLg]);
+    /// ```
+    pub fn is_primarily(&self, category: Category) -> bool {
+        return self.parsed_primary_category().is_equivalent(&category);
+    }
+    /// The archive of the primary category, e.g. `cs` for a paper filed under `cs.CL`. See [`Category::archive`].
+    pub fn primary_archive(&self) -> Option<Archive> {
+        return self.parsed_primary_category().archive();
+    }
+    /// The `https://doi.org/` link of the DOI, if the paper has one.
+    pub fn doi_url(&self) -> Option<String> {
+        return self
+            .doi
+            .as_ref()
+            .map(|doi| format!("https://doi.org/{}", doi));
+    }
+    /// The comment announcing that the paper was withdrawn, e.g. `This paper has been withdrawn by the author due to
+    /// an error in Lemma 3`, if any.
+    ///
+    /// arXiv has no withdrawal flag; a withdrawn version is recognized by the phrasing of its comment, see
+    /// [`WITHDRAWAL_MARKERS`].
+    pub fn withdrawal_note(&self) -> Option<&str> {
+        return self.comment.as_deref().filter(|comment| {
+            let comment = comment.to_lowercase();
+            return comment.starts_with("withdrawn")
+                || WITHDRAWAL_MARKERS
+                    .iter()
+                    .any(|marker| comment.contains(marker));
+        });
+    }
+    /// Whether the paper was withdrawn, according to [`Paper::withdrawal_note`].
+    /// ```rust
+    /// # use arxiv_tools::Paper;
+    /// let paper = Paper {
+    ///     comment: Some(String::from("This paper has been withdrawn by the author")),
+    ///     ..Default::default()
+    /// };
+    /// assert!(paper.is_withdrawn());
+    /// assert!(!Paper::default().is_withdrawn());
+    /// ```
+    pub fn is_withdrawn(&self) -> bool {
+        return self.withdrawal_note().is_some();
+    }
+    /// `published` in RFC 3339, as sent by the API, e.g. `2017-06-12T17:57:34Z`, or an empty string if unknown.
+    pub fn published_rfc3339(&self) -> String {
+        return rfc3339(self.published);
+    }
+    /// `updated` in RFC 3339, as sent by the API, e.g. `2023-08-02T00:41:18Z`, or an empty string if unknown.
+    pub fn updated_rfc3339(&self) -> String {
+        return rfc3339(self.updated);
+    }
+    /// `published`, panicking if it is unknown.
     pub fn published2utc(&self) -> DateTime<Utc> {
-        return DateTime::parse_from_rfc3339(&self.published)
-            .unwrap()
-            .with_timezone(&Utc);
+        return self.published.expect("the paper has no publication date");
     }
-
+    /// `updated`, panicking if it is unknown.
     pub fn updated2utc(&self) -> DateTime<Utc> {
-        return DateTime::parse_from_rfc3339(&self.updated)
-            .unwrap()
-            .with_timezone(&Utc);
+        return self.updated.expect("the paper has no update date");
+    }
+    /// A few lines for terminals and logs, each at most `width` characters: the identifier, submission date and
+    /// categories, then the title and the first authors, wrapped at word boundaries.
+    /// ```rust
+    /// # use arxiv_tools::{Author, Paper};
+    /// let paper = Paper {
+    ///     id: String::from("http://arxiv.org/abs/1706.03762v7"),
+    ///     title: String::from("Attention Is All You Need"),
+    ///     authors: ["Ashish Vaswani", "Noam Shazeer", "Niki Parmar", "Jakob Uszkoreit"]
+    ///         .map(Author::new)
+    ///         .to_vec(),
+    ///     published: "2017-06-12T17:57:34Z".parse().ok(),
+    ///     primary_category: "cs.CL".into(),
+    ///     categories: vec!["cs.CL".into(), "cs.LG".into()],
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     paper.summary(32),
+    ///     "1706.03762v7 2017-06-12 cs.CL, …\n\
+    ///      Attention Is All You Need\n\
+    ///      Ashish Vaswani, Noam Shazeer,\n\
+    ///      Niki Parmar et al."
+    /// );
+    /// ```
+    pub fn summary(&self, width: usize) -> String {
+        let mut lines = vec![truncate(
+            &[
+                self.canonical_id(),
+                self.published_date(),
+                self.categories.join(", "),
+            ]
+            .iter()
+            .filter(|part| !part.is_empty())
+            .map(String::as_str)
+            .collect::<Vec<&str>>()
+            .join(" "),
+            width,
+        )];
+        lines.extend(wrap(&self.title, width));
+        lines.extend(wrap(&self.author_list(), width));
+        return lines.join("\n");
+    }
+    /// `published` as `YYYY-MM-DD`, or an empty string if unknown.
+    pub(crate) fn published_date(&self) -> String {
+        return self
+            .published
+            .map(|published| published.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+    }
+    /// The first [`DISPLAYED_AUTHORS`] authors, followed by `et al.` if there are more.
+    pub(crate) fn author_list(&self) -> String {
+        let mut list = self
+            .authors
+            .iter()
+            .take(DISPLAYED_AUTHORS)
+            .map(|author| author.name.as_ref())
+            .collect::<Vec<&str>>()
+            .join(", ");
+        if self.authors.len() > DISPLAYED_AUTHORS {
+            list.push_str(" et al.");
+        }
+        return list;
+    }
+}
+
+/// Number of authors named by [`Paper::summary`] and the `Display` of [`Paper`] before `et al.`.
+pub const DISPLAYED_AUTHORS: usize = 3;
+
+/// One line: the identifier, the categories, the title, the first authors and the submission date, e.g.
+/// `1706.03762v7 [cs.CL, cs.LG] Attention Is All You Need - Ashish Vaswani, Noam Shazeer, Niki Parmar et al.
+/// (2017-06-12)`.
+impl std::fmt::Display for Paper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.canonical_id())?;
+        if !self.categories.is_empty() {
+            write!(f, " [{}]", self.categories.join(", "))?;
+        }
+        write!(f, " {}", self.title)?;
+        if !self.authors.is_empty() {
+            write!(f, " - {}", self.author_list())?;
+        }
+        if self.published.is_some() {
+            write!(f, " ({})", self.published_date())?;
+        }
+        return Ok(());
+    }
+}
+
+/// `text` cut to `width` characters, ending with `…` if it was longer.
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
     }
+    let mut truncated = text
+        .chars()
+        .take(width.saturating_sub(1))
+        .collect::<String>();
+    truncated.push('…');
+    return truncated;
 }
 
-#[derive(Clone, Debug, Default)]
+/// `text` split into lines of at most `width` characters at whitespace. Longer words are truncated.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let word = truncate(word, width);
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    return lines;
+}
+
+fn rfc3339(timestamp: Option<DateTime<Utc>>) -> String {
+    return timestamp
+        .map(|timestamp| timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        .unwrap_or_default();
+}
+
+/// What a query would send, as returned by [`ArXiv::dry_run`].
+#[derive(Clone, Debug)]
+pub struct DryRun {
+    pub url: Url,
+    pub max_results: u64,
+    /// Number of requests of at most [`MAX_PAGE_SIZE`] results needed to fetch `max_results`.
+    pub estimated_pages: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ArXiv {
     pub args: QueryParams,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub start: Option<u64>,
+    #[serde(
+        rename = "max_results",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub max_resutls: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sort_by: Option<SortBy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sort_order: Option<SortOrder>,
+    /// Papers requested by id, e.g. `2301.00001v2`, instead of searching for `args`. See [`ArXiv::id_list`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id_list: Option<Vec<String>>,
+    /// Maximum number of papers fetched across all pages by [`ArXiv::pager`], [`ArXiv::stream`] and [`ArXiv::query_all`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit_total: Option<u64>,
+    /// Endpoint the query is sent to instead of [`DEFAULT_BASE_URL`], e.g. a mirror or a local stub.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<Url>,
+    /// Number of pages [`ArXiv::stream`] fetches ahead of the one being consumed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefetch: Option<usize>,
+    /// Normalization of the whitespace of titles, abstracts and comments, [`Whitespace::SingleLine`] if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub whitespace: Option<Whitespace>,
+    /// Post-processing of the LaTeX in abstracts, [`Latex::Raw`] if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latex: Option<Latex>,
+    /// Whether the original `<entry>` XML is kept on every paper. See [`ArXiv::retain_raw_xml`].
+    #[cfg(feature = "raw-xml")]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub raw_xml: bool,
+    #[serde(skip)]
+    progress: Option<ProgressHook>,
+    #[serde(skip)]
+    session: ArxivSession,
 }
 
 impl ArXiv {
     pub fn from_args(args: QueryParams) -> Self {
         return ArXiv {
-            args: args,
+            args,
             max_resutls: None,
             start: None,
             sort_by: None,
             sort_order: None,
+            id_list: None,
+            limit_total: None,
+            base_url: None,
+            prefetch: None,
+            whitespace: None,
+            latex: None,
+            #[cfg(feature = "raw-xml")]
+            raw_xml: false,
+            progress: None,
+            session: ArxivSession::new(),
         };
     }
 
+    /// Combine the query of `self` with the query of `other` using `AND`.
+    ///
+    /// Compound queries are grouped first, and the other settings (`start`, `max_results`, ...) are taken from `self`.
+    /// ```rust
+    /// # use arxiv_tools::{ArXiv, QueryParams};
+    /// let lab = ArXiv::from_args(QueryParams::or(vec![
+    ///     QueryParams::author("Doe"),
+    ///     QueryParams::author("Roe"),
+    /// ]));
+    /// let topic = ArXiv::from_args(QueryParams::title("llm"));
+    ///
+    /// let combined = topic.and(&lab);
+    /// assert_eq!(combined.args.to_string(), "ti:llm AND (au:Doe OR au:Roe)");
+    /// ```
+    pub fn and(&self, other: &ArXiv) -> ArXiv {
+        return self.combine(QueryParams::and(vec![
+            self.args.clone().grouped(),
+            other.args.clone().grouped(),
+        ]));
+    }
+    /// Combine the query of `self` with the query of `other` using `OR`. See [`ArXiv::and`].
+    pub fn or(&self, other: &ArXiv) -> ArXiv {
+        return self.combine(QueryParams::or(vec![
+            self.args.clone().grouped(),
+            other.args.clone().grouped(),
+        ]));
+    }
+    /// Combine the query of `self` with the query of `other` using `ANDNOT`. See [`ArXiv::and`].
+    pub fn and_not(&self, other: &ArXiv) -> ArXiv {
+        return self.combine(QueryParams::and_not(vec![
+            self.args.clone().grouped(),
+            other.args.clone().grouped(),
+        ]));
+    }
+    fn combine(&self, args: QueryParams) -> ArXiv {
+        let mut combined = self.session.query(args);
+        combined.start = self.start;
+        combined.max_resutls = self.max_resutls;
+        combined.sort_by = self.sort_by.clone();
+        combined.sort_order = self.sort_order.clone();
+        combined.limit_total = self.limit_total;
+        combined.base_url = self.base_url.clone();
+        combined.prefetch = self.prefetch;
+        combined.whitespace = self.whitespace;
+        combined.latex = self.latex;
+        #[cfg(feature = "raw-xml")]
+        {
+            combined.raw_xml = self.raw_xml;
+        }
+        combined.progress = self.progress.clone();
+        return combined;
+    }
+
+    /// The session this query sends its requests through.
+    pub fn session(&self) -> &ArxivSession {
+        return &self.session;
+    }
+
+    /// Timing statistics of the requests sent through the session of this query.
+    pub fn stats(&self) -> ClientStats {
+        return self.session.stats();
+    }
+
+    /// Load a query definition from a TOML (`.toml`) or JSON (`.json`) file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        return load_file(path.as_ref());
+    }
+
+    /// Save the query definition to a TOML (`.toml`) or JSON (`.json`) file.
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        return save_file(self, path.as_ref());
+    }
+
     pub fn start(&mut self, start: u64) -> &mut Self {
         self.start = Some(start);
         return self;
@@ -329,213 +1412,182 @@ impl ArXiv {
         self.sort_order = Some(sort_order);
         return self;
     }
+    /// Request the papers `ids` with the `id_list` parameter instead of searching for `args`, which is ignored:
+    /// ```rust
+    /// # use arxiv_tools::{ArXiv, QueryParams};
+    /// let mut arxiv = ArXiv::from_args(QueryParams::default());
+    /// arxiv.id_list(&["1706.03762v1", "2301.00001"]);
+    /// assert_eq!(
+    ///     arxiv.build_query().as_str(),
+    ///     "https://export.arxiv.org/api/query?id_list=1706.03762v1,2301.00001"
+    /// );
+    /// ```
+    pub fn id_list(&mut self, ids: &[&str]) -> &mut Self {
+        self.id_list = Some(ids.iter().map(|id| id.to_string()).collect());
+        return self;
+    }
+    /// Stop paging once `limit_total` papers are fetched, e.g. the 5,000 newest matches:
+    /// ```rust
+    /// # use arxiv_tools::{ArXiv, QueryParams, SortBy, SortOrder};
+    /// let mut arxiv = ArXiv::from_args(QueryParams::title("llm"));
+    /// arxiv
+    ///     .sort_by(SortBy::SubmittedDate)
+    ///     .sort_order(SortOrder::Descending)
+    ///     .limit_total(5000);
+    /// ```
+    pub fn limit_total(&mut self, limit_total: u64) -> &mut Self {
+        self.limit_total = Some(limit_total);
+        return self;
+    }
+    /// Send the query to `base_url` instead of [`DEFAULT_BASE_URL`].
+    ///
+    /// Every request derived from this query, e.g. the pages of [`ArXiv::stream`], uses the same endpoint.
+    /// ```rust
+    /// # use arxiv_tools::{ArXiv, QueryParams};
+    /// # use url::Url;
+    /// let mut arxiv = ArXiv::from_args(QueryParams::title("llm"));
+    /// arxiv.with_base_url(Url::parse("http://localhost:8080/api/query").unwrap());
+    /// assert_eq!(
+    ///     arxiv.build_query().as_str(),
+    ///     "http://localhost:8080/api/query?search_query=ti:llm"
+    /// );
+    /// ```
+    pub fn with_base_url(&mut self, base_url: Url) -> &mut Self {
+        self.base_url = Some(base_url);
+        return self;
+    }
+    /// Send the query to `endpoint`, e.g. the official API over plain HTTP:
+    /// ```rust
+    /// # use arxiv_tools::{ArXiv, Endpoint, QueryParams, Scheme};
+    /// let mut arxiv = ArXiv::from_args(QueryParams::title("llm"));
+    /// arxiv.with_endpoint(&Endpoint::with_scheme(Scheme::Http));
+    /// assert_eq!(
+    ///     arxiv.build_query().as_str(),
+    ///     "http://export.arxiv.org/api/query?search_query=ti:llm"
+    /// );
+    /// ```
+    pub fn with_endpoint(&mut self, endpoint: &Endpoint) -> &mut Self {
+        return self.with_base_url(endpoint.url());
+    }
+    /// Make the requests of this query's session wait for `limiter`. See [`ArxivSession::set_rate_limiter`].
+    ///
+    /// The limiter applies to every query sharing the session, not only to this one.
+    pub fn with_rate_limiter(&mut self, limiter: RateLimiter) -> &mut Self {
+        self.session.set_rate_limiter(limiter);
+        return self;
+    }
+    /// Retry transient failures of this query's session according to `policy`. See [`ArxivSession::set_retry_policy`].
+    ///
+    /// The policy applies to every query sharing the session, not only to this one.
+    pub fn with_retry_policy(&mut self, policy: RetryPolicy) -> &mut Self {
+        self.session.set_retry_policy(policy);
+        return self;
+    }
+    /// Guard this query's session with `breaker`. See [`ArxivSession::set_circuit_breaker`].
+    ///
+    /// The breaker applies to every query sharing the session, not only to this one.
+    pub fn with_circuit_breaker(&mut self, breaker: CircuitBreaker) -> &mut Self {
+        self.session.set_circuit_breaker(breaker);
+        return self;
+    }
+    /// Share the categories and author names of this query's papers through `interner`. See
+    /// [`ArxivSession::set_interner`].
+    ///
+    /// The interner applies to every query sharing the session, not only to this one.
+    pub fn with_interner(&mut self, interner: Interner) -> &mut Self {
+        self.session.set_interner(interner);
+        return self;
+    }
+    /// Let [`ArXiv::stream`] fetch up to `pages` pages ahead while the current one is consumed.
+    ///
+    /// Requests stay spaced by the session's [`RateLimiter`], but the wait overlaps with the processing of the papers.
+    pub fn prefetch(&mut self, pages: usize) -> &mut Self {
+        self.prefetch = Some(pages.max(1));
+        return self;
+    }
+    /// Normalize the whitespace of the titles, abstracts and comments of the papers according to `whitespace`.
+    /// ```rust
+    /// # use arxiv_tools::{ArXiv, QueryParams, Whitespace};
+    /// let mut arxiv = ArXiv::from_args(QueryParams::title("llm"));
+    /// arxiv.whitespace(Whitespace::Raw);
+    /// ```
+    pub fn whitespace(&mut self, whitespace: Whitespace) -> &mut Self {
+        self.whitespace = Some(whitespace);
+        return self;
+    }
+    /// Post-process the LaTeX in the abstracts of the papers according to `latex`, e.g. for a plain-text display:
+    /// ```rust
+    /// # use arxiv_tools::{ArXiv, Latex, QueryParams};
+    /// let mut arxiv = ArXiv::from_args(QueryParams::title("llm"));
+    /// arxiv.latex(Latex::Unicode);
+    /// ```
+    pub fn latex(&mut self, latex: Latex) -> &mut Self {
+        self.latex = Some(latex);
+        return self;
+    }
+    /// Keep the original `<entry>` XML of every paper in [`Paper::raw_xml`], e.g. for debugging or archival.
+    #[cfg(feature = "raw-xml")]
+    pub fn retain_raw_xml(&mut self, retain: bool) -> &mut Self {
+        self.raw_xml = retain;
+        return self;
+    }
+    /// Call `hook` after every page fetched by [`ArXiv::pager`], [`ArXiv::stream`] and [`ArXiv::query_all`].
+    /// ```rust
+    /// # use arxiv_tools::{ArXiv, QueryParams};
+    /// let mut arxiv = ArXiv::from_args(QueryParams::title("llm"));
+    /// arxiv.on_progress(|progress| {
+    ///     eprintln!("page {}: {} of {:?}", progress.page, progress.fetched, progress.total);
+    /// });
+    /// ```
+    pub fn on_progress<F>(&mut self, hook: F) -> &mut Self
+    where
+        F: Fn(&Progress) + Send + Sync + 'static,
+    {
+        self.progress = Some(ProgressHook(Arc::new(hook)));
+        return self;
+    }
 
-    fn parse_xml(&self, xml: String) -> Vec<Paper> {
-        let mut reader = Reader::from_str(&xml);
-        let mut buf = Vec::new();
-        let mut in_entry = false;
-        let mut in_id = false;
-        let mut in_title = false;
-        let mut in_author = false;
-        let mut in_name = false;
-        let mut in_abstract = false;
-        let mut in_published = false;
-        let mut in_updated = false;
-        let mut in_comment = false;
-        let mut in_journal_ref = false;
-
-        let mut responses: Vec<Paper> = Vec::new();
-        let mut res = Paper::default();
-        loop {
-            match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(ref e)) => {
-                    if e.name().as_ref() == b"entry" {
-                        in_entry = true;
-                        res = Paper::default();
-                    } else if e.name().as_ref() == b"id" {
-                        in_id = true;
-                    } else if e.name().as_ref() == b"title" {
-                        in_title = true;
-                    } else if e.name().as_ref() == b"author" {
-                        in_author = true;
-                    } else if e.name().as_ref() == b"name" {
-                        if in_author {
-                            in_name = true;
-                        }
-                    } else if e.name().as_ref() == b"summary" {
-                        in_abstract = true;
-                    } else if e.name().as_ref() == b"published" {
-                        in_published = true;
-                    } else if e.name().as_ref() == b"updated" {
-                        in_updated = true;
-                    } else if e.name().as_ref() == b"arxiv:comment" {
-                        in_comment = true;
-                    } else if e.name().as_ref() == b"arxiv:journal_ref" {
-                        in_journal_ref = true;
-                    } else if e.name().as_ref() == b"link" && in_entry {
-                        let mut is_pdf = false;
-                        let mut is_doi = false;
-                        e.attributes().for_each(|attr| {
-                            if let Ok(attr) = attr {
-                                if attr.key.as_ref() == b"title" && attr.value.as_ref() == b"pdf" {
-                                    is_pdf = true;
-                                } else if attr.key.as_ref() == b"title"
-                                    && attr.value.as_ref() == b"doi"
-                                {
-                                    is_doi = true;
-                                }
-                            }
-                        });
-                        e.attributes().for_each(|attr| {
-                            if let Ok(attr) = attr {
-                                if attr.key.as_ref() == b"href" {
-                                    if is_pdf {
-                                        res.pdf_url = String::from_utf8_lossy(attr.value.as_ref())
-                                            .to_string();
-                                    } else if is_doi {
-                                        res.doi = String::from_utf8_lossy(attr.value.as_ref())
-                                            .to_string();
-                                    }
-                                }
-                            }
-                        });
-                    } else if e.name().as_ref() == b"arxiv:primary_category" {
-                        e.attributes().for_each(|attr| {
-                            if let Ok(attr) = attr {
-                                if attr.key.as_ref() == b"term" {
-                                    res.primary_category =
-                                        String::from_utf8_lossy(attr.value.as_ref()).to_string();
-                                }
-                            }
-                        });
-                    } else if e.name().as_ref() == b"category" {
-                        if let Some(attr) = e
-                            .attributes()
-                            .find(|attr| attr.as_ref().unwrap().key.as_ref() == b"term")
-                        {
-                            res.categories.push(
-                                String::from_utf8_lossy(attr.unwrap().value.as_ref()).to_string(),
-                            );
-                        }
-                    } else if e.name().as_ref() == b"category" {
-                        if let Some(attr) = e
-                            .attributes()
-                            .find(|attr| attr.as_ref().unwrap().key.as_ref() == b"term")
-                        {
-                            res.categories.push(
-                                String::from_utf8_lossy(attr.unwrap().value.as_ref()).to_string(),
-                            );
-                        }
-                    }
-                }
-                Ok(Event::End(ref e)) => {
-                    if e.name().as_ref() == b"entry" {
-                        in_entry = false;
-                        responses.push(res.clone());
-                        res = Paper::default();
-                    } else if e.name().as_ref() == b"id" {
-                        in_id = false;
-                    } else if e.name().as_ref() == b"title" {
-                        in_title = false;
-                    } else if e.name().as_ref() == b"author" {
-                        in_author = false;
-                    } else if e.name().as_ref() == b"name" {
-                        if in_author {
-                            in_name = false;
-                        }
-                    } else if e.name().as_ref() == b"summary" {
-                        in_abstract = false;
-                    } else if e.name().as_ref() == b"published" {
-                        in_published = false;
-                    } else if e.name().as_ref() == b"updated" {
-                        in_updated = false;
-                    } else if e.name().as_ref() == b"arxiv:comment" {
-                        in_comment = false;
-                    } else if e.name().as_ref() == b"arxiv:journal_ref" {
-                        in_journal_ref = true;
-                    }
-                }
-                Ok(Event::Text(e)) => {
-                    if in_entry {
-                        if in_id {
-                            res.id = e.unescape().unwrap().to_string();
-                        } else if in_title {
-                            res.title = e.unescape().unwrap().to_string();
-                        } else if in_author && in_name {
-                            res.authors.push(e.unescape().unwrap().to_string());
-                        } else if in_abstract {
-                            res.abstract_text =
-                                e.unescape().unwrap().to_string().trim().replace("\n", "");
-                        } else if in_published {
-                            res.published = e.unescape().unwrap().to_string();
-                        } else if in_updated {
-                            res.updated = e.unescape().unwrap().to_string();
-                        } else if in_comment {
-                            res.comment.push(e.unescape().unwrap().to_string());
-                        } else if in_journal_ref {
-                            res.journal_ref = e.unescape().unwrap().to_string();
-                        }
-                    }
-                }
-                Ok(Event::Empty(ref e)) => {
-                    if e.name().as_ref() == b"link" && in_entry {
-                        let mut is_pdf = false;
-                        let mut is_doi = false;
-                        e.attributes().for_each(|attr| {
-                            if let Ok(attr) = attr {
-                                if attr.key.as_ref() == b"title" && attr.value.as_ref() == b"pdf" {
-                                    is_pdf = true;
-                                } else if attr.key.as_ref() == b"title"
-                                    && attr.value.as_ref() == b"doi"
-                                {
-                                    is_doi = true;
-                                }
-                            }
-                        });
-                        e.attributes().for_each(|attr| {
-                            if let Ok(attr) = attr {
-                                if attr.key.as_ref() == b"href" {
-                                    if is_pdf {
-                                        res.pdf_url = String::from_utf8_lossy(attr.value.as_ref())
-                                            .to_string();
-                                    } else if is_doi {
-                                        res.doi = String::from_utf8_lossy(attr.value.as_ref())
-                                            .to_string();
-                                    }
-                                }
-                            }
-                        });
-                    } else if e.name().as_ref() == b"arxiv:primary_category" && in_entry {
-                        e.attributes().for_each(|attr| {
-                            if let Ok(attr) = attr {
-                                if attr.key.as_ref() == b"term" {
-                                    res.primary_category =
-                                        String::from_utf8_lossy(attr.value.as_ref()).to_string();
-                                }
-                            }
-                        });
-                    } else if e.name().as_ref() == b"category" && in_entry {
-                        if let Some(attr) = e
-                            .attributes()
-                            .find(|attr| attr.as_ref().unwrap().key.as_ref() == b"term")
-                        {
-                            res.categories.push(
-                                String::from_utf8_lossy(attr.unwrap().value.as_ref()).to_string(),
-                            );
-                        }
-                    }
-                }
-                Ok(Event::Eof) => break,
-                Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
-                _ => (),
+    fn feed_reader(&self) -> FeedReader {
+        #[allow(unused_mut)]
+        let mut reader = FeedReader::new(self.whitespace.unwrap_or_default());
+        if let Some(interner) = self.session.interner() {
+            reader.with_interner(interner);
+        }
+        #[cfg(feature = "raw-xml")]
+        reader.retain_raw_xml(self.raw_xml);
+        return reader;
+    }
+
+    fn parse_xml(&self, xml: String) -> Result<SearchResults, ArxivError> {
+        #[allow(unused_mut)]
+        let mut plain = self.session.interner().is_none();
+        #[cfg(feature = "raw-xml")]
+        {
+            plain &= !self.raw_xml;
+        }
+        let mut results = match plain {
+            true => parse_atom_with(&xml, self.whitespace.unwrap_or_default())?,
+            false => self.feed_reader().read_all(xml.as_bytes())?,
+        };
+        let latex = self.latex.unwrap_or_default();
+        if latex != Latex::Raw {
+            for paper in results.papers.iter_mut() {
+                paper.abstract_text = latex.apply(&paper.abstract_text);
             }
-            buf.clear();
         }
-        return responses;
+        return Ok(results);
     }
 
-    fn build_query(&self) -> String {
-        let mut query = self.args.to_string();
+    /// The request URL that [`ArXiv::query`] sends.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all, fields(query = %self.args.to_string()), ret(Display))
+    )]
+    pub fn build_query(&self) -> Url {
+        let mut query = match &self.id_list {
+            Some(ids) => format!("id_list={}", ids.join(",")),
+            None => format!("search_query={}", self.args.to_query()),
+        };
         query = query.replace("%20", "+");
         if let Some(start) = &self.start {
             query.push_str(&format!("&start={}", start));
@@ -550,16 +1602,200 @@ impl ArXiv {
             query.push_str(&format!("&sortOrder={}", sort_order.to_string()));
         }
 
-        return format!("http://export.arxiv.org/api/query?search_query={}", query);
+        let base_url = match &self.base_url {
+            Some(base_url) => base_url.as_str(),
+            None => DEFAULT_BASE_URL,
+        };
+        let url = format!("{}?{}", base_url, query);
+        return Url::parse(&url).expect("the query URL is always valid");
     }
 
+    /// Describe the request without sending it: the URL and the number of pages needed to fetch `max_results`.
+    /// ```rust
+    /// # use arxiv_tools::{ArXiv, QueryParams};
+    /// let mut arxiv = ArXiv::from_args(QueryParams::title("llm"));
+    /// arxiv.max_results(5000);
+    ///
+    /// let dry_run = arxiv.dry_run();
+    /// assert_eq!(dry_run.url.host_str(), Some("export.arxiv.org"));
+    /// assert_eq!(dry_run.estimated_pages, 3);
+    /// ```
+    pub fn dry_run(&self) -> DryRun {
+        let max_results = self.max_resutls.unwrap_or(DEFAULT_MAX_RESULTS);
+        return DryRun {
+            url: self.build_query(),
+            max_results,
+            estimated_pages: max_results.div_ceil(MAX_PAGE_SIZE).max(1),
+        };
+    }
+
+    /// Execute the query.
+    ///
+    /// # Panics
+    /// Panics if the request fails or the API rejects the query. Use [`ArXiv::try_query`] to handle these cases.
     pub async fn query(&mut self) -> Vec<Paper> {
+        match self.try_query().await {
+            Ok(responses) => return responses.papers,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Fetch the metadata of every version of the paper `id`, oldest first, through a new session. See
+    /// [`crate::versions`] and [`ArxivSession::versions`].
+    pub async fn versions(id: &str) -> Result<Vec<Paper>, ArxivError> {
+        return ArxivSession::new().versions(id).await;
+    }
+
+    /// Fetch every result of the query, one page after another. See [`ArXiv::stream`] for the paging.
+    pub async fn query_all(&self) -> Result<Vec<Paper>, ArxivError> {
+        return self.stream().try_collect().await;
+    }
+
+    /// Fetch the results of the query one page at a time, on demand. See [`Pager`].
+    pub fn pager(&self) -> Pager {
+        return Pager::new(self);
+    }
+
+    /// Continue fetching pages where the pager that saved `checkpoint` stopped. See [`Checkpoint`].
+    pub fn resume(&self, checkpoint: &Checkpoint) -> Pager {
+        return Pager::resume(self, checkpoint);
+    }
+
+    /// Stream every result of the query, fetching the next page only once the current one is consumed.
+    ///
+    /// Pages are fetched as by [`ArXiv::pager`], and the stream ends once the feed runs out of results
+    /// or after the first error. With [`ArXiv::prefetch`], pages are fetched ahead in a background task,
+    /// so the stream must be created within a Tokio runtime.
+    /// ```rust,no_run
+    /// # use arxiv_tools::{ArXiv, QueryParams};
+    /// # use futures::TryStreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let arxiv = ArXiv::from_args(QueryParams::title("transformer"));
+    /// let mut papers = std::pin::pin!(arxiv.stream());
+    /// while let Some(paper) = papers.try_next().await.unwrap() {
+    ///     println!("{}", paper.title);
+    /// }
+    /// # }
+    /// ```
+    pub fn stream(&self) -> impl Stream<Item = Result<Paper, ArxivError>> {
+        let pages = match self.prefetch {
+            Some(ahead) => self.pager().prefetch(ahead).left_stream(),
+            None => stream::unfold(self.pager(), |mut pager| async move {
+                let page = pager.next_page().await?;
+                return Some((page, pager));
+            })
+            .right_stream(),
+        };
+        return pages.flat_map(|page| match page {
+            Ok(page) => stream::iter(page.papers.into_iter().map(Ok)).left_stream(),
+            Err(e) => stream::iter(Some(Err(e))).right_stream(),
+        });
+    }
+
+    /// Execute the query like [`ArXiv::try_query`], but stream the papers of the page as their entries arrive instead
+    /// of once the whole response is read, e.g. to keep the memory of pages of 2,000 entries low.
+    ///
+    /// The stream ends after the first error. The response bypasses the cache of the session, see
+    /// [`FeedReader`](atom::FeedReader).
+    /// ```rust,no_run
+    /// # use arxiv_tools::{ArXiv, QueryParams};
+    /// # use futures::TryStreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut arxiv = ArXiv::from_args(QueryParams::title("transformer"));
+    /// arxiv.max_results(2000);
+    /// let mut papers = std::pin::pin!(arxiv.query_stream());
+    /// while let Some(paper) = papers.try_next().await.unwrap() {
+    ///     println!("{}", paper.title);
+    /// }
+    /// # }
+    /// ```
+    pub fn query_stream(&self) -> impl Stream<Item = Result<Paper, ArxivError>> {
+        let page = PageStream {
+            session: self.session.clone(),
+            url: self.build_query(),
+            latex: self.latex.unwrap_or_default(),
+            body: None,
+            reader: Some(self.feed_reader()),
+            ready: VecDeque::new(),
+            parsed: 0,
+        };
+        return stream::try_unfold(page, |mut page| async move {
+            let paper = page.next().await?;
+            return Ok(paper.map(|paper| (paper, page)));
+        });
+    }
+
+    /// Execute the query, returning an error instead of panicking.
+    ///
+    /// An empty [`SearchResults`] is only returned when the API answered with a valid, empty feed;
+    /// network failures, rejected queries and malformed responses are errors.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(query = %self.args.to_string()))
+    )]
+    pub async fn try_query(&mut self) -> Result<SearchResults, ArxivError> {
         let url = self.build_query();
-        let body = request::get(&url).await.unwrap().text().await.unwrap();
-        let responses = self.parse_xml(body);
-        return responses;
+        let body = self.session.get(&url).await?;
+        let results = self.parse_xml(String::from_utf8_lossy(&body).to_string());
+        self.session
+            .record_outcome(results.as_ref().map(|results| results.papers.len()));
+        return results;
+    }
+}
+
+/// A page of [`ArXiv::query_stream`], parsed while its body is received.
+struct PageStream {
+    session: ArxivSession,
+    url: Url,
+    latex: Latex,
+    body: Option<BodyStream>,
+    /// `None` once the feed ended or failed.
+    reader: Option<FeedReader>,
+    ready: VecDeque<Paper>,
+    parsed: usize,
+}
+
+impl PageStream {
+    async fn next(&mut self) -> Result<Option<Paper>, ArxivError> {
+        loop {
+            if let Some(mut paper) = self.ready.pop_front() {
+                paper.abstract_text = self.latex.apply(&paper.abstract_text);
+                return Ok(Some(paper));
+            }
+            let Some(reader) = self.reader.as_mut() else {
+                return Ok(None);
+            };
+            let Some(body) = self.body.as_mut() else {
+                self.body = Some(self.session.get_stream(&self.url).await?);
+                continue;
+            };
+            let read = match body.try_next().await {
+                Ok(Some(chunk)) => reader.push(&chunk).map(|papers| {
+                    self.parsed += papers.len();
+                    self.ready.extend(papers);
+                }),
+                Ok(None) => {
+                    let finished = self.reader.take().map(FeedReader::finish);
+                    finished
+                        .unwrap()
+                        .map(|_| self.session.record_outcome(Ok(self.parsed)))
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = read {
+                self.reader = None;
+                self.session.record_outcome(Err(&e));
+                return Err(e);
+            }
+        }
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::len_zero)]
 mod tests;
+
+#[cfg(test)]
+mod test_fixtures;
@@ -0,0 +1,158 @@
+//! # Listing Pages
+//! A **best-effort** parser for the arXiv listing pages (`https://arxiv.org/list/{category}/recent`).
+//!
+//! The listing pages are updated as soon as new papers are announced, while the API may lag behind
+//! by a few hours. They are HTML meant for humans, not a stable interface, so the parser may break
+//! or miss fields whenever arXiv changes its layout. Use the API for anything that must be reliable.
+//!
+//! ```rust
+//! # use arxiv_tools::listing::parse_listing;
+//! let html = r#"
+//! <dl id="articles">
+//!   <dt><a href="/abs/2412.00001" title="Abstract" id="2412.00001">arXiv:2412.00001</a></dt>
+//!   <dd><div class="meta">
+//!     <div class="list-title mathjax"><span class="descriptor">Title:</span> A Paper</div>
+//!     <div class="list-authors"><a href="/a/doe_j_1">John Doe</a></div>
+//!     <div class="list-subjects"><span class="descriptor">Subjects:</span>
+//!       <span class="primary-subject">Machine Learning (cs.LG)</span></div>
+//!   </div></dd>
+//! </dl>"#;
+//!
+//! let entries = parse_listing(html);
+//! assert_eq!(entries[0].id, "2412.00001");
+//! assert_eq!(entries[0].primary_category, "cs.LG");
+//! ```
+use crate::{ArxivError, ArxivSession, Category};
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListingEntry {
+    pub id: String,
+    pub title: String,
+    pub authors: Vec<String>,
+    pub comment: String,
+    pub primary_category: String,
+    pub categories: Vec<String>,
+}
+
+/// Fetch and parse the recent listing page of `category` (best-effort).
+///
+/// Use [`ArxivSession::fetch_recent`] to send the request through an existing session. Fails with
+/// [`ArxivError::Listing`] if the page has no list of articles, see [`try_parse_listing`].
+pub async fn fetch_recent(category: Category) -> Result<Vec<ListingEntry>, ArxivError> {
+    return ArxivSession::new().fetch_recent(category).await;
+}
+
+pub(crate) async fn fetch_recent_in(
+    session: &ArxivSession,
+    category: Category,
+) -> Result<Vec<ListingEntry>, ArxivError> {
+    let url = Url::parse(&format!("https://arxiv.org/list/{}/recent", category))
+        .map_err(|e| ArxivError::Listing(format!("invalid URL for {}: {}", category, e)))?;
+    let body = session.get(&url).await?;
+    return try_parse_listing(&String::from_utf8_lossy(&body));
+}
+
+/// Parse a listing page like [`parse_listing`], but fail with [`ArxivError::Listing`] if it has no list of articles,
+/// e.g. because it is an error page or its layout changed.
+pub fn try_parse_listing(html: &str) -> Result<Vec<ListingEntry>, ArxivError> {
+    let document = Html::parse_document(html);
+    let articles = Selector::parse("dl#articles").unwrap();
+    if document.select(&articles).next().is_none() {
+        return Err(ArxivError::Listing(String::from(
+            "no list of articles found",
+        )));
+    }
+    return Ok(read_listing(&document));
+}
+
+/// Parse a listing page (best-effort). Entries whose identifier cannot be found are skipped.
+pub fn parse_listing(html: &str) -> Vec<ListingEntry> {
+    return read_listing(&Html::parse_document(html));
+}
+
+fn read_listing(document: &Html) -> Vec<ListingEntry> {
+    let dt_selector = Selector::parse("dl#articles > dt").unwrap();
+    let dd_selector = Selector::parse("dl#articles > dd").unwrap();
+    let abs_selector = Selector::parse("a[title=\"Abstract\"]").unwrap();
+    let title_selector = Selector::parse("div.list-title").unwrap();
+    let authors_selector = Selector::parse("div.list-authors a").unwrap();
+    let comment_selector = Selector::parse("div.list-comments").unwrap();
+    let subjects_selector = Selector::parse("div.list-subjects").unwrap();
+    let primary_selector = Selector::parse("span.primary-subject").unwrap();
+
+    let mut entries = Vec::new();
+    for (dt, dd) in document
+        .select(&dt_selector)
+        .zip(document.select(&dd_selector))
+    {
+        let id = match dt
+            .select(&abs_selector)
+            .next()
+            .and_then(|a| a.value().attr("href"))
+        {
+            Some(href) => href.trim_start_matches("/abs/").to_string(),
+            None => continue,
+        };
+
+        let mut entry = ListingEntry {
+            id,
+            ..Default::default()
+        };
+        if let Some(title) = dd.select(&title_selector).next() {
+            entry.title = strip_descriptor(&element_text(&title), "Title:");
+        }
+        entry.authors = dd
+            .select(&authors_selector)
+            .map(|a| element_text(&a))
+            .collect();
+        if let Some(comment) = dd.select(&comment_selector).next() {
+            entry.comment = strip_descriptor(&element_text(&comment), "Comments:");
+        }
+        if let Some(subjects) = dd.select(&subjects_selector).next() {
+            entry.categories = extract_codes(&element_text(&subjects));
+            entry.primary_category = subjects
+                .select(&primary_selector)
+                .next()
+                .and_then(|primary| extract_codes(&element_text(&primary)).into_iter().next())
+                .unwrap_or_else(|| entry.categories.first().cloned().unwrap_or_default());
+        }
+        entries.push(entry);
+    }
+    return entries;
+}
+
+fn element_text(element: &ElementRef) -> String {
+    return element
+        .text()
+        .collect::<Vec<&str>>()
+        .join("")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+}
+
+fn strip_descriptor(text: &str, descriptor: &str) -> String {
+    return text.trim_start_matches(descriptor).trim().to_string();
+}
+
+/// Extract the category codes from text such as `Machine Learning (cs.LG); Artificial Intelligence (cs.AI)`.
+fn extract_codes(text: &str) -> Vec<String> {
+    return text
+        .split(';')
+        .filter_map(|subject| {
+            let open = subject.rfind('(')?;
+            let close = subject.rfind(')')?;
+            if open < close {
+                Some(subject[open + 1..close].trim().to_string())
+            } else {
+                None
+            }
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,97 @@
+use super::*;
+use crate::transport::{async_trait, Transport};
+use bytes::Bytes;
+
+const LISTING: &str = r#"
+<html><body>
+<dl id='articles'>
+<h3>Mon, 2 Dec 2024 (showing 2 of 2 entries )</h3>
+<dt>
+  <a name='item1'>[1]</a>
+  <a href ="/abs/2412.00001" title="Abstract" id="2412.00001">arXiv:2412.00001</a>
+  [<a href="/pdf/2412.00001" title="Download PDF" id="pdf-2412.00001">pdf</a>]
+</dt>
+<dd>
+  <div class='meta'>
+    <div class='list-title mathjax'><span class='descriptor'>Title:</span>
+      Scaling Laws for
+      Sparse Models
+    </div>
+    <div class='list-authors'><a href="https://arxiv.org/a/doe_j_1">John Doe</a>, <a href="https://arxiv.org/a/roe_j_1">Jane Roe</a></div>
+    <div class='list-comments mathjax'><span class='descriptor'>Comments:</span>
+      12 pages, 3 figures
+    </div>
+    <div class='list-subjects'><span class='descriptor'>Subjects:</span>
+      <span class="primary-subject">Machine Learning (cs.LG)</span>; Artificial Intelligence (cs.AI)
+    </div>
+  </div>
+</dd>
+<dt>
+  <a name='item2'>[2]</a>
+  <a href ="/abs/2412.00002" title="Abstract" id="2412.00002">arXiv:2412.00002</a>
+</dt>
+<dd>
+  <div class='meta'>
+    <div class='list-title mathjax'><span class='descriptor'>Title:</span> Another Paper</div>
+    <div class='list-authors'><a href="https://arxiv.org/a/smith_a_1">Alice Smith</a></div>
+    <div class='list-subjects'><span class='descriptor'>Subjects:</span>
+      <span class="primary-subject">Computation and Language (cs.CL)</span>
+    </div>
+  </div>
+</dd>
+</dl>
+</body></html>
+"#;
+
+#[test]
+fn test_parse_listing() {
+    let entries = parse_listing(LISTING);
+    assert_eq!(entries.len(), 2);
+
+    let first = &entries[0];
+    assert_eq!(first.id, "2412.00001");
+    assert_eq!(first.title, "Scaling Laws for Sparse Models");
+    assert_eq!(first.authors, vec!["John Doe", "Jane Roe"]);
+    assert_eq!(first.comment, "12 pages, 3 figures");
+    assert_eq!(first.primary_category, "cs.LG");
+    assert_eq!(first.categories, vec!["cs.LG", "cs.AI"]);
+
+    let second = &entries[1];
+    assert_eq!(second.id, "2412.00002");
+    assert_eq!(second.comment, "");
+    assert_eq!(second.categories, vec!["cs.CL"]);
+}
+
+#[test]
+fn test_parse_listing_unexpected_layout() {
+    assert!(parse_listing("<html><body><p>maintenance</p></body></html>").is_empty());
+}
+
+#[test]
+fn test_try_parse_listing() {
+    assert_eq!(try_parse_listing(LISTING).unwrap().len(), 2);
+    assert!(matches!(
+        try_parse_listing("<html><body><p>maintenance</p></body></html>"),
+        Err(ArxivError::Listing(_))
+    ));
+}
+
+/// Serves a page without a list of articles.
+#[derive(Debug)]
+struct Maintenance;
+
+#[async_trait]
+impl Transport for Maintenance {
+    async fn get(&self, _url: &Url) -> Result<Bytes, ArxivError> {
+        return Ok(Bytes::from_static(
+            b"<html><body><p>maintenance</p></body></html>",
+        ));
+    }
+}
+
+#[tokio::test]
+async fn test_fetch_recent_unexpected_layout() {
+    let session = ArxivSession::with_transport(Maintenance);
+    let error = session.fetch_recent(Category::CsLg).await.unwrap_err();
+    assert_eq!(error.class(), "listing");
+}
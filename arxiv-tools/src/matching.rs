@@ -0,0 +1,175 @@
+//! # Local Matching
+//! Evaluates a [`QueryParams`] expression against a [`Paper`] without asking the API, e.g. to filter a snapshot of
+//! harvested papers or to check that the API answered a query with matching papers:
+//! ```rust
+//! # use arxiv_tools::{Category, Paper, QueryParams};
+//! let paper = Paper::builder()
+//!     .id("1706.03762v7")
+//!     .title("Attention Is All You Need")
+//!     .author("Ashish Vaswani")
+//!     .category("cs.CL")
+//!     .published("2017-06-12T17:57:34Z".parse().unwrap())
+//!     .build();
+//! let query = QueryParams::and(vec![
+//!     QueryParams::title("attention is all"),
+//!     QueryParams::author_full_name("Ashish Vaswani"),
+//!     QueryParams::subject_category(Category::CsCl),
+//!     QueryParams::submitted_date("20170601", "20170630").unwrap(),
+//! ]);
+//! assert!(paper.matches(&query));
+//! assert!(!paper.matches(&QueryParams::title("transformer")));
+//! ```
+//!
+//! The API's search is approximated:
+//! - text terms match case-insensitively on whole words, a term of several words as a phrase, and a trailing `*`
+//!   as a prefix, e.g. `transform*`;
+//! - `au:` terms in the `surname_f` form match a surname, optionally followed by the initial of the first given name,
+//!   e.g. `del_maestro_a` or `maestro` for `Adrian Del Maestro`, others match the name as text;
+//! - `cat:` terms match aliases of the categories of the paper, and `cs.*` any category of an archive;
+//! - `id:` terms without a version match every version;
+//! - `rn:` terms never match, since report numbers are not parsed from the feed.
+use crate::{ArxivId, Author, Category, Paper, QueryParams};
+
+#[cfg(test)]
+mod tests;
+
+impl Paper {
+    /// Whether the paper satisfies `query`, approximating the search of the API. See [`crate::matching`].
+    pub fn matches(&self, query: &QueryParams) -> bool {
+        match query {
+            QueryParams::Title(term) => return matches_text(&self.title, term),
+            QueryParams::Author(term) => return self.matches_author(term),
+            QueryParams::Abstract(term) => return matches_text(&self.abstract_text, term),
+            QueryParams::Comment(term) => {
+                return self
+                    .comment
+                    .as_deref()
+                    .is_some_and(|comment| matches_text(comment, term));
+            }
+            QueryParams::JournalRef(term) => {
+                return self
+                    .journal_ref
+                    .as_deref()
+                    .is_some_and(|journal_ref| matches_text(journal_ref, term));
+            }
+            QueryParams::SubjectCategory(code) => return self.matches_category(code),
+            QueryParams::ReportNumber(_) => return false,
+            QueryParams::Id(id) => return self.matches_id(id),
+            QueryParams::All(term) => {
+                return matches_text(&self.title, term)
+                    || matches_text(&self.abstract_text, term)
+                    || self.matches_author(term)
+                    || [&self.comment, &self.journal_ref]
+                        .iter()
+                        .any(|text| text.as_deref().is_some_and(|text| matches_text(text, term)))
+                    || self.matches_category(term);
+            }
+            QueryParams::And(args) | QueryParams::Group(args) => {
+                return args.iter().all(|arg| self.matches(arg));
+            }
+            QueryParams::Or(args) => return args.iter().any(|arg| self.matches(arg)),
+            QueryParams::AndNot(args) => {
+                let Some((first, rest)) = args.split_first() else {
+                    return true;
+                };
+                return self.matches(first) && !rest.iter().any(|arg| self.matches(arg));
+            }
+            QueryParams::SubmittedDate(from, to) => {
+                let Some(published) = self.published else {
+                    return false;
+                };
+                let minute = published.format("%Y%m%d%H%M").to_string();
+                return from.as_str() <= minute.as_str() && minute.as_str() <= to.as_str();
+            }
+        }
+    }
+
+    fn matches_author(&self, term: &str) -> bool {
+        if term.contains('_') {
+            let wanted = words(term);
+            return self
+                .authors
+                .iter()
+                .any(|author| matches_surname_initial(author, &wanted));
+        }
+        return self
+            .authors
+            .iter()
+            .any(|author| matches_text(&author.name, term));
+    }
+
+    fn matches_category(&self, code: &str) -> bool {
+        if let Some(prefix) = code.strip_suffix('*') {
+            return self
+                .categories
+                .iter()
+                .any(|category| category.starts_with(prefix));
+        }
+        let category = code.parse::<Category>().unwrap();
+        return self.has_category(category);
+    }
+
+    fn matches_id(&self, id: &str) -> bool {
+        let (Ok(wanted), Some(own)) = (id.parse::<ArxivId>(), self.arxiv_id()) else {
+            return self.id == id;
+        };
+        match wanted.version() {
+            Some(_) => return wanted == own,
+            None => return wanted.base() == own.base(),
+        }
+    }
+}
+
+/// Whether the words of a `surname_f` term, e.g. `del_maestro_a`, name `author`: the surname as a trailing run of
+/// words of the name, optionally followed by the initial of the first given name. Every split of the name is tried,
+/// since capitalized particles such as `Del` can't be told apart from given names.
+fn matches_surname_initial(author: &Author, wanted: &[String]) -> bool {
+    let name = [author.given(), author.family()]
+        .iter()
+        .flat_map(|part| words(part))
+        .collect::<Vec<String>>();
+    let initial = name
+        .first()
+        .and_then(|first| first.chars().next())
+        .map(String::from);
+    return (0..name.len()).any(|start| {
+        let surname = &name[start..];
+        return wanted == surname
+            || (start > 0
+                && wanted.split_last().is_some_and(|(last, rest)| {
+                    return rest == surname && Some(last) == initial.as_ref();
+                }));
+    });
+}
+
+/// Whether the words of `term` occur in `text` in the same order, ignoring case and punctuation. A trailing `*`
+/// makes the last word a prefix.
+fn matches_text(text: &str, term: &str) -> bool {
+    let (term, prefix) = match term.trim().strip_suffix('*') {
+        Some(term) => (term, true),
+        None => (term, false),
+    };
+    let wanted = words(term);
+    if wanted.is_empty() {
+        return false;
+    }
+    let words = words(text);
+    return words.windows(wanted.len()).any(|window| {
+        let (last, init) = window.split_last().unwrap();
+        let (wanted_last, wanted_init) = wanted.split_last().unwrap();
+        return init == wanted_init
+            && match prefix {
+                true => last.starts_with(wanted_last.as_str()),
+                false => last == wanted_last,
+            };
+    });
+}
+
+/// The lowercase words of `text`, split at everything but letters and digits.
+fn words(text: &str) -> Vec<String> {
+    return text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+}
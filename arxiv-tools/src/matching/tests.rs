@@ -0,0 +1,94 @@
+use super::*;
+
+fn paper() -> Paper {
+    return crate::test_fixtures::paper()
+        .title("Counting Lattice Paths with Restricted Steps")
+        .abstract_text("We count lattice paths in the plane, using $q$-analogues.")
+        .comment("12 pages, 3 figures")
+        .build();
+}
+
+#[test]
+fn test_match_text() {
+    let paper = paper();
+    assert!(paper.matches(&QueryParams::title("lattice")));
+    assert!(paper.matches(&QueryParams::title("LATTICE PATHS")));
+    assert!(paper.matches(&QueryParams::title("restrict*")));
+    assert!(!paper.matches(&QueryParams::title("restrict")));
+    assert!(!paper.matches(&QueryParams::title("paths lattice")));
+    assert!(!paper.matches(&QueryParams::title("")));
+    assert!(paper.matches(&QueryParams::abstract_text("q-analogues")));
+    assert!(paper.matches(&QueryParams::comment("3 figures")));
+    assert!(!paper.matches(&QueryParams::journal_ref("Phys")));
+    assert!(!paper.matches(&QueryParams::report_number("CERN-TH-2021-001")));
+    assert!(paper.matches(&QueryParams::all("plane")));
+    assert!(paper.matches(&QueryParams::all("waals")));
+    assert!(!paper.matches(&QueryParams::all("neutrino")));
+}
+
+#[test]
+fn test_match_fields() {
+    let paper = paper();
+    assert!(paper.matches(&QueryParams::author("Del Maestro")));
+    assert!(paper.matches(&QueryParams::author_surname_initial("Del Maestro", "A.")));
+    assert!(paper.matches(&QueryParams::author_surname("maestro")));
+    assert!(paper.matches(&QueryParams::author_full_name("Johannes van der Waals")));
+    assert!(paper.matches(&QueryParams::author_surname_initial("Waals", "J")));
+    assert!(!paper.matches(&QueryParams::author_surname_initial("Waals", "A")));
+    assert!(!paper.matches(&QueryParams::author_surname_initial("Adrian", "A")));
+
+    assert!(paper.matches(&QueryParams::subject_category(Category::MathCo)));
+    assert!(paper.matches(&QueryParams::subject_category(Category::MathNa)));
+    assert!(paper.matches(&QueryParams::SubjectCategory(String::from("math.*"))));
+    assert!(!paper.matches(&QueryParams::subject_category(Category::CsCl)));
+
+    assert!(paper.matches(&QueryParams::id("2101.00001")));
+    assert!(paper.matches(&QueryParams::id("2101.00001v2")));
+    assert!(!paper.matches(&QueryParams::id("2101.00001v1")));
+    assert!(!paper.matches(&QueryParams::id("2101.00002")));
+
+    assert!(paper.matches(&QueryParams::submitted_date("20210101", "20210101").unwrap()));
+    assert!(paper.matches(&QueryParams::submitted_date("202101010915", "202101010915").unwrap()));
+    assert!(!paper.matches(&QueryParams::submitted_date("202101010916", "20210102").unwrap()));
+    assert!(
+        !Paper::default().matches(&QueryParams::submitted_date("20210101", "20210101").unwrap())
+    );
+}
+
+#[test]
+fn test_match_operators() {
+    let paper = paper();
+    let lattice = QueryParams::title("lattice");
+    let neutrino = QueryParams::title("neutrino");
+    assert!(paper.matches(&QueryParams::and(vec![
+        lattice.clone(),
+        QueryParams::title("paths")
+    ])));
+    assert!(!paper.matches(&QueryParams::and(vec![lattice.clone(), neutrino.clone()])));
+    assert!(paper.matches(&QueryParams::or(vec![neutrino.clone(), lattice.clone()])));
+    assert!(!paper.matches(&QueryParams::or(vec![neutrino.clone()])));
+    assert!(paper.matches(&QueryParams::and_not(vec![
+        lattice.clone(),
+        neutrino.clone()
+    ])));
+    assert!(!paper.matches(&QueryParams::and_not(vec![
+        lattice.clone(),
+        QueryParams::title("steps")
+    ])));
+    assert!(paper.matches(&QueryParams::group(vec![lattice.clone()])));
+    assert!(paper.matches(&paper_query()));
+}
+
+/// A compound query as built by callers, with a nested group.
+fn paper_query() -> QueryParams {
+    return QueryParams::and(vec![
+        QueryParams::or(vec![
+            QueryParams::title("ai"),
+            QueryParams::title("lattice"),
+        ]),
+        QueryParams::group(vec![QueryParams::or(vec![
+            QueryParams::subject_category(Category::MathCo),
+            QueryParams::subject_category(Category::CsLg),
+        ])]),
+    ]);
+}
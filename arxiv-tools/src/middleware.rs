@@ -0,0 +1,99 @@
+//! # Middleware
+//! Hooks run around every request of an [`ArxivSession`](crate::ArxivSession), e.g. to log requests, add or
+//! sign headers for an internal gateway, or collect custom metrics.
+//!
+//! ```rust
+//! # use arxiv_tools::ArxivSession;
+//! # use reqwest::header::HeaderValue;
+//! let session = ArxivSession::new();
+//! session.on_request(|request| {
+//!     request
+//!         .headers
+//!         .insert("x-signature", HeaderValue::from_static("signed"));
+//! });
+//! session.on_response(|request, response| {
+//!     eprintln!("{} took {:?}", request.url, response.elapsed);
+//! });
+//! ```
+//!
+//! Hooks run in the order they were added, once per attempt, so retried requests pass through them again.
+//! Headers are only sent by transports that support them, such as the default
+//! [`ReqwestTransport`](crate::transport::ReqwestTransport).
+use crate::ArxivError;
+use bytes::Bytes;
+use reqwest::header::HeaderMap;
+use std::fmt::Debug;
+use std::time::Duration;
+use url::Url;
+
+#[cfg(test)]
+mod tests;
+
+/// A request about to be sent.
+#[derive(Clone, Debug)]
+pub struct Request {
+    pub url: Url,
+    /// Headers sent in addition to those of the client.
+    pub headers: HeaderMap,
+}
+
+impl Request {
+    pub fn new(url: Url) -> Self {
+        return Request {
+            url,
+            headers: HeaderMap::new(),
+        };
+    }
+}
+
+/// The outcome of a request.
+#[derive(Debug)]
+pub struct Response<'a> {
+    /// The body, or the error the request failed with.
+    pub result: &'a Result<Bytes, ArxivError>,
+    /// Time from sending the request until the body was read.
+    pub elapsed: Duration,
+    /// Attempt of the request, starting at 1 and incremented by every retry.
+    pub attempt: u32,
+}
+
+pub trait Middleware: Debug + Send + Sync {
+    /// Inspect or modify `request` before it is sent.
+    fn on_request(&self, _request: &mut Request) {}
+
+    /// Inspect the outcome of `request`.
+    fn on_response(&self, _request: &Request, _response: &Response) {}
+}
+
+type RequestHook = dyn Fn(&mut Request) + Send + Sync;
+type ResponseHook = dyn Fn(&Request, &Response) + Send + Sync;
+
+/// Middleware calling a closure before every request.
+pub(crate) struct OnRequest(pub(crate) Box<RequestHook>);
+
+impl Debug for OnRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.write_str("OnRequest");
+    }
+}
+
+impl Middleware for OnRequest {
+    fn on_request(&self, request: &mut Request) {
+        (self.0)(request);
+    }
+}
+
+/// Middleware calling a closure after every request.
+pub(crate) struct OnResponse(pub(crate) Box<ResponseHook>);
+
+impl Debug for OnResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.write_str("OnResponse");
+    }
+}
+
+impl Middleware for OnResponse {
+    fn on_response(&self, request: &Request, response: &Response) {
+        (self.0)(request, response);
+    }
+}
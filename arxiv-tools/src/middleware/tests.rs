@@ -0,0 +1,70 @@
+use super::*;
+use crate::transport::{async_trait, Reply, Transport};
+use crate::{ArxivSession, QueryParams};
+use reqwest::header::HeaderValue;
+use std::sync::{Arc, Mutex};
+
+/// Serves the feed fixture and records the headers of every request.
+#[derive(Debug, Default)]
+struct Recorder {
+    headers: Arc<Mutex<Vec<HeaderMap>>>,
+}
+
+#[async_trait]
+impl Transport for Recorder {
+    async fn get(&self, url: &Url) -> Result<Bytes, ArxivError> {
+        return Ok(self.send(&Request::new(url.clone())).await?.body);
+    }
+
+    async fn send(&self, request: &Request) -> Result<Reply, ArxivError> {
+        self.headers.lock().unwrap().push(request.headers.clone());
+        return Ok(Reply::new(Bytes::from_static(
+            include_str!("../../fixtures/feed.xml").as_bytes(),
+        )));
+    }
+}
+
+/// Counts the responses it sees.
+#[derive(Debug, Default)]
+struct Counter(Arc<Mutex<u32>>);
+
+impl Middleware for Counter {
+    fn on_response(&self, _request: &Request, response: &Response) {
+        assert!(response.result.is_ok());
+        *self.0.lock().unwrap() += 1;
+    }
+}
+
+#[tokio::test]
+async fn test_middleware() {
+    let transport = Recorder::default();
+    let sent = Arc::clone(&transport.headers);
+    let session = ArxivSession::with_transport(transport);
+    session.on_request(|request| {
+        request
+            .headers
+            .insert("x-signature", HeaderValue::from_static("signed"));
+    });
+    let urls = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::clone(&urls);
+    session.on_response(move |request, response| {
+        assert_eq!(response.attempt, 1);
+        seen.lock().unwrap().push(request.url.to_string());
+    });
+    let counter = Counter::default();
+    let responses = Arc::clone(&counter.0);
+    session.add_middleware(counter);
+
+    session
+        .query(QueryParams::title("attention"))
+        .try_query()
+        .await
+        .unwrap();
+
+    let headers = sent.lock().unwrap();
+    assert_eq!(headers.len(), 1);
+    assert_eq!(headers[0]["x-signature"], "signed");
+    assert_eq!(urls.lock().unwrap().len(), 1);
+    assert!(urls.lock().unwrap()[0].contains("search_query=ti:attention"));
+    assert_eq!(*responses.lock().unwrap(), 1);
+}
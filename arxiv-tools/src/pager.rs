@@ -0,0 +1,345 @@
+//! # Paging
+//! A [`Pager`] fetches the results of a query one page at a time, only when asked to,
+//! e.g. for a "load more" button. [`ArXiv::stream`] and [`ArXiv::query_all`] page through it.
+//!
+//! ```rust,no_run
+//! # use arxiv_tools::{ArXiv, QueryParams};
+//! # #[tokio::main]
+//! # async fn main() {
+//! let mut arxiv = ArXiv::from_args(QueryParams::title("transformer"));
+//! arxiv.max_results(50);
+//!
+//! let mut pager = arxiv.pager();
+//! while let Some(page) = pager.next_page().await {
+//!     let page = page.unwrap();
+//!     println!("{} of {:?}", page.papers.len(), page.total);
+//! }
+//! # }
+//! ```
+//!
+//! For long harvests, the position of a pager can be saved as a [`Checkpoint`] after every page,
+//! and a restarted job continues from it with [`ArXiv::resume`].
+//! ```rust,no_run
+//! # use arxiv_tools::{ArXiv, Checkpoint, QueryParams};
+//! # #[tokio::main]
+//! # async fn main() {
+//! let arxiv = ArXiv::from_args(QueryParams::title("transformer"));
+//! let path = std::path::Path::new("harvest.json");
+//!
+//! let mut pager = match Checkpoint::from_file(path) {
+//!     Ok(checkpoint) => arxiv.resume(&checkpoint),
+//!     Err(_) => arxiv.pager(),
+//! };
+//! while let Some(page) = pager.next_page().await {
+//!     let page = page.unwrap();
+//!     // store page.papers ...
+//!     pager.checkpoint().to_file(path).unwrap();
+//! }
+//! # }
+//! ```
+use crate::{
+    load_file, save_file, ArXiv, ArxivError, Paper, QueryParams, SearchResults, SortBy, SortOrder,
+    MAX_PAGE_SIZE,
+};
+use anyhow::Result;
+use futures::stream::{self, Stream};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Offset beyond which queries sorted by submission date continue from a date cursor instead.
+///
+/// Deep offsets get slow and unreliable, so the pager restricts `submittedDate` to start at the last paper seen
+/// and pages from offset 0 again.
+pub const DEEP_PAGING_THRESHOLD: u64 = 10_000;
+
+/// Number of times a spuriously empty page is requested again before the results are taken as exhausted.
+pub const EMPTY_PAGE_RETRIES: u32 = 3;
+
+/// Fetches the pages of a query on demand.
+///
+/// Pages start at the `start` of the query and hold `max_results` papers, at most [`MAX_PAGE_SIZE`] and by default as many.
+/// Requests are spaced by the session's [`RateLimiter`](crate::RateLimiter), and paging stops after `limit_total` papers if it is set.
+/// An empty page is requested again, up to [`EMPTY_PAGE_RETRIES`] times, while the feed reports results beyond it,
+/// waiting as long as the session's [`RetryPolicy`](crate::RetryPolicy) between two attempts.
+/// Queries sorted by submission date switch from offsets to a date cursor past [`DEEP_PAGING_THRESHOLD`].
+#[derive(Debug)]
+pub struct Pager {
+    query: ArXiv,
+    /// The query without the `submittedDate` slice.
+    base: QueryParams,
+    page_size: u64,
+    /// `start` of the next page to fetch, `None` once the results are exhausted.
+    next: Option<u64>,
+    pages: u64,
+    fetched: u64,
+    /// `submittedDate` range the query is restricted to, if any.
+    slice: Option<(String, String)>,
+    last_seen_id: Option<String>,
+    /// Ids of the papers submitted in the minute the date cursor starts at, which the next page repeats.
+    seen_at_cursor: HashSet<String>,
+}
+
+/// Progress of a paged query, reported after every page. See [`ArXiv::on_progress`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Progress {
+    /// Index of the page just fetched, starting at 0.
+    pub page: u64,
+    /// Number of papers fetched so far, including this page.
+    pub fetched: u64,
+    /// Number of papers matching the query, as reported by the feed.
+    pub total: Option<u64>,
+}
+
+/// A callback receiving [`Progress`] updates.
+#[derive(Clone)]
+pub(crate) struct ProgressHook(pub(crate) Arc<dyn Fn(&Progress) + Send + Sync>);
+
+impl fmt::Debug for ProgressHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.write_str("ProgressHook");
+    }
+}
+
+/// The position of a [`Pager`], saved to resume a harvest where it stopped.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// `start` of the next page to fetch.
+    pub offset: u64,
+    /// `submittedDate` range being harvested, for harvests split into date slices.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slice: Option<(String, String)>,
+    /// Number of papers fetched so far, counted against `limit_total`.
+    #[serde(default)]
+    pub fetched: u64,
+    /// Id of the last paper fetched, to check for papers shifting between pages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen_id: Option<String>,
+    /// Whether every page has been fetched.
+    #[serde(default)]
+    pub done: bool,
+    /// Ids of the papers of the minute the date cursor starts at, which were fetched before the cursor moved.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub seen_at_cursor: HashSet<String>,
+}
+
+impl Checkpoint {
+    /// Load a checkpoint from a TOML (`.toml`) or JSON (`.json`) file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        return load_file(path.as_ref());
+    }
+
+    /// Save the checkpoint to a TOML (`.toml`) or JSON (`.json`) file, replacing the previous one atomically.
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        return save_file(self, path.as_ref());
+    }
+}
+
+impl Pager {
+    pub(crate) fn new(arxiv: &ArXiv) -> Self {
+        return Pager {
+            query: arxiv.clone(),
+            base: arxiv.args.clone(),
+            page_size: arxiv
+                .max_resutls
+                .unwrap_or(MAX_PAGE_SIZE)
+                .min(MAX_PAGE_SIZE),
+            next: Some(arxiv.start.unwrap_or(0)),
+            pages: 0,
+            fetched: 0,
+            slice: None,
+            last_seen_id: None,
+            seen_at_cursor: HashSet::new(),
+        };
+    }
+
+    pub(crate) fn resume(arxiv: &ArXiv, checkpoint: &Checkpoint) -> Self {
+        let mut pager = Pager::new(arxiv);
+        pager.restrict(checkpoint.slice.clone());
+        pager.next = if checkpoint.done {
+            None
+        } else {
+            Some(checkpoint.offset)
+        };
+        pager.fetched = checkpoint.fetched;
+        pager.last_seen_id = checkpoint.last_seen_id.clone();
+        pager.seen_at_cursor = checkpoint.seen_at_cursor.clone();
+        return pager;
+    }
+
+    /// Fetch the next page, or `None` once the feed ran out of results or a request failed.
+    pub async fn next_page(&mut self) -> Option<Result<SearchResults, ArxivError>> {
+        let start = self.next?;
+        let page_size = match self.query.limit_total {
+            Some(limit) => self.page_size.min(limit.saturating_sub(self.fetched)),
+            None => self.page_size,
+        };
+        if page_size == 0 {
+            self.next = None;
+            return None;
+        }
+        self.pages += 1;
+        self.query.start(start).max_results(page_size);
+        match self.fetch(start).await {
+            Ok(mut page) => {
+                page.papers.truncate(page_size as usize);
+                // the offset advances by the papers the API returned, including those of the cursor minute
+                self.next = next_page_start(start, page.papers.len(), page_size, page.total);
+                let seen = std::mem::take(&mut self.seen_at_cursor);
+                if self.next.is_some_and(|next| next >= DEEP_PAGING_THRESHOLD) {
+                    self.advance_cursor(&page.papers);
+                }
+                page.papers.retain(|paper| !seen.contains(&paper.id));
+                self.fetched += page.papers.len() as u64;
+                if self
+                    .query
+                    .limit_total
+                    .is_some_and(|limit| self.fetched >= limit)
+                {
+                    self.next = None;
+                }
+                if let Some(paper) = page.papers.last() {
+                    self.last_seen_id = Some(paper.id.clone());
+                }
+                if let Some(hook) = &self.query.progress {
+                    (hook.0)(&Progress {
+                        page: self.pages - 1,
+                        fetched: self.fetched,
+                        total: page.total,
+                    });
+                }
+                return Some(Ok(page));
+            }
+            Err(e) => {
+                self.next = None;
+                return Some(Err(e));
+            }
+        }
+    }
+
+    /// Restrict the query to the `submittedDate` range `slice`, or lift the restriction.
+    fn restrict(&mut self, slice: Option<(String, String)>) {
+        self.query.args = match &slice {
+            Some((from, to)) => QueryParams::and(vec![
+                self.base.clone().grouped(),
+                QueryParams::SubmittedDate(from.clone(), to.clone()),
+            ]),
+            None => self.base.clone(),
+        };
+        self.slice = slice;
+    }
+
+    /// Replace the offset by a `submittedDate` bound at the last paper of `papers`, restarting at offset 0.
+    ///
+    /// Only queries sorted by submission date can be continued this way; others keep paging by offset.
+    fn advance_cursor(&mut self, papers: &[Paper]) {
+        if self.query.sort_by != Some(SortBy::SubmittedDate) {
+            return;
+        }
+        let Some(bound) = papers.last().and_then(submitted_minute) else {
+            return;
+        };
+        let (from, to) = self
+            .slice
+            .clone()
+            .unwrap_or((String::from(EARLIEST), String::from(LATEST)));
+        let slice = match self.query.sort_order {
+            Some(SortOrder::Descending) => (from, bound.clone()),
+            _ => (bound.clone(), to),
+        };
+        self.seen_at_cursor = papers
+            .iter()
+            .filter(|paper| submitted_minute(paper).as_ref() == Some(&bound))
+            .map(|paper| paper.id.clone())
+            .collect();
+        self.restrict(Some(slice));
+        self.next = Some(0);
+    }
+
+    /// Send the request for the page at `start`, retrying pages that are empty although results should remain.
+    async fn fetch(&mut self, start: u64) -> Result<SearchResults, ArxivError> {
+        let mut retries = 0;
+        loop {
+            let page = self.query.try_query().await?;
+            if !is_spuriously_empty(start, &page) || retries == EMPTY_PAGE_RETRIES {
+                return Ok(page);
+            }
+            retries += 1;
+            let delay = self.query.session.retry_policy().delay(retries);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Fetch the pages in a background task, up to `ahead` pages before they are consumed.
+    pub(crate) fn prefetch(
+        mut self,
+        ahead: usize,
+    ) -> impl Stream<Item = Result<SearchResults, ArxivError>> {
+        let (sender, receiver) = mpsc::channel(ahead.max(1));
+        tokio::spawn(async move {
+            while let Some(page) = self.next_page().await {
+                if sender.send(page).await.is_err() {
+                    break;
+                }
+            }
+        });
+        return stream::unfold(receiver, |mut receiver| async move {
+            let page = receiver.recv().await?;
+            return Some((page, receiver));
+        });
+    }
+
+    /// Whether [`Pager::next_page`] may return another page.
+    pub fn has_next(&self) -> bool {
+        return self.next.is_some();
+    }
+
+    /// The current position, to be saved and passed to [`ArXiv::resume`] later.
+    pub fn checkpoint(&self) -> Checkpoint {
+        return Checkpoint {
+            offset: self.next.unwrap_or(self.query.start.unwrap_or(0)),
+            slice: self.slice.clone(),
+            fetched: self.fetched,
+            last_seen_id: self.last_seen_id.clone(),
+            done: self.next.is_none(),
+            seen_at_cursor: self.seen_at_cursor.clone(),
+        };
+    }
+
+    /// Number of pages requested so far.
+    pub fn pages(&self) -> u64 {
+        return self.pages;
+    }
+}
+
+/// `submittedDate` bounds of an unrestricted date cursor.
+const EARLIEST: &str = "000001010000";
+const LATEST: &str = "999912312359";
+
+/// The `YYYYMMDDHHMM` minute `paper` was submitted, as used by `submittedDate`.
+fn submitted_minute(paper: &Paper) -> Option<String> {
+    return paper
+        .published
+        .map(|published| published.format("%Y%m%d%H%M").to_string());
+}
+
+/// The export API occasionally answers with an empty feed for a page that has results.
+/// Such a page is detected when `totalResults` says results remain beyond `start`.
+fn is_spuriously_empty(start: u64, page: &SearchResults) -> bool {
+    return page.papers.is_empty() && page.total.is_some_and(|total| start < total);
+}
+
+/// The `start` of the page after one that began at `start` and returned `received` results, if there is one.
+fn next_page_start(start: u64, received: usize, page_size: u64, total: Option<u64>) -> Option<u64> {
+    let next = start + received as u64;
+    if (received as u64) < page_size || total.is_some_and(|total| next >= total) {
+        return None;
+    }
+    return Some(next);
+}
+
+#[cfg(test)]
+mod tests;
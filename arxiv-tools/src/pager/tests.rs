@@ -0,0 +1,304 @@
+use super::*;
+use crate::transport::Transport;
+use crate::ArxivSession;
+use async_trait::async_trait;
+use bytes::Bytes;
+use url::Url;
+
+#[test]
+fn test_next_page_start() {
+    assert_eq!(next_page_start(0, 2000, 2000, Some(4500)), Some(2000));
+    assert_eq!(next_page_start(2000, 2000, 2000, Some(4500)), Some(4000));
+    assert_eq!(next_page_start(4000, 500, 2000, Some(4500)), None);
+    assert_eq!(next_page_start(0, 100, 100, Some(100)), None);
+    assert_eq!(next_page_start(0, 100, 100, None), Some(100));
+    assert_eq!(next_page_start(100, 0, 100, None), None);
+}
+
+#[test]
+fn test_pager_settings() {
+    let mut arxiv = ArXiv::from_args(crate::QueryParams::title("llm"));
+    arxiv.start(40).max_results(5000);
+    let pager = arxiv.pager();
+    assert_eq!(pager.page_size, MAX_PAGE_SIZE);
+    assert_eq!(pager.next, Some(40));
+    assert!(pager.has_next());
+    assert_eq!(pager.pages(), 0);
+}
+
+#[test]
+fn test_checkpoint_resume() {
+    let arxiv = ArXiv::from_args(crate::QueryParams::title("llm"));
+    let checkpoint = Checkpoint {
+        offset: 4000,
+        slice: Some((String::from("202412010000"), String::from("202412012359"))),
+        fetched: 4000,
+        last_seen_id: Some(String::from("http://arxiv.org/abs/2412.00001v1")),
+        done: false,
+        seen_at_cursor: HashSet::from([String::from("http://arxiv.org/abs/2412.00001v1")]),
+    };
+    let pager = arxiv.resume(&checkpoint);
+    assert_eq!(pager.next, Some(4000));
+    assert_eq!(
+        pager.query.args.to_string(),
+        "ti:llm AND submittedDate:[202412010000 TO 202412012359]"
+    );
+    assert_eq!(pager.checkpoint(), checkpoint);
+
+    let path = std::env::temp_dir().join("arxiv_tools_checkpoint.json");
+    checkpoint.to_file(&path).unwrap();
+    assert_eq!(Checkpoint::from_file(&path).unwrap(), checkpoint);
+
+    let done = Checkpoint {
+        done: true,
+        ..checkpoint
+    };
+    assert!(!arxiv.resume(&done).has_next());
+}
+
+#[tokio::test]
+async fn test_limit_total_reached() {
+    let mut arxiv = ArXiv::from_args(crate::QueryParams::title("llm"));
+    arxiv.limit_total(100);
+    let checkpoint = Checkpoint {
+        offset: 100,
+        fetched: 100,
+        ..Default::default()
+    };
+    let mut pager = arxiv.resume(&checkpoint);
+    assert!(pager.next_page().await.is_none());
+    assert!(!pager.has_next());
+    assert_eq!(pager.pages(), 0);
+}
+
+#[test]
+fn test_spuriously_empty_page() {
+    let empty = |total| SearchResults {
+        total,
+        ..Default::default()
+    };
+    assert!(is_spuriously_empty(2000, &empty(Some(4500))));
+    assert!(!is_spuriously_empty(4500, &empty(Some(4500))));
+    assert!(!is_spuriously_empty(0, &empty(None)));
+
+    let page = SearchResults {
+        papers: vec![crate::Paper::default()],
+        total: Some(4500),
+        ..Default::default()
+    };
+    assert!(!is_spuriously_empty(2000, &page));
+}
+
+#[tokio::test]
+async fn test_prefetch_stops_with_pager() {
+    use futures::StreamExt;
+
+    let mut arxiv = ArXiv::from_args(crate::QueryParams::title("llm"));
+    arxiv.limit_total(0).prefetch(2);
+    assert_eq!(arxiv.prefetch, Some(2));
+    assert_eq!(arxiv.stream().count().await, 0);
+}
+
+#[test]
+fn test_advance_cursor() {
+    let paper = |id: &str, published: &str| crate::Paper {
+        id: id.to_string(),
+        published: published.parse().ok(),
+        ..Default::default()
+    };
+    let papers = vec![
+        paper("a", "2024-12-01T09:14:00Z"),
+        paper("b", "2024-12-01T09:15:10Z"),
+        paper("c", "2024-12-01T09:15:40Z"),
+    ];
+
+    // offset paging is kept for queries that are not sorted by submission date
+    let mut pager = ArXiv::from_args(crate::QueryParams::title("llm")).pager();
+    pager.next = Some(DEEP_PAGING_THRESHOLD);
+    pager.advance_cursor(&papers);
+    assert_eq!(pager.next, Some(DEEP_PAGING_THRESHOLD));
+
+    let mut arxiv = ArXiv::from_args(crate::QueryParams::title("llm"));
+    arxiv.sort_by(SortBy::SubmittedDate);
+    let mut pager = arxiv.pager();
+    pager.next = Some(DEEP_PAGING_THRESHOLD);
+    pager.advance_cursor(&papers);
+    assert_eq!(pager.next, Some(0));
+    assert_eq!(
+        pager.query.args.to_string(),
+        "ti:llm AND submittedDate:[202412010915 TO 999912312359]"
+    );
+    assert_eq!(
+        pager.seen_at_cursor,
+        HashSet::from([String::from("b"), String::from("c")])
+    );
+
+    arxiv.sort_order(SortOrder::Descending);
+    let mut pager = arxiv.resume(&Checkpoint {
+        slice: Some((String::from("202401010000"), String::from("202412312359"))),
+        ..Default::default()
+    });
+    pager.advance_cursor(&papers);
+    assert_eq!(
+        pager.checkpoint().slice,
+        Some((String::from("202401010000"), String::from("202412010915")))
+    );
+}
+
+/// Serves a corpus of papers sorted by submission date like the API: the unrestricted query has the corpus at offsets
+/// from `DEEP_PAGING_THRESHOLD - 4`, a query restricted to a `submittedDate` range has the matching papers from 0.
+#[derive(Debug)]
+struct Corpus {
+    /// The id and `YYYYMMDDHHMM` submission minute of every paper, oldest first.
+    papers: Vec<(String, String)>,
+}
+
+impl Corpus {
+    const OFFSET: u64 = DEEP_PAGING_THRESHOLD - 4;
+}
+
+#[async_trait]
+impl Transport for Corpus {
+    async fn get(&self, url: &Url) -> Result<Bytes, ArxivError> {
+        let param = |name: &str| {
+            return url
+                .query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+                .unwrap();
+        };
+        let (start, max_results) = (
+            param("start").parse::<u64>().unwrap(),
+            param("max_results").parse::<u64>().unwrap(),
+        );
+        let query = param("search_query");
+        let (offset, papers) = match query.split_once("submittedDate:[") {
+            Some((_, range)) => {
+                let (from, to) = range.trim_end_matches(']').split_once(" TO ").unwrap();
+                let papers = self
+                    .papers
+                    .iter()
+                    .filter(|(_, minute)| from <= minute.as_str() && minute.as_str() <= to)
+                    .collect::<Vec<_>>();
+                (0, papers)
+            }
+            None => (Corpus::OFFSET, self.papers.iter().collect()),
+        };
+        let entries = papers
+            .iter()
+            .skip((start - offset) as usize)
+            .take(max_results as usize)
+            .map(|(id, minute)| {
+                return format!(
+                    "<entry><id>{}</id><title>{}</title><published>{}-{}-{}T{}:{}:00Z</published></entry>",
+                    id,
+                    id,
+                    &minute[..4],
+                    &minute[4..6],
+                    &minute[6..8],
+                    &minute[8..10],
+                    &minute[10..]
+                );
+            })
+            .collect::<String>();
+        return Ok(Bytes::from(format!(
+            "<feed xmlns=\"http://www.w3.org/2005/Atom\" xmlns:opensearch=\"http://a9.com/-/spec/opensearch/1.1/\">\
+             <opensearch:totalResults>{}</opensearch:totalResults>{}</feed>",
+            offset + papers.len() as u64,
+            entries
+        )));
+    }
+}
+
+fn corpus_session() -> ArxivSession {
+    let minutes = [0, 1, 2, 3, 3, 4, 5, 6, 6, 7];
+    let session = ArxivSession::with_transport(Corpus {
+        papers: minutes
+            .iter()
+            .enumerate()
+            .map(|(i, minute)| (format!("paper-{}", i), format!("2024120100{:02}", minute)))
+            .collect(),
+    });
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::ZERO);
+    return session;
+}
+
+#[tokio::test]
+async fn test_next_page_crosses_deep_paging_threshold() {
+    let mut arxiv = corpus_session().query(crate::QueryParams::title("llm"));
+    arxiv
+        .start(Corpus::OFFSET)
+        .max_results(4)
+        .sort_by(SortBy::SubmittedDate);
+    let mut pager = arxiv.pager();
+    let mut ids = Vec::new();
+    let mut checkpoints = Vec::new();
+    while let Some(page) = pager.next_page().await {
+        ids.extend(page.unwrap().papers.into_iter().map(|paper| paper.id));
+        checkpoints.push(pager.checkpoint());
+    }
+    assert_eq!(
+        ids,
+        (0..10).map(|i| format!("paper-{}", i)).collect::<Vec<_>>()
+    );
+    assert_eq!(pager.pages(), 3);
+
+    // the cursor moved to the minute of paper-3 after the first page, and restarts at offset 0
+    assert_eq!(checkpoints[0].offset, 0);
+    assert_eq!(
+        checkpoints[0].slice,
+        Some((String::from("202412010003"), String::from(LATEST)))
+    );
+    assert_eq!(
+        checkpoints[0].seen_at_cursor,
+        HashSet::from([String::from("paper-3")])
+    );
+    // the second page repeated paper-3 and advanced by the 4 papers the API returned
+    assert_eq!(checkpoints[1].offset, 4);
+    assert!(checkpoints[1].seen_at_cursor.is_empty());
+    assert!(checkpoints[2].done);
+
+    // a harvest resumed right after the cursor moved doesn't repeat paper-3
+    let mut pager = arxiv.resume(&checkpoints[0]);
+    let page = pager.next_page().await.unwrap().unwrap();
+    assert_eq!(page.papers[0].id, "paper-4");
+}
+
+/// Answers every request with an empty page of a feed reporting 100 results.
+#[derive(Debug)]
+struct Empty;
+
+#[async_trait]
+impl Transport for Empty {
+    async fn get(&self, _url: &Url) -> Result<Bytes, ArxivError> {
+        return Ok(Bytes::from(
+            "<feed xmlns=\"http://www.w3.org/2005/Atom\" xmlns:opensearch=\"http://a9.com/-/spec/opensearch/1.1/\">\
+             <opensearch:totalResults>100</opensearch:totalResults></feed>",
+        ));
+    }
+}
+
+#[tokio::test]
+async fn test_empty_page_retries_back_off() {
+    let session = ArxivSession::with_transport(Empty);
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::ZERO);
+    session.set_retry_policy(crate::RetryPolicy {
+        base_delay: std::time::Duration::from_millis(20),
+        jitter: 0.0,
+        ..Default::default()
+    });
+    let mut arxiv = session.query(crate::QueryParams::title("llm"));
+    arxiv.start(50);
+    let mut pager = arxiv.pager();
+
+    let started = std::time::Instant::now();
+    let page = pager.next_page().await.unwrap().unwrap();
+    assert!(page.papers.is_empty());
+    assert_eq!(session.stats().requests, 1 + EMPTY_PAGE_RETRIES as u64);
+    // 20ms, 40ms and 80ms between the four attempts
+    assert!(started.elapsed() >= std::time::Duration::from_millis(140));
+}
@@ -0,0 +1,156 @@
+//! # Date-Range Partitioning
+//! Queries matching more papers than the API can page through are split into `submittedDate` slices,
+//! which are queried one after another and merged.
+//!
+//! ```rust,no_run
+//! # use arxiv_tools::{ArXiv, QueryParams, Category};
+//! # use arxiv_tools::partition::DateSlice;
+//! # use chrono::NaiveDate;
+//! # #[tokio::main]
+//! # async fn main() {
+//! let mut arxiv = ArXiv::from_args(QueryParams::subject_category(Category::CsLg));
+//! arxiv.max_results(2000);
+//!
+//! let from = NaiveDate::from_ymd_opt(2024, 12, 1).unwrap();
+//! let to = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+//! let papers = arxiv.query_partitioned(from, to, DateSlice::Daily).await.unwrap();
+//! # }
+//! ```
+//!
+//! [`ArXiv::backfill`] pages through every slice instead, streaming the papers of long historical ranges.
+use crate::{ArXiv, ArxivError, Checkpoint, Pager, Paper, QueryParams};
+use chrono::{Duration, NaiveDate};
+use futures::future;
+use futures::stream::{self, Stream, TryStreamExt};
+use std::collections::{HashSet, VecDeque};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum DateSlice {
+    #[default]
+    Daily,
+    Weekly,
+}
+
+impl DateSlice {
+    fn days(&self) -> i64 {
+        match self {
+            DateSlice::Daily => return 1,
+            DateSlice::Weekly => return 7,
+        }
+    }
+}
+
+/// Split the inclusive range `from..=to` into `submittedDate` ranges of one `slice` each.
+///
+/// The last slice is cut short at `to`. Returns nothing if `from` is after `to`.
+pub fn date_slices(from: NaiveDate, to: NaiveDate, slice: DateSlice) -> Vec<QueryParams> {
+    return date_ranges(from, to, slice)
+        .into_iter()
+        .map(|(from, to)| QueryParams::SubmittedDate(from, to))
+        .collect();
+}
+
+fn date_ranges(from: NaiveDate, to: NaiveDate, slice: DateSlice) -> Vec<(String, String)> {
+    let mut ranges = Vec::new();
+    let mut start = from;
+    while start <= to {
+        let end = (start + Duration::days(slice.days() - 1)).min(to);
+        ranges.push((
+            format!("{}0000", start.format("%Y%m%d")),
+            format!("{}2359", end.format("%Y%m%d")),
+        ));
+        start = end + Duration::days(1);
+    }
+    return ranges;
+}
+
+impl ArXiv {
+    /// Run the query over every `submittedDate` slice of `from..=to`, paging through each slice, and merge the results.
+    ///
+    /// Slices are paged as by [`ArXiv::backfill`], so `limit_total` applies per slice.
+    /// Papers returned by more than one slice are kept once, in the order they were first seen.
+    pub async fn query_partitioned(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        slice: DateSlice,
+    ) -> Result<Vec<Paper>, ArxivError> {
+        let mut seen = HashSet::new();
+        return self
+            .backfill(from, to, slice)
+            .try_filter(|paper| future::ready(seen.insert(paper.id.clone())))
+            .try_collect()
+            .await;
+    }
+
+    /// Stream every paper of the `submittedDate` slices of `from..=to`, paging through each slice in turn.
+    ///
+    /// Each slice is paged as by [`ArXiv::pager`], so `limit_total` applies per slice. The stream ends after the
+    /// last slice or the first error.
+    /// ```rust,no_run
+    /// # use arxiv_tools::{ArXiv, QueryParams, Category};
+    /// # use arxiv_tools::partition::DateSlice;
+    /// # use chrono::NaiveDate;
+    /// # use futures::TryStreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let arxiv = ArXiv::from_args(QueryParams::subject_category(Category::CsLg));
+    /// let from = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+    /// let to = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+    ///
+    /// let mut papers = std::pin::pin!(arxiv.backfill(from, to, DateSlice::Weekly));
+    /// while let Some(paper) = papers.try_next().await.unwrap() {
+    ///     println!("{}", paper.id);
+    /// }
+    /// # }
+    /// ```
+    pub fn backfill(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        slice: DateSlice,
+    ) -> impl Stream<Item = Result<Paper, ArxivError>> {
+        let state = Backfill {
+            query: self.clone(),
+            ranges: date_ranges(from, to, slice).into(),
+            pager: None,
+            buffer: VecDeque::new(),
+        };
+        return stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(paper) = state.buffer.pop_front() {
+                    return Some((Ok(paper), state));
+                }
+                if let Some(pager) = &mut state.pager {
+                    match pager.next_page().await {
+                        Some(Ok(page)) => state.buffer.extend(page.papers),
+                        Some(Err(e)) => {
+                            state.pager = None;
+                            state.ranges.clear();
+                            return Some((Err(e), state));
+                        }
+                        None => state.pager = None,
+                    }
+                    continue;
+                }
+                let range = state.ranges.pop_front()?;
+                state.pager = Some(state.query.resume(&Checkpoint {
+                    offset: state.query.start.unwrap_or(0),
+                    slice: Some(range),
+                    ..Default::default()
+                }));
+            }
+        });
+    }
+}
+
+/// State of [`ArXiv::backfill`]: the slices left and the pager of the current one.
+struct Backfill {
+    query: ArXiv,
+    ranges: VecDeque<(String, String)>,
+    pager: Option<Pager>,
+    buffer: VecDeque<Paper>,
+}
+
+#[cfg(test)]
+mod tests;
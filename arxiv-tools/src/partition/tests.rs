@@ -0,0 +1,118 @@
+use super::*;
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    return NaiveDate::from_ymd_opt(y, m, d).unwrap();
+}
+
+#[test]
+fn test_daily_slices() {
+    let slices = date_slices(date(2024, 12, 30), date(2025, 1, 1), DateSlice::Daily);
+    assert_eq!(
+        slices,
+        vec![
+            QueryParams::SubmittedDate(String::from("202412300000"), String::from("202412302359")),
+            QueryParams::SubmittedDate(String::from("202412310000"), String::from("202412312359")),
+            QueryParams::SubmittedDate(String::from("202501010000"), String::from("202501012359")),
+        ]
+    );
+}
+
+#[test]
+fn test_weekly_slices() {
+    let slices = date_slices(date(2024, 12, 1), date(2024, 12, 10), DateSlice::Weekly);
+    assert_eq!(
+        slices,
+        vec![
+            QueryParams::SubmittedDate(String::from("202412010000"), String::from("202412072359")),
+            QueryParams::SubmittedDate(String::from("202412080000"), String::from("202412102359")),
+        ]
+    );
+
+    assert!(date_slices(date(2024, 12, 2), date(2024, 12, 1), DateSlice::Weekly).is_empty());
+}
+
+#[tokio::test]
+async fn test_backfill_empty_range() {
+    use futures::StreamExt;
+
+    let arxiv = ArXiv::from_args(QueryParams::title("llm"));
+    let papers = arxiv
+        .backfill(date(2024, 12, 2), date(2024, 12, 1), DateSlice::default())
+        .collect::<Vec<_>>()
+        .await;
+    assert!(papers.is_empty());
+}
+
+/// Serves five papers per day of a `submittedDate` slice, plus one paper found in every slice.
+#[derive(Debug)]
+struct Daily;
+
+#[async_trait::async_trait]
+impl crate::transport::Transport for Daily {
+    async fn get(&self, url: &url::Url) -> Result<bytes::Bytes, ArxivError> {
+        let param = |name: &str| {
+            return url
+                .query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+                .unwrap();
+        };
+        let start = param("start").parse::<usize>().unwrap();
+        let max_results = param("max_results").parse::<usize>().unwrap();
+        let query = param("search_query");
+        let day = &query.split_once("submittedDate:[").unwrap().1[..8];
+        let mut ids = (0..5)
+            .map(|i| format!("{}-{}", day, i))
+            .collect::<Vec<String>>();
+        ids.push(String::from("every-day"));
+        let entries = ids
+            .iter()
+            .skip(start)
+            .take(max_results)
+            .map(|id| format!("<entry><id>{}</id><title>{}</title></entry>", id, id))
+            .collect::<String>();
+        return Ok(bytes::Bytes::from(format!(
+            "<feed xmlns=\"http://www.w3.org/2005/Atom\" \
+             xmlns:opensearch=\"http://a9.com/-/spec/opensearch/1.1/\">\
+             <opensearch:totalResults>{}</opensearch:totalResults>{}</feed>",
+            ids.len(),
+            entries
+        )));
+    }
+}
+
+#[tokio::test]
+async fn test_query_partitioned_pages_slices() {
+    let session = crate::ArxivSession::with_transport(Daily);
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::ZERO);
+    let mut arxiv = session.query(QueryParams::title("llm"));
+    arxiv.max_results(2);
+    let papers = arxiv
+        .query_partitioned(date(2024, 12, 1), date(2024, 12, 2), DateSlice::Daily)
+        .await
+        .unwrap();
+    let ids = papers
+        .iter()
+        .map(|paper| paper.id.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(
+        ids,
+        vec![
+            "20241201-0",
+            "20241201-1",
+            "20241201-2",
+            "20241201-3",
+            "20241201-4",
+            "every-day",
+            "20241202-0",
+            "20241202-1",
+            "20241202-2",
+            "20241202-3",
+            "20241202-4",
+        ]
+    );
+    // three pages of two papers per slice
+    assert_eq!(session.stats().requests, 6);
+}
@@ -0,0 +1,111 @@
+//! # Serialization Profiles
+//! Wrappers choosing how much of a [`Paper`] is serialized, e.g. to export millions of records without their
+//! abstracts:
+//! ```rust
+//! # use arxiv_tools::Paper;
+//! let paper = Paper::builder()
+//!     .id("1706.03762v7")
+//!     .title("Attention Is All You Need")
+//!     .abstract_text("The dominant sequence transduction models are based on...")
+//!     .author("Ashish Vaswani")
+//!     .author("Noam Shazeer")
+//!     .category("cs.CL")
+//!     .published("2017-06-12T17:57:34Z".parse().unwrap())
+//!     .build();
+//! assert_eq!(
+//!     serde_json::to_string(&paper.compact()).unwrap(),
+//!     r#"{"id":"http://arxiv.org/abs/1706.03762v7","title":"Attention Is All You Need","authors":["Ashish Vaswani","Noam Shazeer"],"published":"2017-06-12T17:57:34Z"}"#
+//! );
+//! assert_eq!(
+//!     serde_json::to_value(paper.full()).unwrap(),
+//!     serde_json::to_value(&paper).unwrap()
+//! );
+//! ```
+//!
+//! [`Profile`] selects a shape at runtime, e.g. from a command line option or a configuration file.
+//! The compact shape is meant for exports only: it can't be read back as a [`Paper`].
+use crate::Paper;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+
+#[cfg(test)]
+mod tests;
+
+/// A [`Paper`] serialized as its id, title, author names and publication date only.
+#[derive(Clone, Copy, Debug)]
+pub struct Compact<'a>(pub &'a Paper);
+
+impl Serialize for Compact<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let paper = self.0;
+        let authors = paper
+            .authors
+            .iter()
+            .map(|author| &*author.name)
+            .collect::<Vec<&str>>();
+        let mut state = serializer.serialize_struct("Paper", 4)?;
+        state.serialize_field("id", &paper.id)?;
+        state.serialize_field("title", &paper.title)?;
+        state.serialize_field("authors", &authors)?;
+        state.serialize_field("published", &paper.published)?;
+        return state.end();
+    }
+}
+
+/// A [`Paper`] serialized with every field, as the [`Paper`] itself is.
+#[derive(Clone, Copy, Debug)]
+pub struct Full<'a>(pub &'a Paper);
+
+impl Serialize for Full<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return self.0.serialize(serializer);
+    }
+}
+
+/// A serialization profile, serialized as its lowercase name, e.g. `"compact"`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    /// See [`Compact`].
+    Compact,
+    /// See [`Full`].
+    #[default]
+    Full,
+}
+
+impl Profile {
+    /// `paper` wrapped to be serialized in this profile.
+    pub fn apply(self, paper: &Paper) -> Profiled<'_> {
+        match self {
+            Profile::Compact => return Profiled::Compact(Compact(paper)),
+            Profile::Full => return Profiled::Full(Full(paper)),
+        }
+    }
+}
+
+/// A [`Paper`] wrapped in the profile chosen with [`Profile::apply`].
+#[derive(Clone, Copy, Debug)]
+pub enum Profiled<'a> {
+    Compact(Compact<'a>),
+    Full(Full<'a>),
+}
+
+impl Serialize for Profiled<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Profiled::Compact(compact) => return compact.serialize(serializer),
+            Profiled::Full(full) => return full.serialize(serializer),
+        }
+    }
+}
+
+impl Paper {
+    /// The paper serialized in the compact profile. See [`Compact`].
+    pub fn compact(&self) -> Compact<'_> {
+        return Compact(self);
+    }
+    /// The paper serialized in the full profile. See [`Full`].
+    pub fn full(&self) -> Full<'_> {
+        return Full(self);
+    }
+}
@@ -0,0 +1,43 @@
+use super::*;
+use crate::test_fixtures::paper;
+use serde_json::json;
+
+#[test]
+fn test_compact_profile() {
+    let paper = paper().build();
+    assert_eq!(
+        serde_json::to_value(paper.compact()).unwrap(),
+        json!({
+            "id": "http://arxiv.org/abs/2101.00001v2",
+            "title": "Counting Lattice Paths",
+            "authors": ["Adrian Del Maestro", "J. D. van der Waals"],
+            "published": "2021-01-01T09:15:00Z",
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(Paper::default().compact()).unwrap()["published"],
+        json!(null)
+    );
+}
+
+#[test]
+fn test_full_profile() {
+    let paper = paper().build();
+    let full = serde_json::to_string(&paper.full()).unwrap();
+    assert_eq!(full, serde_json::to_string(&paper).unwrap());
+    assert_eq!(serde_json::from_str::<Paper>(&full).unwrap(), paper);
+}
+
+#[test]
+fn test_select_profile() {
+    let paper = paper().build();
+    let papers = [Profile::Compact, Profile::Full]
+        .map(|profile| serde_json::to_value(profile.apply(&paper)).unwrap());
+    assert_eq!(papers[0], serde_json::to_value(paper.compact()).unwrap());
+    assert_eq!(papers[1], serde_json::to_value(&paper).unwrap());
+    assert_eq!(Profile::default(), Profile::Full);
+    assert_eq!(
+        serde_json::from_str::<Profile>(r#""compact""#).unwrap(),
+        Profile::Compact
+    );
+}
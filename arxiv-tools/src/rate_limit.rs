@@ -0,0 +1,95 @@
+//! # Rate Limiting
+//! arXiv asks API users to leave at least 3 seconds between requests. Every request sent through an
+//! [`ArxivSession`](crate::ArxivSession), including the pages fetched by auto-pagination and the listing
+//! pages, first waits for its turn at the session's [`RateLimiter`].
+//!
+//! ```rust
+//! # use arxiv_tools::ArxivSession;
+//! # use std::time::Duration;
+//! let session = ArxivSession::new();
+//! assert_eq!(session.rate_limiter().interval(), Duration::from_secs(3));
+//!
+//! // e.g. for a local mirror without usage limits
+//! session.rate_limiter().set_interval(Duration::ZERO);
+//! ```
+//!
+//! Sessions have their own limiter unless they are given a shared one, such as [`RateLimiter::global`].
+use crate::REQUEST_DELAY;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[cfg(test)]
+mod tests;
+
+/// Spaces requests at least `interval` apart. Clones share the same schedule.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    interval: Duration,
+    /// Earliest time the next request may be sent, `None` before the first request.
+    next: Option<Instant>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        return RateLimiter::new(REQUEST_DELAY);
+    }
+}
+
+impl RateLimiter {
+    pub fn new(interval: Duration) -> Self {
+        return RateLimiter {
+            state: Arc::new(Mutex::new(State {
+                interval,
+                next: None,
+            })),
+        };
+    }
+
+    /// The process-wide limiter, with the default interval until it is changed.
+    ///
+    /// Every call returns a handle to the same schedule, so all sessions using it are spaced together.
+    pub fn global() -> RateLimiter {
+        static GLOBAL: OnceLock<RateLimiter> = OnceLock::new();
+        return GLOBAL.get_or_init(RateLimiter::default).clone();
+    }
+
+    /// Whether `self` and `other` share the same schedule.
+    pub fn is_shared_with(&self, other: &RateLimiter) -> bool {
+        return Arc::ptr_eq(&self.state, &other.state);
+    }
+
+    /// Minimum time between the start of two requests.
+    pub fn interval(&self) -> Duration {
+        return self.state.lock().unwrap().interval;
+    }
+
+    /// Change the minimum time between requests. Requests already waiting keep their slot.
+    pub fn set_interval(&self, interval: Duration) {
+        self.state.lock().unwrap().interval = interval;
+    }
+
+    /// Wait until a request may be sent.
+    ///
+    /// Slots are handed out in the order `acquire` is called, so concurrent callers are spaced as well.
+    pub async fn acquire(&self) {
+        let slot = {
+            let mut state = self.state.lock().unwrap();
+            let now = Instant::now();
+            let slot = match state.next {
+                Some(next) if next > now => next,
+                _ => now,
+            };
+            state.next = Some(slot + state.interval);
+            slot
+        };
+        #[cfg(feature = "tracing")]
+        tracing::trace!(wait = ?slot.saturating_duration_since(Instant::now()), "waiting for the rate limiter");
+        tokio::time::sleep_until(slot).await;
+    }
+}
@@ -0,0 +1,56 @@
+use super::*;
+
+#[tokio::test]
+async fn test_first_request_is_immediate() {
+    let limiter = RateLimiter::new(Duration::from_secs(60));
+    let started = Instant::now();
+    limiter.acquire().await;
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_requests_are_spaced() {
+    let limiter = RateLimiter::new(Duration::from_millis(50));
+    let started = Instant::now();
+    for _ in 0..3 {
+        limiter.acquire().await;
+    }
+    assert!(started.elapsed() >= Duration::from_millis(100));
+}
+
+#[tokio::test]
+async fn test_concurrent_requests_are_spaced() {
+    let limiter = RateLimiter::new(Duration::from_millis(50));
+    let started = Instant::now();
+    futures::future::join3(limiter.acquire(), limiter.acquire(), limiter.acquire()).await;
+    assert!(started.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn test_set_interval() {
+    let limiter = RateLimiter::default();
+    assert_eq!(limiter.interval(), REQUEST_DELAY);
+    limiter.clone().set_interval(Duration::ZERO);
+    assert_eq!(limiter.interval(), Duration::ZERO);
+}
+
+#[test]
+fn test_global_limiter_is_shared() {
+    assert!(RateLimiter::global().is_shared_with(&RateLimiter::global()));
+    assert!(!RateLimiter::default().is_shared_with(&RateLimiter::default()));
+}
+
+#[test]
+fn test_sessions_share_limiter() {
+    let limiter = RateLimiter::new(Duration::from_secs(1));
+    let first = crate::ArxivSession::new();
+    let mut second = crate::ArXiv::default();
+    assert!(!first
+        .rate_limiter()
+        .is_shared_with(&second.session().rate_limiter()));
+
+    first.set_rate_limiter(limiter.clone());
+    second.with_rate_limiter(limiter.clone());
+    assert!(first.rate_limiter().is_shared_with(&limiter));
+    assert!(second.session().rate_limiter().is_shared_with(&limiter));
+}
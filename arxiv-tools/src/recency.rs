@@ -0,0 +1,93 @@
+//! # Recency
+//! The age of a paper, and the day arXiv announced it, so daily digests can group papers the way the mailings do
+//! rather than by raw submission time:
+//! ```rust
+//! # use arxiv_tools::Paper;
+//! # use chrono::NaiveDate;
+//! // submitted on a Friday afternoon, after the 14:00 ET deadline
+//! let paper = Paper::builder()
+//!     .published("2024-12-06T20:30:00Z".parse().unwrap())
+//!     .build();
+//! assert_eq!(
+//!     paper.announcement_date(),
+//!     NaiveDate::from_ymd_opt(2024, 12, 9)
+//! );
+//! assert_eq!(
+//!     paper.announced_at().unwrap().to_rfc3339(),
+//!     "2024-12-10T01:00:00+00:00"
+//! );
+//! ```
+//!
+//! arXiv accepts submissions until 14:00 US Eastern time on weekdays, and announces them at 20:00 the same day,
+//! except for Friday's submissions, which are announced on Sunday. Holidays shift the schedule and are not
+//! accounted for.
+use crate::Paper;
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Timelike, Utc, Weekday,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Hour, in US Eastern time, until which submissions make the next announcement.
+const SUBMISSION_DEADLINE_HOUR: u32 = 14;
+/// Hour, in US Eastern time, at which new submissions are announced.
+const ANNOUNCEMENT_HOUR: u32 = 20;
+
+impl Paper {
+    /// Whole days since the paper was submitted, or `None` if `published` is unknown.
+    pub fn age_days(&self) -> Option<i64> {
+        return Some((Utc::now() - self.published?).num_days());
+    }
+    /// Whether the paper was submitted within the last `days` days.
+    pub fn is_recent(&self, days: u32) -> bool {
+        return self
+            .published
+            .is_some_and(|published| Utc::now() - published <= Duration::days(days.into()));
+    }
+    /// The day, in US Eastern time, arXiv announced the first version of the paper. See [`crate::recency`].
+    pub fn announcement_date(&self) -> Option<NaiveDate> {
+        return self.published.map(announcement_date);
+    }
+    /// When arXiv announced the first version of the paper, at 20:00 US Eastern time. See [`crate::recency`].
+    pub fn announced_at(&self) -> Option<DateTime<Utc>> {
+        let date = self.announcement_date()?;
+        let local = date.and_time(NaiveTime::from_hms_opt(ANNOUNCEMENT_HOUR, 0, 0)?);
+        // 20:00 is never within a daylight saving transition, so the offset at the standard time guess is right
+        let offset = eastern_offset_hours(Utc.from_utc_datetime(&(local + Duration::hours(5))));
+        return Some(Utc.from_utc_datetime(&(local - Duration::hours(offset))));
+    }
+}
+
+/// The announcement day of a paper submitted at `submitted`.
+fn announcement_date(submitted: DateTime<Utc>) -> NaiveDate {
+    let local = submitted.naive_utc() + Duration::hours(eastern_offset_hours(submitted));
+    let mut deadline = local.date();
+    if local.hour() >= SUBMISSION_DEADLINE_HOUR {
+        deadline += Duration::days(1);
+    }
+    match deadline.weekday() {
+        Weekday::Sat => return deadline + Duration::days(2),
+        Weekday::Sun => return deadline + Duration::days(1),
+        Weekday::Fri => return deadline + Duration::days(2),
+        _ => return deadline,
+    }
+}
+
+/// The offset of US Eastern time from UTC at `instant`: -4 hours during daylight saving time, from 2:00 on the
+/// second Sunday of March until 2:00 on the first Sunday of November, -5 hours otherwise.
+fn eastern_offset_hours(instant: DateTime<Utc>) -> i64 {
+    let year = instant.year();
+    let start = nth_sunday(year, 3, 2).and_hms_opt(7, 0, 0).unwrap();
+    let end = nth_sunday(year, 11, 1).and_hms_opt(6, 0, 0).unwrap();
+    let instant = instant.naive_utc();
+    if start <= instant && instant < end {
+        return -4;
+    }
+    return -5;
+}
+
+/// The `n`th Sunday of `month` in `year`.
+fn nth_sunday(year: i32, month: u32, n: u32) -> NaiveDate {
+    return NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, n as u8).unwrap();
+}
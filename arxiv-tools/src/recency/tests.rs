@@ -0,0 +1,82 @@
+use super::*;
+
+fn submitted(published: &str) -> Paper {
+    return Paper::builder()
+        .published(published.parse().unwrap())
+        .build();
+}
+
+#[test]
+fn test_announcement_date() {
+    let cases = [
+        // Monday before the deadline (EST): announced Monday
+        ("2024-12-02T18:59:00Z", "2024-12-02"),
+        // Monday after the deadline: announced Tuesday
+        ("2024-12-02T19:00:00Z", "2024-12-03"),
+        // Thursday after the deadline and Friday before it: announced Sunday
+        ("2024-12-05T20:00:00Z", "2024-12-08"),
+        ("2024-12-06T13:00:00Z", "2024-12-08"),
+        // Friday after the deadline and the weekend: announced Monday
+        ("2024-12-06T19:00:00Z", "2024-12-09"),
+        ("2024-12-07T12:00:00Z", "2024-12-09"),
+        ("2024-12-08T23:00:00Z", "2024-12-09"),
+        // during daylight saving time the deadline is 18:00 UTC
+        ("2024-07-01T17:59:00Z", "2024-07-01"),
+        ("2024-07-01T18:00:00Z", "2024-07-02"),
+        // Tuesday 01:00 UTC is still Monday evening in New York
+        ("2024-07-02T01:00:00Z", "2024-07-02"),
+    ];
+    for (published, announced) in cases {
+        assert_eq!(
+            submitted(published).announcement_date(),
+            Some(announced.parse().unwrap()),
+            "{}",
+            published
+        );
+    }
+    assert_eq!(Paper::default().announcement_date(), None);
+}
+
+#[test]
+fn test_announced_at() {
+    let winter = submitted("2024-12-02T12:00:00Z").announced_at().unwrap();
+    assert_eq!(
+        winter,
+        "2024-12-03T01:00:00Z".parse::<DateTime<Utc>>().unwrap()
+    );
+    let summer = submitted("2024-07-01T12:00:00Z").announced_at().unwrap();
+    assert_eq!(
+        summer,
+        "2024-07-02T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+    );
+    // daylight saving time starts on March 10 and ends on November 3 in 2024
+    assert_eq!(
+        eastern_offset_hours("2024-03-10T06:59:00Z".parse().unwrap()),
+        -5
+    );
+    assert_eq!(
+        eastern_offset_hours("2024-03-10T07:00:00Z".parse().unwrap()),
+        -4
+    );
+    assert_eq!(
+        eastern_offset_hours("2024-11-03T05:59:00Z".parse().unwrap()),
+        -4
+    );
+    assert_eq!(
+        eastern_offset_hours("2024-11-03T06:00:00Z".parse().unwrap()),
+        -5
+    );
+}
+
+#[test]
+fn test_recency() {
+    let paper = Paper::builder()
+        .published(Utc::now() - Duration::hours(50))
+        .build();
+    assert_eq!(paper.age_days(), Some(2));
+    assert!(paper.is_recent(3));
+    assert!(!paper.is_recent(2));
+    assert!(!paper.is_recent(0));
+    assert_eq!(Paper::default().age_days(), None);
+    assert!(!Paper::default().is_recent(365));
+}
@@ -0,0 +1,339 @@
+//! # Replay
+//! A session can record its requests to a directory: every request is appended to an event log, and every response
+//! body is kept next to it. A later [`ArxivSession::replay`] session answers the same requests from the recording
+//! without network access, so a past run, e.g. the digest of a watcher, can be re-executed deterministically to find
+//! out why a paper was or wasn't included.
+//!
+//! ```rust,no_run
+//! # use arxiv_tools::{ArxivSession, QueryParams};
+//! # #[tokio::main]
+//! # async fn main() {
+//! let dir = std::env::temp_dir().join("arxiv_tools_doc_recording");
+//! let session = ArxivSession::new();
+//! session.record_to(&dir).unwrap();
+//! let papers = session.query(QueryParams::title("llm")).query().await;
+//!
+//! // later, offline
+//! let replay = ArxivSession::replay(&dir).unwrap();
+//! let replayed = replay.query(QueryParams::title("llm")).query().await;
+//! assert_eq!(replayed, papers);
+//! # }
+//! ```
+//!
+//! Requests are recorded by a [`RecordingTransport`] wrapping the transport of the session, streamed responses
+//! included, and replayed by a [`ReplayTransport`]. The requests of a URL are replayed in the order they were
+//! recorded, so a request that failed while recording fails again when replayed, and its retry gets the response the
+//! retry got. A `304 Not Modified` is recorded with the body it confirmed, and replayed as that body.
+//!
+//! [`ArxivSession::replay`]: crate::ArxivSession::replay
+use crate::cache::cache_key;
+use crate::middleware::Request;
+use crate::transport::{async_trait, BodyStream, Reply, Transport};
+use crate::ArxivError;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+#[cfg(test)]
+mod tests;
+
+/// File name of the event log in a recording directory.
+pub const EVENT_LOG: &str = "events.jsonl";
+/// Directory of the response bodies in a recording directory.
+pub const RESPONSES: &str = "responses";
+
+/// A request recorded in the event log.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    /// Time the response was received.
+    pub at: DateTime<Utc>,
+    /// The request URL, canonicalized by [`cache_key`].
+    pub url: String,
+    /// HTTP status of the response, `None` if the request failed without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// File of the response body in the [`RESPONSES`] directory, `None` if the request failed. For a
+    /// `304 Not Modified`, the body of the previous response to the URL, which the session returned instead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// The message of the [`ArxivError::Api`] the request failed with, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_error: Option<String>,
+    /// The error the request failed with otherwise, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Records requests to a directory: the event log [`EVENT_LOG`] and the response bodies in [`RESPONSES`].
+#[derive(Debug)]
+pub struct Recorder {
+    dir: PathBuf,
+    log: Mutex<Log>,
+}
+
+#[derive(Debug)]
+struct Log {
+    file: File,
+    /// Number of events in the log, used to name the response bodies.
+    count: usize,
+    /// File of the last response body recorded for every URL, to record a `304 Not Modified` with.
+    bodies: HashMap<String, String>,
+}
+
+impl Recorder {
+    /// Record to `dir`, created if needed. An existing recording is appended to.
+    pub fn create<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(dir.join(RESPONSES))?;
+        let events = match read_events(dir.join(EVENT_LOG)) {
+            Ok(events) => events,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        let count = events.len();
+        let bodies = events
+            .into_iter()
+            .filter_map(|event| return Some((event.url, event.body?)))
+            .collect();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(EVENT_LOG))?;
+        return Ok(Recorder {
+            dir,
+            log: Mutex::new(Log {
+                file,
+                count,
+                bodies,
+            }),
+        });
+    }
+
+    pub fn dir(&self) -> &Path {
+        return &self.dir;
+    }
+
+    /// Append the outcome of a request of `url` to the log, and keep the body of its response.
+    ///
+    /// Fails with [`ArxivError::Replay`] if the recording can't be written.
+    pub(crate) fn record(
+        &self,
+        url: &Url,
+        outcome: Result<&Reply, &ArxivError>,
+    ) -> Result<(), ArxivError> {
+        let failed = |e: io::Error| {
+            return ArxivError::Replay(format!("failed to record {}: {}", url, e));
+        };
+        let mut event = Event {
+            at: Utc::now(),
+            url: cache_key(url),
+            status: None,
+            body: None,
+            api_error: None,
+            error: None,
+        };
+        let mut log = self.log.lock().unwrap();
+        match outcome {
+            Ok(reply) if reply.status == 304 => {
+                event.status = Some(reply.status);
+                event.body = log.bodies.get(&event.url).cloned();
+            }
+            Ok(reply) => {
+                let name = format!("{:06}.body", log.count);
+                fs::write(self.dir.join(RESPONSES).join(&name), &reply.body).map_err(failed)?;
+                event.status = Some(reply.status);
+                log.bodies.insert(event.url.clone(), name.clone());
+                event.body = Some(name);
+            }
+            Err(ArxivError::Status(status)) => event.status = Some(*status),
+            Err(ArxivError::Api(message)) => event.api_error = Some(message.clone()),
+            Err(e) => event.error = Some(e.to_string()),
+        }
+        let line = serde_json::to_string(&event).map_err(io::Error::from);
+        line.and_then(|line| writeln!(log.file, "{}", line))
+            .map_err(failed)?;
+        log.count += 1;
+        return Ok(());
+    }
+}
+
+/// A [`Transport`] recording every request sent through another one with a [`Recorder`].
+///
+/// Used by [`ArxivSession::record_to`](crate::ArxivSession::record_to). A streamed response is recorded once it was
+/// read to the end. A request that can't be recorded fails with [`ArxivError::Replay`].
+#[derive(Debug)]
+pub struct RecordingTransport {
+    transport: Arc<dyn Transport>,
+    recorder: Arc<Recorder>,
+}
+
+impl RecordingTransport {
+    /// Record the requests sent through `transport` with `recorder`.
+    pub fn new<T: Transport + 'static>(transport: T, recorder: Recorder) -> Self {
+        return RecordingTransport::wrap(Arc::new(transport), recorder);
+    }
+
+    pub(crate) fn wrap(transport: Arc<dyn Transport>, recorder: Recorder) -> Self {
+        return RecordingTransport {
+            transport,
+            recorder: Arc::new(recorder),
+        };
+    }
+
+    pub fn recorder(&self) -> &Recorder {
+        return &self.recorder;
+    }
+}
+
+#[async_trait]
+impl Transport for RecordingTransport {
+    async fn get(&self, url: &Url) -> Result<Bytes, ArxivError> {
+        let body = self.transport.get(url).await;
+        match &body {
+            Ok(body) => self.recorder.record(url, Ok(&Reply::new(body.clone())))?,
+            Err(e) => self.recorder.record(url, Err(e))?,
+        }
+        return body;
+    }
+
+    async fn send(&self, request: &Request) -> Result<Reply, ArxivError> {
+        let reply = self.transport.send(request).await;
+        self.recorder.record(&request.url, reply.as_ref())?;
+        return reply;
+    }
+
+    async fn get_stream(&self, request: &Request) -> Result<BodyStream, ArxivError> {
+        let body = match self.transport.get_stream(request).await {
+            Ok(body) => body,
+            Err(e) => {
+                self.recorder.record(&request.url, Err(&e))?;
+                return Err(e);
+            }
+        };
+        let state = Some((body, Vec::new(), self.recorder.clone(), request.url.clone()));
+        return Ok(Box::pin(stream::unfold(state, |state| async move {
+            let (mut body, mut received, recorder, url) = state?;
+            match body.next().await {
+                Some(Ok(chunk)) => {
+                    received.extend_from_slice(&chunk);
+                    return Some((Ok(chunk), Some((body, received, recorder, url))));
+                }
+                Some(Err(e)) => {
+                    let recorded = recorder.record(&url, Err(&e));
+                    return Some((recorded.and(Err(e)), None));
+                }
+                None => {
+                    let reply = Reply::new(Bytes::from(received));
+                    return recorder
+                        .record(&url, Ok(&reply))
+                        .err()
+                        .map(|e| (Err(e), None));
+                }
+            }
+        })));
+    }
+}
+
+/// Read the event log at `path`, oldest event first.
+pub fn read_events<P: AsRef<Path>>(path: P) -> io::Result<Vec<Event>> {
+    let mut events = Vec::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            events.push(serde_json::from_str(&line)?);
+        }
+    }
+    return Ok(events);
+}
+
+/// A [`Transport`] answering requests from a recording instead of the network.
+///
+/// Every request takes the next recorded event of its URL: the recorded response if there was one, a recorded
+/// `304 Not Modified` being answered with the body it confirmed, as the replaying session has no previous response
+/// to answer it from. Otherwise the
+/// recorded failure, as [`ArxivError::Status`], [`ArxivError::Api`] or, e.g. for network failures,
+/// [`ArxivError::Replay`]. A request that wasn't recorded, or more often than recorded, fails with
+/// [`ArxivError::Replay`] as well.
+#[derive(Debug)]
+pub struct ReplayTransport {
+    dir: PathBuf,
+    /// The events of every request URL not replayed yet, oldest first.
+    events: Mutex<HashMap<String, VecDeque<Event>>>,
+}
+
+impl ReplayTransport {
+    /// Replay the recording in `dir`, as written by [`ArxivSession::record_to`](crate::ArxivSession::record_to).
+    pub fn open<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        let mut events: HashMap<String, VecDeque<Event>> = HashMap::new();
+        for event in read_events(dir.as_ref().join(EVENT_LOG))? {
+            events
+                .entry(event.url.clone())
+                .or_default()
+                .push_back(event);
+        }
+        return Ok(ReplayTransport {
+            dir: dir.as_ref().to_path_buf(),
+            events: Mutex::new(events),
+        });
+    }
+
+    /// The next recorded response to `url`.
+    fn next(&self, url: &Url) -> Result<Reply, ArxivError> {
+        let key = cache_key(url);
+        let event = self
+            .events
+            .lock()
+            .unwrap()
+            .get_mut(&key)
+            .and_then(|events| events.pop_front());
+        let Some(event) = event else {
+            return Err(ArxivError::Replay(format!(
+                "{} was not recorded, or not as often",
+                key
+            )));
+        };
+        if let Some(body) = event.body {
+            let body = fs::read(self.dir.join(RESPONSES).join(body)).map_err(|e| {
+                return ArxivError::Replay(format!(
+                    "failed to read the response to {}: {}",
+                    key, e
+                ));
+            })?;
+            let status = match event.status {
+                Some(304) | None => 200,
+                Some(status) => status,
+            };
+            return Ok(Reply {
+                status,
+                headers: HeaderMap::new(),
+                body: Bytes::from(body),
+            });
+        }
+        if let Some(message) = event.api_error {
+            return Err(ArxivError::Api(message));
+        }
+        if let Some(status) = event.status {
+            return Err(ArxivError::Status(status));
+        }
+        return Err(ArxivError::Replay(event.error.unwrap_or_default()));
+    }
+}
+
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn get(&self, url: &Url) -> Result<Bytes, ArxivError> {
+        return Ok(self.next(url)?.body);
+    }
+
+    async fn send(&self, request: &Request) -> Result<Reply, ArxivError> {
+        return self.next(&request.url);
+    }
+}
@@ -0,0 +1,240 @@
+use super::*;
+use crate::cache::MemoryCache;
+use crate::{ArxivSession, Paper, QueryParams, RetryPolicy};
+use futures::TryStreamExt;
+use std::time::Duration;
+
+const FEED: &str = include_str!("../../fixtures/feed.xml");
+
+/// Answers the first request with `503` and every later one with [`FEED`], streamed in small chunks.
+#[derive(Debug, Default)]
+struct Busy {
+    requests: Mutex<usize>,
+}
+
+#[async_trait]
+impl Transport for Busy {
+    async fn get(&self, _url: &Url) -> Result<Bytes, ArxivError> {
+        let mut requests = self.requests.lock().unwrap();
+        *requests += 1;
+        if *requests == 1 {
+            return Err(ArxivError::Status(503));
+        }
+        return Ok(Bytes::from_static(FEED.as_bytes()));
+    }
+
+    async fn get_stream(&self, request: &Request) -> Result<BodyStream, ArxivError> {
+        let body = self.get(&request.url).await?;
+        let chunks = body
+            .chunks(100)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect::<Vec<_>>();
+        return Ok(Box::pin(stream::iter(chunks)));
+    }
+}
+
+/// Answers `304 Not Modified` to requests carrying the `ETag` it hands out with [`FEED`].
+#[derive(Debug)]
+struct Revalidating;
+
+#[async_trait]
+impl Transport for Revalidating {
+    async fn get(&self, _url: &Url) -> Result<Bytes, ArxivError> {
+        unreachable!("requests are sent with `send`");
+    }
+
+    async fn send(&self, request: &Request) -> Result<Reply, ArxivError> {
+        if request.headers.contains_key("if-none-match") {
+            return Ok(Reply {
+                status: 304,
+                headers: HeaderMap::new(),
+                body: Bytes::new(),
+            });
+        }
+        let mut reply = Reply::new(Bytes::from_static(FEED.as_bytes()));
+        reply
+            .headers
+            .insert("etag", reqwest::header::HeaderValue::from_static("\"v1\""));
+        return Ok(reply);
+    }
+}
+
+fn session<T: Transport + 'static>(transport: T) -> ArxivSession {
+    let session = ArxivSession::with_transport(transport);
+    session.rate_limiter().set_interval(Duration::ZERO);
+    session.set_retry_policy(RetryPolicy {
+        base_delay: Duration::ZERO,
+        ..RetryPolicy::default()
+    });
+    return session;
+}
+
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "arxiv_tools_test_replay_{}_{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    return dir;
+}
+
+#[tokio::test]
+async fn test_record_and_replay() {
+    let dir = test_dir("record");
+    let session = session(Busy::default());
+    session.record_to(&dir).unwrap();
+    let mut arxiv = session.query(QueryParams::title("attention"));
+    let papers = arxiv.try_query().await.unwrap().papers;
+    assert_eq!(papers.len(), 2);
+    assert_eq!(session.stats().retries, 1);
+
+    let events = read_events(dir.join(EVENT_LOG)).unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].url, cache_key(&arxiv.build_query()));
+    assert_eq!(
+        (events[0].status, events[0].body.as_deref()),
+        (Some(503), None)
+    );
+    assert_eq!(
+        (events[1].status, events[1].body.as_deref()),
+        (Some(200), Some("000001.body"))
+    );
+
+    // the requests of a URL are replayed in order: the failure first, then the retry
+    let replay = ArxivSession::replay(&dir).unwrap();
+    let mut replayed = replay.query(QueryParams::title("attention"));
+    assert_eq!(replayed.try_query().await.unwrap().papers, papers);
+    assert_eq!(replay.stats().retries, 1);
+    assert!(matches!(
+        replayed.try_query().await,
+        Err(ArxivError::Replay(_))
+    ));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_record_stream() {
+    let dir = test_dir("stream");
+    let session = session(Busy::default());
+    session.record_to(&dir).unwrap();
+    let arxiv = session.query(QueryParams::title("attention"));
+    let papers: Vec<Paper> = arxiv.query_stream().try_collect().await.unwrap();
+    assert_eq!(papers.len(), 2);
+
+    // the streamed body is recorded whole
+    let events = read_events(dir.join(EVENT_LOG)).unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].status, Some(503));
+    let body = events[1].body.as_deref().unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.join(RESPONSES).join(body)).unwrap(),
+        FEED
+    );
+
+    let replay = ArxivSession::replay(&dir).unwrap();
+    let arxiv = replay.query(QueryParams::title("attention"));
+    let replayed: Vec<Paper> = arxiv.query_stream().try_collect().await.unwrap();
+    assert_eq!(replayed, papers);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_record_not_modified() {
+    let dir = test_dir("not_modified");
+    let session = session(Revalidating);
+    session.set_conditional_requests(true);
+    let mut arxiv = session.query(QueryParams::title("attention"));
+    arxiv.try_query().await.unwrap();
+    // the response confirmed by the next request is recorded as well
+    session.record_to(&dir).unwrap();
+    let papers = arxiv.try_query().await.unwrap().papers;
+    assert_eq!(arxiv.try_query().await.unwrap().papers, papers);
+    assert_eq!(session.stats().not_modified, 1);
+
+    let events = read_events(dir.join(EVENT_LOG)).unwrap();
+    assert_eq!(
+        events
+            .iter()
+            .map(|event| (event.status, event.body.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![
+            (Some(200), Some("000000.body")),
+            (Some(304), Some("000000.body"))
+        ]
+    );
+
+    let replay = ArxivSession::replay(&dir).unwrap();
+    let mut replayed = replay.query(QueryParams::title("attention"));
+    assert_eq!(replayed.try_query().await.unwrap().papers, papers);
+    assert_eq!(replayed.try_query().await.unwrap().papers, papers);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_record_to_keeps_the_cache() {
+    let (first, second) = (test_dir("first"), test_dir("second"));
+    let session = session(Busy::default());
+    session.set_cache(MemoryCache::new(), Duration::from_secs(60));
+    session.record_to(&first).unwrap();
+    // recording again replaces the first recording instead of adding a second one
+    session.record_to(&second).unwrap();
+    let mut arxiv = session.query(QueryParams::title("attention"));
+    arxiv.try_query().await.unwrap();
+    arxiv.try_query().await.unwrap();
+    assert_eq!(session.stats().cache_hits, 1);
+
+    assert!(read_events(first.join(EVENT_LOG)).unwrap().is_empty());
+    // the failure, its retry and the cache hit
+    assert_eq!(read_events(second.join(EVENT_LOG)).unwrap().len(), 3);
+    let replay = ArxivSession::replay(&second).unwrap();
+    let mut replayed = replay.query(QueryParams::title("attention"));
+    assert_eq!(replayed.try_query().await.unwrap().papers.len(), 2);
+    assert_eq!(replayed.try_query().await.unwrap().papers.len(), 2);
+    fs::remove_dir_all(&first).unwrap();
+    fs::remove_dir_all(&second).unwrap();
+}
+
+#[tokio::test]
+async fn test_replay_failures() {
+    let dir = test_dir("failures");
+    let url = Url::parse("http://example.com/query?b=2&a=1").unwrap();
+    let recorder = Recorder::create(&dir).unwrap();
+    recorder
+        .record(&url, Err(&ArxivError::Api(String::from("malformed id"))))
+        .unwrap();
+    recorder
+        .record(
+            &url,
+            Err(&ArxivError::Client(String::from("no TLS backend"))),
+        )
+        .unwrap();
+    // a recorder appends to the recording
+    let recorder = Recorder::create(&dir).unwrap();
+    recorder
+        .record(&url, Ok(&Reply::new(Bytes::from_static(b"<feed/>"))))
+        .unwrap();
+    assert_eq!(
+        read_events(dir.join(EVENT_LOG)).unwrap()[2].body.as_deref(),
+        Some("000002.body")
+    );
+
+    // URLs are matched canonicalized
+    let replay = ReplayTransport::open(&dir).unwrap();
+    let same = Url::parse("http://example.com/query?a=1&b=2").unwrap();
+    assert!(
+        matches!(replay.get(&same).await, Err(ArxivError::Api(message)) if message == "malformed id")
+    );
+    assert_eq!(
+        replay.get(&same).await.unwrap_err().to_string(),
+        "replay failed: failed to build the HTTP client: no TLS backend"
+    );
+    assert_eq!(replay.get(&same).await.unwrap(), "<feed/>");
+    assert!(replay
+        .get(&same)
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("was not recorded"));
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,104 @@
+//! # Retries
+//! Transient failures, e.g. a dropped connection or a `503` while arXiv is busy, are retried by the session
+//! according to its [`RetryPolicy`], waiting exponentially longer between attempts:
+//! ```rust
+//! # use arxiv_tools::{ArxivSession, RetryPolicy};
+//! # use std::time::Duration;
+//! let session = ArxivSession::new();
+//! session.set_retry_policy(RetryPolicy {
+//!     max_attempts: 5,
+//!     base_delay: Duration::from_secs(10),
+//!     ..RetryPolicy::default()
+//! });
+//! ```
+//!
+//! Retried requests still wait for the session's [`RateLimiter`](crate::RateLimiter), and the number of retries
+//! is counted in [`ClientStats::retries`](crate::ClientStats::retries).
+use crate::ArxivError;
+use std::time::Duration;
+
+#[cfg(test)]
+mod tests;
+
+/// Kind of failure a [`RetryPolicy`] may retry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RetryOn {
+    /// The request could not be sent or the response could not be read, e.g. a timeout or a reset connection.
+    Network,
+    /// The API answered with a `5xx` status.
+    ServerError,
+    /// The API answered with `429 Too Many Requests`.
+    TooManyRequests,
+    /// The API answered with this status.
+    Status(u16),
+}
+
+impl RetryOn {
+    fn matches(&self, error: &ArxivError) -> bool {
+        match (self, error) {
+            (RetryOn::Network, ArxivError::Http(e)) => {
+                return e.is_timeout() || e.is_connect() || e.is_request() || e.is_body();
+            }
+            (RetryOn::ServerError, ArxivError::Status(status)) => return *status >= 500,
+            (RetryOn::TooManyRequests, ArxivError::Status(status)) => return *status == 429,
+            (RetryOn::Status(expected), ArxivError::Status(status)) => return status == expected,
+            _ => return false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts per request, including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for every further retry.
+    pub base_delay: Duration,
+    /// Upper bound of the delay between two attempts.
+    pub max_delay: Duration,
+    /// Fraction of the delay, between `0.0` and `1.0`, that is randomized so that clients don't retry in lockstep.
+    pub jitter: f64,
+    /// Failures that are retried. Others, e.g. [`ArxivError::Api`], are returned at once.
+    pub retry_on: Vec<RetryOn>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        return RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            jitter: 0.5,
+            retry_on: vec![
+                RetryOn::Network,
+                RetryOn::ServerError,
+                RetryOn::TooManyRequests,
+            ],
+        };
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn never() -> Self {
+        return RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        };
+    }
+
+    /// Whether the request should be sent again after failing with `error` on attempt `attempt` (1-based).
+    pub fn should_retry(&self, error: &ArxivError, attempt: u32) -> bool {
+        return attempt < self.max_attempts && self.retry_on.iter().any(|on| on.matches(error));
+    }
+
+    /// Delay before the attempt following attempt `attempt` (1-based).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        return delay.mul_f64(1.0 - jitter * fastrand::f64());
+    }
+}
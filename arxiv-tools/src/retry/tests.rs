@@ -0,0 +1,44 @@
+use super::*;
+
+#[test]
+fn test_should_retry() {
+    let policy = RetryPolicy::default();
+    assert!(policy.should_retry(&ArxivError::Status(503), 1));
+    assert!(policy.should_retry(&ArxivError::Status(429), 2));
+    assert!(!policy.should_retry(&ArxivError::Status(503), 3));
+    assert!(!policy.should_retry(&ArxivError::Status(404), 1));
+    assert!(!policy.should_retry(&ArxivError::Api(String::from("bad id")), 1));
+    assert!(!RetryPolicy::never().should_retry(&ArxivError::Status(503), 1));
+
+    let policy = RetryPolicy {
+        retry_on: vec![RetryOn::Status(404)],
+        ..RetryPolicy::default()
+    };
+    assert!(policy.should_retry(&ArxivError::Status(404), 1));
+    assert!(!policy.should_retry(&ArxivError::Status(503), 1));
+}
+
+#[test]
+fn test_delay() {
+    let policy = RetryPolicy {
+        base_delay: Duration::from_secs(1),
+        max_delay: Duration::from_secs(5),
+        jitter: 0.0,
+        ..RetryPolicy::default()
+    };
+    assert_eq!(policy.delay(1), Duration::from_secs(1));
+    assert_eq!(policy.delay(2), Duration::from_secs(2));
+    assert_eq!(policy.delay(3), Duration::from_secs(4));
+    assert_eq!(policy.delay(4), Duration::from_secs(5));
+    assert_eq!(policy.delay(100), Duration::from_secs(5));
+
+    let policy = RetryPolicy {
+        jitter: 0.5,
+        ..policy
+    };
+    for attempt in 1..10 {
+        let delay = policy.delay(attempt);
+        assert!(delay <= Duration::from_secs(5));
+        assert!(delay >= Duration::from_millis(500));
+    }
+}
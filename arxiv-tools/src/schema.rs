@@ -0,0 +1,154 @@
+//! # Schema
+//! Papers kept in files, e.g. the JSON Lines of a harvest kept over years, start with a [`Header`] line stamping the
+//! [`SCHEMA_VERSION`] of their records. [`check_file`] refuses to add records to a file of another version, so records
+//! of different shapes are never mixed silently, and [`migrate`] rewrites the records of an older file in the current
+//! version:
+//! ```rust
+//! # use arxiv_tools::Paper;
+//! # use arxiv_tools::schema::{check_file, write_header};
+//! # use std::io::Write;
+//! let path = std::env::temp_dir().join("arxiv_tools_doc_schema.jsonl");
+//! # let _ = std::fs::remove_file(&path);
+//! let mut file = std::fs::File::create(&path).unwrap();
+//! write_header(&mut file).unwrap();
+//! writeln!(file, "{}", serde_json::to_string(&Paper::default()).unwrap()).unwrap();
+//!
+//! // before appending to the file later
+//! check_file(&path).unwrap();
+//! ```
+use crate::{Paper, Profile};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+/// Version of the serialized [`Paper`], incremented whenever its shape changes incompatibly:
+/// - `1`, the first stamped version;
+/// - `2`, authors are objects with a `name` and `affiliations` instead of strings;
+/// - `3`, `comment` is a string instead of a list, and the missing `doi`, `comment`, `journal_ref` and `pdf_url`
+///   are omitted instead of empty.
+///
+/// Files written before versions were stamped are version `0`. Records of every version are read as a [`Paper`].
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// The first line of a file of papers, e.g. `{"schema_version":3}`, or `{"schema_version":3,"profile":"compact"}`
+/// for the dumps of [`crate::export::jsonl::Writer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Header {
+    pub schema_version: u32,
+    /// The [`Profile`] of the records, the full one if `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Profile>,
+}
+
+impl Header {
+    /// The header of the current [`SCHEMA_VERSION`].
+    pub fn current() -> Self {
+        return Header {
+            schema_version: SCHEMA_VERSION,
+            profile: None,
+        };
+    }
+
+    /// The header of `line`, or `None` if it is a record, i.e. the file is unstamped.
+    pub(crate) fn parse(line: &str) -> Option<Header> {
+        return serde_json::from_str(line).ok();
+    }
+
+    /// Fails with [`io::ErrorKind::InvalidData`] unless records of the current [`SCHEMA_VERSION`] can be added.
+    pub fn check(&self) -> io::Result<()> {
+        if self.schema_version != SCHEMA_VERSION {
+            return Err(incompatible(self.schema_version));
+        }
+        return Ok(());
+    }
+}
+
+/// Write the header of the current [`SCHEMA_VERSION`] as a line.
+pub fn write_header<W: Write>(writer: &mut W) -> io::Result<()> {
+    return writeln!(writer, "{}", serde_json::to_string(&Header::current())?);
+}
+
+/// The header of the file read by `reader`, `None` if it is empty. Fails with [`io::ErrorKind::InvalidData`] if it
+/// is unstamped.
+pub fn read_header<R: Read>(reader: R) -> io::Result<Option<Header>> {
+    let Some(line) = BufReader::new(reader).lines().next().transpose()? else {
+        return Ok(None);
+    };
+    return match Header::parse(&line) {
+        Some(header) => Ok(Some(header)),
+        None => Err(incompatible(0)),
+    };
+}
+
+/// Check that records of the current [`SCHEMA_VERSION`] can be appended to the file at `path`: it doesn't exist, is
+/// empty or is stamped with the current version. Fails with [`io::ErrorKind::InvalidData`] otherwise; [`migrate`] it
+/// first.
+pub fn check_file<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    let header = match File::open(path) {
+        Ok(file) => read_header(file)?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e),
+    };
+    return match header {
+        Some(header) => header.check(),
+        None => Ok(()),
+    };
+}
+
+fn incompatible(version: u32) -> io::Error {
+    return io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "the file has schema version {}, not {}",
+            version, SCHEMA_VERSION
+        ),
+    );
+}
+
+/// Rewrite the JSON Lines of papers read by `reader`, of an older [`SCHEMA_VERSION`] or unstamped, to `writer` in the
+/// current one. Returns the number of papers migrated.
+///
+/// Only files of the full profile can be migrated, since the compact one can't be read back as a [`Paper`]. Fails
+/// with [`io::ErrorKind::InvalidData`] for other files, e.g. of a newer version written by a later release.
+/// ```rust
+/// # use arxiv_tools::Paper;
+/// # use arxiv_tools::schema::migrate;
+/// // a line of a file written before versions were stamped
+/// let unstamped = serde_json::to_string(&Paper::default()).unwrap();
+/// let mut migrated = Vec::new();
+/// assert_eq!(migrate(unstamped.as_bytes(), &mut migrated).unwrap(), 1);
+/// assert!(String::from_utf8(migrated).unwrap().starts_with(r#"{"schema_version":3}"#));
+/// ```
+pub fn migrate<R: BufRead, W: Write>(reader: R, writer: &mut W) -> io::Result<u64> {
+    let mut lines = reader.lines().peekable();
+    if let Some(Ok(line)) = lines.peek() {
+        if let Some(header) = Header::parse(line) {
+            if header.schema_version > SCHEMA_VERSION {
+                return Err(incompatible(header.schema_version));
+            }
+            if header.profile.unwrap_or_default() != Profile::Full {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "records of the compact profile can't be migrated",
+                ));
+            }
+            lines.next();
+        }
+    }
+    write_header(writer)?;
+    let mut count = 0;
+    for line in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let paper: Paper = serde_json::from_str(&line)?;
+        writeln!(writer, "{}", serde_json::to_string(&paper)?)?;
+        count += 1;
+    }
+    return Ok(count);
+}
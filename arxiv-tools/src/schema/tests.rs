@@ -0,0 +1,93 @@
+use super::*;
+
+fn test_path(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "arxiv_tools_test_schema_{}_{}.jsonl",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    return path;
+}
+
+fn paper(title: &str) -> Paper {
+    return Paper {
+        id: String::from("http://arxiv.org/abs/2101.00001v1"),
+        title: String::from(title),
+        ..Default::default()
+    };
+}
+
+#[test]
+fn test_check_file() {
+    let path = test_path("check");
+    // a file that doesn't exist yet can be written
+    check_file(&path).unwrap();
+
+    std::fs::write(&path, "").unwrap();
+    check_file(&path).unwrap();
+
+    let mut file = File::create(&path).unwrap();
+    write_header(&mut file).unwrap();
+    writeln!(file, "{}", serde_json::to_string(&paper("a")).unwrap()).unwrap();
+    drop(file);
+    check_file(&path).unwrap();
+    assert_eq!(
+        read_header(File::open(&path).unwrap()).unwrap(),
+        Some(Header::current())
+    );
+
+    // unstamped and other versions are refused
+    std::fs::write(&path, serde_json::to_string(&paper("a")).unwrap()).unwrap();
+    let e = check_file(&path).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    assert!(e.to_string().contains("schema version 0"));
+
+    std::fs::write(&path, r#"{"schema_version":99}"#).unwrap();
+    assert_eq!(
+        check_file(&path).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_migrate() {
+    let lines = [paper("a"), paper("b")]
+        .iter()
+        .map(|paper| serde_json::to_string(paper).unwrap())
+        .collect::<Vec<String>>()
+        .join("\n");
+    let mut migrated = Vec::new();
+    assert_eq!(migrate(lines.as_bytes(), &mut migrated).unwrap(), 2);
+    let migrated = String::from_utf8(migrated).unwrap();
+    let mut migrated_lines = migrated.lines();
+    assert_eq!(
+        Header::parse(migrated_lines.next().unwrap()),
+        Some(Header::current())
+    );
+    let titles = migrated_lines
+        .map(|line| serde_json::from_str::<Paper>(line).unwrap().title)
+        .collect::<Vec<String>>();
+    assert_eq!(titles, vec!["a", "b"]);
+
+    // a stamped file is migrated without its old header
+    let mut again = Vec::new();
+    assert_eq!(migrate(migrated.as_bytes(), &mut again).unwrap(), 2);
+    assert_eq!(again, migrated.as_bytes());
+
+    let compact = r#"{"schema_version":1,"profile":"compact"}"#;
+    assert_eq!(
+        migrate(compact.as_bytes(), &mut Vec::new())
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::InvalidData
+    );
+    let newer = r#"{"schema_version":99}"#;
+    assert_eq!(
+        migrate(newer.as_bytes(), &mut Vec::new())
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::InvalidData
+    );
+}
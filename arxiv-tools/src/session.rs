@@ -0,0 +1,513 @@
+//! # Session
+//! An [`ArxivSession`] owns the resources shared by every query derived from it: the HTTP client,
+//! so connections are pooled and reused, the [`RateLimiter`], the [`RetryPolicy`], an optional
+//! [`CircuitBreaker`] and the request statistics.
+//!
+//! ```rust
+//! # use arxiv_tools::{ArxivSession, QueryParams};
+//! let session = ArxivSession::new();
+//!
+//! let mut llm = session.query(QueryParams::title("llm"));
+//! llm.max_results(100);
+//! let agents = session.query(QueryParams::title("agent"));
+//!
+//! // both queries send their requests through the same client and share statistics
+//! assert_eq!(session.stats().requests, 0);
+//! ```
+//!
+//! The client can be configured with a [`ClientConfig`](crate::ClientConfig), a preconfigured client can be
+//! injected, or the whole HTTP layer can be replaced with [`ArxivSession::with_transport`]:
+//! ```rust
+//! # use arxiv_tools::ArxivSession;
+//! let client = reqwest::Client::builder()
+//!     .pool_max_idle_per_host(2)
+//!     .build()
+//!     .unwrap();
+//! let session = ArxivSession::with_client(client);
+//! ```
+//!
+//! Each session has its own [`RateLimiter`]. Sessions in the same process, e.g. the default sessions of
+//! independent [`ArXiv`] queries, stay within arXiv's limits together by sharing one:
+//! ```rust
+//! # use arxiv_tools::{ArXiv, ArxivSession, QueryParams, RateLimiter};
+//! let harvester = ArxivSession::new();
+//! harvester.set_rate_limiter(RateLimiter::global());
+//!
+//! let mut search = ArXiv::from_args(QueryParams::title("llm"));
+//! search.with_rate_limiter(RateLimiter::global());
+//! ```
+use crate::cache::{cache_key, Cache};
+use crate::listing::{self, ListingEntry};
+use crate::middleware::{Middleware, OnRequest, OnResponse, Request, Response};
+use crate::replay::{Recorder, RecordingTransport, ReplayTransport};
+use crate::similar::{self, SimilarPaper};
+use crate::transport::{BodyStream, Reply, ReqwestTransport, SharedTransport, Transport};
+use crate::versions;
+use crate::{
+    ArXiv, ArxivError, Category, CircuitBreaker, ClientConfig, ClientStats, Interner, Paper,
+    QueryParams, RateLimiter, RetryPolicy,
+};
+use bytes::Bytes;
+use reqwest::header::{HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+
+#[derive(Clone, Debug)]
+pub struct ArxivSession {
+    inner: Arc<SessionInner>,
+}
+
+#[derive(Debug)]
+struct SessionInner {
+    transport: Arc<dyn Transport>,
+    /// The transport wrapped to record requests, `None` unless recording.
+    recording: Mutex<Option<Arc<RecordingTransport>>>,
+    limiter: Mutex<RateLimiter>,
+    retry: Mutex<RetryPolicy>,
+    breaker: Mutex<Option<CircuitBreaker>>,
+    /// Cache of successful responses and their time-to-live.
+    cache: Mutex<Option<(Arc<dyn Cache>, Duration)>>,
+    /// Validators of previous responses by URL, `None` unless conditional requests are enabled.
+    validators: Mutex<Option<HashMap<Url, Validators>>>,
+    middleware: Mutex<Vec<Arc<dyn Middleware>>>,
+    interner: Mutex<Option<Interner>>,
+    stats: Mutex<ClientStats>,
+}
+
+impl Default for ArxivSession {
+    fn default() -> Self {
+        return ArxivSession::with_transport(SharedTransport);
+    }
+}
+
+impl ArxivSession {
+    /// Create a session with the default client, which is built on first use and shared by all default sessions.
+    ///
+    /// Use [`ArxivSession::with_config`] to build a client that fails at construction instead of at the first request.
+    pub fn new() -> Self {
+        return ArxivSession::default();
+    }
+
+    /// Create a session sending its requests through a client built from `config`.
+    pub fn with_config(config: &ClientConfig) -> Result<Self, ArxivError> {
+        return Ok(ArxivSession::with_client(config.build()?));
+    }
+
+    /// Create a session sending its requests through `client`.
+    pub fn with_client(client: reqwest::Client) -> Self {
+        return ArxivSession::with_transport(ReqwestTransport::new(client));
+    }
+
+    /// Create a session sending its requests through `transport`.
+    pub fn with_transport<T: Transport + 'static>(transport: T) -> Self {
+        return ArxivSession {
+            inner: Arc::new(SessionInner {
+                transport: Arc::new(transport),
+                recording: Mutex::new(None),
+                limiter: Mutex::new(RateLimiter::default()),
+                retry: Mutex::new(RetryPolicy::default()),
+                breaker: Mutex::new(None),
+                cache: Mutex::new(None),
+                validators: Mutex::new(None),
+                middleware: Mutex::new(Vec::new()),
+                interner: Mutex::new(None),
+                stats: Mutex::new(ClientStats::default()),
+            }),
+        };
+    }
+
+    /// Create a session answering requests from the recording in `dir` instead of the network. See
+    /// [`crate::replay`].
+    ///
+    /// Its requests don't wait for a rate limiter, nor between retries.
+    pub fn replay<P: AsRef<Path>>(dir: P) -> std::io::Result<Self> {
+        let session = ArxivSession::with_transport(ReplayTransport::open(dir)?);
+        session.rate_limiter().set_interval(Duration::ZERO);
+        session.set_retry_policy(RetryPolicy {
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            ..RetryPolicy::default()
+        });
+        return Ok(session);
+    }
+
+    /// Create a query bound to this session.
+    pub fn query(&self, args: QueryParams) -> ArXiv {
+        let mut arxiv = ArXiv::from_args(args);
+        arxiv.session = self.clone();
+        return arxiv;
+    }
+
+    /// The transport shared by all queries of this session.
+    pub fn transport(&self) -> &dyn Transport {
+        return self.inner.transport.as_ref();
+    }
+
+    /// The rate limiter every request of this session waits for.
+    pub fn rate_limiter(&self) -> RateLimiter {
+        return self.inner.limiter.lock().unwrap().clone();
+    }
+
+    /// Make the requests of this session wait for `limiter`, e.g. one shared with other sessions.
+    pub fn set_rate_limiter(&self, limiter: RateLimiter) {
+        *self.inner.limiter.lock().unwrap() = limiter;
+    }
+
+    /// The policy transient failures of this session are retried with.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        return self.inner.retry.lock().unwrap().clone();
+    }
+
+    /// Retry transient failures of this session according to `policy`.
+    pub fn set_retry_policy(&self, policy: RetryPolicy) {
+        *self.inner.retry.lock().unwrap() = policy;
+    }
+
+    /// The circuit breaker of this session, if any.
+    pub fn circuit_breaker(&self) -> Option<CircuitBreaker> {
+        return self.inner.breaker.lock().unwrap().clone();
+    }
+
+    /// Stop sending requests through this session while `breaker` is open.
+    pub fn set_circuit_breaker(&self, breaker: CircuitBreaker) {
+        *self.inner.breaker.lock().unwrap() = Some(breaker);
+    }
+
+    /// Answer requests from `cache` if possible, and cache successful responses for `ttl`.
+    ///
+    /// Cache hits don't wait for the rate limiter and aren't counted as requests.
+    pub fn set_cache<C: Cache + 'static>(&self, cache: C, ttl: Duration) {
+        *self.inner.cache.lock().unwrap() = Some((Arc::new(cache), ttl));
+    }
+
+    /// Record the requests of this session in `dir`, creating it if needed, to replay them with
+    /// [`ArxivSession::replay`]. An existing recording in `dir` is appended to.
+    ///
+    /// Requests are recorded by a [`RecordingTransport`] wrapping the transport of the session, streamed ones
+    /// included. Responses answered from the cache are recorded as well, so that they can be replayed without it.
+    /// This replaces a recording started before.
+    ///
+    /// The responses kept for conditional requests are dropped, so that the response a `304 Not Modified` confirms
+    /// is in the recording.
+    pub fn record_to<P: AsRef<Path>>(&self, dir: P) -> std::io::Result<()> {
+        let recording =
+            RecordingTransport::wrap(self.inner.transport.clone(), Recorder::create(dir)?);
+        *self.inner.recording.lock().unwrap() = Some(Arc::new(recording));
+        if let Some(validators) = self.inner.validators.lock().unwrap().as_mut() {
+            validators.clear();
+        }
+        return Ok(());
+    }
+
+    /// The transport recording the requests of this session, if it records them with [`ArxivSession::record_to`].
+    pub fn recording(&self) -> Option<Arc<RecordingTransport>> {
+        return self.inner.recording.lock().unwrap().clone();
+    }
+
+    /// Send repeated requests with `If-None-Match` / `If-Modified-Since` headers.
+    ///
+    /// When the API answers `304 Not Modified`, the previous response to the same URL is returned, e.g. for a
+    /// watcher running the same query every few minutes. The last response to every URL is kept in memory
+    /// while this is enabled; disabling it drops them.
+    pub fn set_conditional_requests(&self, enabled: bool) {
+        let mut validators = self.inner.validators.lock().unwrap();
+        match (enabled, validators.is_some()) {
+            (true, false) => *validators = Some(HashMap::new()),
+            (false, true) => *validators = None,
+            _ => (),
+        }
+    }
+
+    /// Run `middleware` around every request of this session, after the middleware added before.
+    pub fn add_middleware<M: Middleware + 'static>(&self, middleware: M) {
+        self.inner
+            .middleware
+            .lock()
+            .unwrap()
+            .push(Arc::new(middleware));
+    }
+
+    /// Call `hook` before every request of this session, e.g. to add headers. See [`ArxivSession::add_middleware`].
+    pub fn on_request<F: Fn(&mut Request) + Send + Sync + 'static>(&self, hook: F) {
+        self.add_middleware(OnRequest(Box::new(hook)));
+    }
+
+    /// Call `hook` after every request of this session. See [`ArxivSession::add_middleware`].
+    pub fn on_response<F: Fn(&Request, &Response) + Send + Sync + 'static>(&self, hook: F) {
+        self.add_middleware(OnResponse(Box::new(hook)));
+    }
+
+    /// The interner the categories and author names of the papers of this session are shared through, if any.
+    pub fn interner(&self) -> Option<Interner> {
+        return self.inner.interner.lock().unwrap().clone();
+    }
+
+    /// Share the categories and author names of the papers parsed by this session through `interner`.
+    pub fn set_interner(&self, interner: Interner) {
+        *self.inner.interner.lock().unwrap() = Some(interner);
+    }
+
+    /// Timing statistics of all requests sent through this session.
+    pub fn stats(&self) -> ClientStats {
+        return self.inner.stats.lock().unwrap().clone();
+    }
+
+    /// Fetch and parse the recent listing page of `category`. See [`listing::fetch_recent`].
+    pub async fn fetch_recent(&self, category: Category) -> Result<Vec<ListingEntry>, ArxivError> {
+        return listing::fetch_recent_in(self, category).await;
+    }
+
+    /// Fetch the metadata of every version of the paper `id`, oldest first. See [`crate::versions`].
+    pub async fn versions(&self, id: &str) -> Result<Vec<Paper>, ArxivError> {
+        return versions::versions_in(self, id).await;
+    }
+
+    /// Find the `k` papers most similar to the paper `id`. See [`similar::find_similar_to`].
+    pub async fn find_similar_to(
+        &self,
+        id: &str,
+        k: usize,
+    ) -> Result<Vec<SimilarPaper>, ArxivError> {
+        return similar::find_similar_in(self, id, k).await;
+    }
+
+    /// Send a request through the transport once the rate limiter allows it, and record its duration.
+    ///
+    /// Cached responses are returned without a request. Transient failures are retried according to the retry policy, unless the circuit breaker opens.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(url = %url))
+    )]
+    pub(crate) async fn get(&self, url: &Url) -> Result<Bytes, ArxivError> {
+        let cache = self.inner.cache.lock().unwrap().clone();
+        let Some((cache, ttl)) = cache else {
+            return self.fetch(url).await;
+        };
+        let key = cache_key(url);
+        if let Some(body) = cache.get(&key).await {
+            self.inner.stats.lock().unwrap().cache_hits += 1;
+            if let Some(recording) = self.recording() {
+                recording
+                    .recorder()
+                    .record(url, Ok(&Reply::new(body.clone())))?;
+            }
+            return Ok(body);
+        }
+        let body = self.fetch(url).await?;
+        cache.put(&key, body.clone(), ttl).await;
+        return Ok(body);
+    }
+
+    /// Send a request, bypassing the cache.
+    async fn fetch(&self, url: &Url) -> Result<Bytes, ArxivError> {
+        let policy = self.retry_policy();
+        let mut attempt = 1;
+        loop {
+            let permit = match self
+                .circuit_breaker()
+                .map(|breaker| breaker.check())
+                .transpose()
+            {
+                Ok(permit) => permit,
+                Err(e) => {
+                    self.record_outcome(Err(&e));
+                    return Err(e);
+                }
+            };
+            let middleware = self.inner.middleware.lock().unwrap().clone();
+            let mut request = Request::new(url.clone());
+            let cached = self.cached(url);
+            if let Some(cached) = &cached {
+                cached.apply(&mut request);
+            }
+            for middleware in &middleware {
+                middleware.on_request(&mut request);
+            }
+            self.rate_limiter().acquire().await;
+            let started = Instant::now();
+            let body = match self.sender().send(&request).await {
+                Ok(reply) => self.revalidate(url, reply, cached),
+                Err(e) => Err(e),
+            };
+            let elapsed = started.elapsed();
+            self.record(elapsed);
+            if let Err(e) = &body {
+                self.record_outcome(Err(e));
+            }
+            if let Some(permit) = permit {
+                permit.record(&body);
+            }
+            let response = Response {
+                result: &body,
+                elapsed,
+                attempt,
+            };
+            for middleware in &middleware {
+                middleware.on_response(&request, &response);
+            }
+            #[cfg(feature = "tracing")]
+            match &body {
+                Ok(body) => {
+                    tracing::debug!(attempt, ?elapsed, bytes = body.len(), "request succeeded")
+                }
+                Err(e) => tracing::warn!(attempt, ?elapsed, error = %e, "request failed"),
+            }
+            match body {
+                Err(e) if policy.should_retry(&e, attempt) => {
+                    self.inner.stats.lock().unwrap().record_retry();
+                    let delay = policy.delay(attempt);
+                    #[cfg(feature = "tracing")]
+                    tracing::info!(attempt, ?delay, error = %e, "retrying request");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                body => return body,
+            }
+        }
+    }
+
+    /// Send a request like [`ArxivSession::get`], but return the body as a stream of chunks as they arrive.
+    ///
+    /// Failures before the body is streamed are retried according to the retry policy. The body is not buffered, so
+    /// it bypasses the cache and conditional requests, and [`Middleware::on_response`] is not called.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(url = %url))
+    )]
+    pub(crate) async fn get_stream(&self, url: &Url) -> Result<BodyStream, ArxivError> {
+        let policy = self.retry_policy();
+        let mut attempt = 1;
+        loop {
+            let permit = match self
+                .circuit_breaker()
+                .map(|breaker| breaker.check())
+                .transpose()
+            {
+                Ok(permit) => permit,
+                Err(e) => {
+                    self.record_outcome(Err(&e));
+                    return Err(e);
+                }
+            };
+            let mut request = Request::new(url.clone());
+            for middleware in self.inner.middleware.lock().unwrap().clone() {
+                middleware.on_request(&mut request);
+            }
+            self.rate_limiter().acquire().await;
+            let started = Instant::now();
+            let body = self.sender().get_stream(&request).await;
+            self.record(started.elapsed());
+            if let Some(permit) = permit {
+                permit.record(&body);
+            }
+            match body {
+                Err(e) if policy.should_retry(&e, attempt) => {
+                    self.record_outcome(Err(&e));
+                    self.inner.stats.lock().unwrap().record_retry();
+                    #[cfg(feature = "tracing")]
+                    tracing::info!(attempt, error = %e, "retrying request");
+                    tokio::time::sleep(policy.delay(attempt)).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    self.record_outcome(Err(&e));
+                    return Err(e);
+                }
+                body => return body,
+            }
+        }
+    }
+
+    /// The transport requests are sent through: the one of the session, wrapped while recording.
+    fn sender(&self) -> Arc<dyn Transport> {
+        if let Some(recording) = self.recording() {
+            return recording;
+        }
+        return self.inner.transport.clone();
+    }
+
+    /// Validators of the previous response to `url`, if conditional requests are enabled.
+    fn cached(&self, url: &Url) -> Option<Validators> {
+        return self
+            .inner
+            .validators
+            .lock()
+            .unwrap()
+            .as_ref()?
+            .get(url)
+            .cloned();
+    }
+
+    /// The body of `reply`, or of the `cached` response if `reply` is `304 Not Modified`.
+    fn revalidate(
+        &self,
+        url: &Url,
+        reply: Reply,
+        cached: Option<Validators>,
+    ) -> Result<Bytes, ArxivError> {
+        if reply.status == 304 {
+            let Some(cached) = cached else {
+                return Err(ArxivError::Status(reply.status));
+            };
+            self.inner.stats.lock().unwrap().not_modified += 1;
+            return Ok(cached.body);
+        }
+        if let Some(validators) = self.inner.validators.lock().unwrap().as_mut() {
+            match Validators::from_reply(&reply) {
+                Some(entry) => validators.insert(url.clone(), entry),
+                None => validators.remove(url),
+            };
+        }
+        return Ok(reply.body);
+    }
+
+    pub(crate) fn record(&self, duration: Duration) {
+        self.inner.stats.lock().unwrap().record(duration);
+    }
+
+    /// Count the papers of a parsed response, or the class of an error.
+    pub(crate) fn record_outcome(&self, outcome: Result<usize, &ArxivError>) {
+        let mut stats = self.inner.stats.lock().unwrap();
+        match outcome {
+            Ok(papers) => stats.record_papers(papers),
+            Err(e) => stats.record_error(e),
+        }
+    }
+}
+
+/// `ETag` and `Last-Modified` of a response, with its body to answer `304 Not Modified`.
+#[derive(Clone, Debug)]
+struct Validators {
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+    body: Bytes,
+}
+
+impl Validators {
+    fn from_reply(reply: &Reply) -> Option<Self> {
+        let etag = reply.headers.get(ETAG).cloned();
+        let last_modified = reply.headers.get(LAST_MODIFIED).cloned();
+        if etag.is_none() && last_modified.is_none() {
+            return None;
+        }
+        return Some(Validators {
+            etag,
+            last_modified,
+            body: reply.body.clone(),
+        });
+    }
+
+    fn apply(&self, request: &mut Request) {
+        if let Some(etag) = &self.etag {
+            request.headers.insert(IF_NONE_MATCH, etag.clone());
+        }
+        if let Some(last_modified) = &self.last_modified {
+            request
+                .headers
+                .insert(IF_MODIFIED_SINCE, last_modified.clone());
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! # Query by Example
+//! Find papers similar to a given arXiv paper by searching for its most salient title/abstract terms
+//! and ranking the candidates by term overlap with the seed paper.
+//!
+//! ```rust,no_run
+//! # use arxiv_tools::similar::find_similar_to;
+//! # #[tokio::main]
+//! # async fn main() {
+//! let related = find_similar_to("1706.03762", 10).await.unwrap();
+//! for similar in related {
+//!     println!("{:.3} {}", similar.score, similar.paper.title);
+//! }
+//! # }
+//! ```
+use crate::{ArxivError, ArxivSession, Paper, QueryParams};
+use std::collections::{HashMap, HashSet};
+
+/// Number of salient terms used to build the candidate queries.
+const QUERY_TERMS: usize = 6;
+/// Title terms count this many times more than abstract terms when picking salient terms.
+const TITLE_WEIGHT: usize = 3;
+
+const STOPWORDS: &[&str] = &[
+    "a", "about", "above", "after", "again", "against", "all", "also", "am", "an", "and", "any",
+    "approach", "are", "as", "at", "based", "be", "because", "been", "before", "being", "below",
+    "between", "both", "but", "by", "can", "could", "did", "do", "does", "doing", "down", "during",
+    "each", "few", "for", "from", "further", "had", "has", "have", "having", "here", "how",
+    "however", "if", "in", "into", "is", "it", "its", "itself", "method", "methods", "more",
+    "most", "new", "no", "nor", "not", "novel", "of", "off", "on", "once", "only", "or", "other",
+    "our", "ours", "out", "over", "own", "paper", "propose", "proposed", "result", "results",
+    "same", "show", "shows", "so", "some", "such", "than", "that", "the", "their", "them", "then",
+    "there", "these", "they", "this", "those", "through", "to", "too", "under", "until", "up",
+    "use", "used", "using", "very", "via", "was", "we", "were", "what", "when", "where", "which",
+    "while", "who", "whom", "why", "will", "with", "work", "would", "you", "your",
+];
+
+#[derive(Debug, Clone)]
+pub struct SimilarPaper {
+    pub paper: Paper,
+    /// Jaccard similarity of the title/abstract terms, between 0 and 1.
+    pub score: f64,
+}
+
+/// Fetch the paper `id`, search for papers sharing its salient terms and return the `k` most similar ones.
+///
+/// Returns an empty list if the seed paper cannot be found.
+/// Use [`ArxivSession::find_similar_to`] to send the requests through an existing session.
+pub async fn find_similar_to(id: &str, k: usize) -> Result<Vec<SimilarPaper>, ArxivError> {
+    return ArxivSession::new().find_similar_to(id, k).await;
+}
+
+pub(crate) async fn find_similar_in(
+    session: &ArxivSession,
+    id: &str,
+    k: usize,
+) -> Result<Vec<SimilarPaper>, ArxivError> {
+    let mut seed_query = session.query(QueryParams::id(id));
+    let seed = match seed_query.try_query().await?.papers.into_iter().next() {
+        Some(seed) => seed,
+        None => return Ok(Vec::new()),
+    };
+
+    let terms = salient_terms(&seed, QUERY_TERMS);
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+    let candidates_per_query = (k as u64 * 5).max(20);
+    let mut queries = vec![QueryParams::or(
+        terms.iter().map(|term| QueryParams::all(term)).collect(),
+    )];
+    if terms.len() >= 2 {
+        queries.push(QueryParams::and(vec![
+            QueryParams::abstract_text(&terms[0]),
+            QueryParams::abstract_text(&terms[1]),
+        ]));
+    }
+
+    let mut seen = HashSet::new();
+    seen.insert(seed.id.clone());
+    let mut candidates = Vec::new();
+    for args in queries {
+        let mut arxiv = session.query(args);
+        arxiv.max_results(candidates_per_query);
+        for paper in arxiv.try_query().await?.papers {
+            if seen.insert(paper.id.clone()) {
+                candidates.push(paper);
+            }
+        }
+    }
+
+    return Ok(rank_by_similarity(&seed, candidates, k));
+}
+
+/// Rank `candidates` by their similarity to `seed` and keep the best `k`.
+pub fn rank_by_similarity(seed: &Paper, candidates: Vec<Paper>, k: usize) -> Vec<SimilarPaper> {
+    let seed_terms = term_set(seed);
+    let mut ranked = candidates
+        .into_iter()
+        .map(|paper| {
+            let score = jaccard(&seed_terms, &term_set(&paper));
+            SimilarPaper { paper, score }
+        })
+        .collect::<Vec<SimilarPaper>>();
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    ranked.truncate(k);
+    return ranked;
+}
+
+/// The `n` most frequent non-stopword terms of the title and abstract, title terms weighted higher.
+pub fn salient_terms(paper: &Paper, n: usize) -> Vec<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for term in tokenize(&paper.title) {
+        *counts.entry(term).or_default() += TITLE_WEIGHT;
+    }
+    for term in tokenize(&paper.abstract_text) {
+        *counts.entry(term).or_default() += 1;
+    }
+    let mut terms = counts.into_iter().collect::<Vec<(String, usize)>>();
+    terms.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    return terms.into_iter().take(n).map(|(term, _)| term).collect();
+}
+
+fn term_set(paper: &Paper) -> HashSet<String> {
+    return tokenize(&paper.title)
+        .chain(tokenize(&paper.abstract_text))
+        .collect();
+}
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    return text
+        .split(|c: char| !c.is_alphanumeric() && c != '-')
+        .map(|word| word.trim_matches('-').to_lowercase())
+        .filter(|word| word.chars().count() > 2 && !STOPWORDS.contains(&word.as_str()))
+        .filter(|word| !word.chars().all(|c| c.is_ascii_digit()));
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    return a.intersection(b).count() as f64 / union as f64;
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,50 @@
+use super::*;
+
+fn paper(id: &str, title: &str, abstract_text: &str) -> Paper {
+    return Paper {
+        id: id.to_string(),
+        title: title.to_string(),
+        abstract_text: abstract_text.to_string(),
+        ..Default::default()
+    };
+}
+
+#[test]
+fn test_salient_terms() {
+    let seed = paper(
+        "1706.03762",
+        "Attention Is All You Need",
+        "We propose the Transformer, based solely on attention mechanisms. \
+         The Transformer generalizes well to other tasks.",
+    );
+    let terms = salient_terms(&seed, 3);
+    assert_eq!(terms, vec!["attention", "need", "transformer"]);
+}
+
+#[test]
+fn test_rank_by_similarity() {
+    let seed = paper(
+        "seed",
+        "Attention Is All You Need",
+        "The Transformer relies on self-attention.",
+    );
+    let candidates = vec![
+        paper(
+            "far",
+            "Protein Folding",
+            "Structure prediction of proteins.",
+        ),
+        paper(
+            "near",
+            "Self-Attention in Transformer Models",
+            "Attention is studied.",
+        ),
+        paper("mid", "Attention for Graphs", "Graph networks."),
+    ];
+
+    let ranked = rank_by_similarity(&seed, candidates, 2);
+    assert_eq!(ranked.len(), 2);
+    assert_eq!(ranked[0].paper.id, "near");
+    assert_eq!(ranked[1].paper.id, "mid");
+    assert!(ranked[0].score > ranked[1].score);
+}
@@ -0,0 +1,97 @@
+//! # Statistics
+//! Simple request statistics kept by [`ArxivSession`](crate::ArxivSession), for applications without a metrics stack.
+//!
+//! With the `metrics` feature, the same measurements are also reported through the
+//! [`metrics`](https://docs.rs/metrics) facade, so any installed recorder, e.g. a Prometheus exporter, picks them up:
+//!
+//! | metric | kind | labels |
+//! |---|---|---|
+//! | [`REQUESTS`] | counter | |
+//! | [`RETRIES`] | counter | |
+//! | [`ERRORS`] | counter | `class`, see [`ArxivError::class`] |
+//! | [`REQUEST_DURATION`] | histogram, seconds | |
+//! | [`PAPERS`] | counter | |
+use crate::ArxivError;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Duration;
+
+/// Name of the counter of requests sent to the API.
+pub const REQUESTS: &str = "arxiv_requests_total";
+/// Name of the counter of retried requests.
+pub const RETRIES: &str = "arxiv_retries_total";
+/// Name of the counter of failed requests and responses, labeled by error `class`.
+pub const ERRORS: &str = "arxiv_errors_total";
+/// Name of the histogram of request durations in seconds.
+pub const REQUEST_DURATION: &str = "arxiv_request_duration_seconds";
+/// Name of the counter of papers parsed from responses.
+pub const PAPERS: &str = "arxiv_papers_parsed_total";
+
+/// Number of recent requests the rolling average latency is computed over.
+pub const ROLLING_WINDOW: usize = 20;
+
+#[derive(Clone, Debug, Default)]
+pub struct ClientStats {
+    /// Number of requests sent to the API.
+    pub requests: u64,
+    /// Number of requests that were sent again after a transient failure. Retries count as requests as well.
+    pub retries: u64,
+    /// Number of requests answered from the cache of the session without being sent.
+    pub cache_hits: u64,
+    /// Number of conditional requests answered with `304 Not Modified` and served from the previous response.
+    pub not_modified: u64,
+    /// Number of failures by [`ArxivError::class`], including failures that were retried.
+    pub errors: BTreeMap<&'static str, u64>,
+    /// Number of papers parsed from responses.
+    pub papers: u64,
+    /// Duration of the most recent request, including reading the response body.
+    pub last_duration: Option<Duration>,
+    recent: VecDeque<Duration>,
+}
+
+impl ClientStats {
+    pub(crate) fn record(&mut self, duration: Duration) {
+        self.requests += 1;
+        self.last_duration = Some(duration);
+        if self.recent.len() == ROLLING_WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(duration);
+        #[cfg(feature = "metrics")]
+        {
+            metrics::counter!(REQUESTS).increment(1);
+            metrics::histogram!(REQUEST_DURATION).record(duration.as_secs_f64());
+        }
+    }
+
+    pub(crate) fn record_retry(&mut self) {
+        self.retries += 1;
+        #[cfg(feature = "metrics")]
+        metrics::counter!(RETRIES).increment(1);
+    }
+
+    pub(crate) fn record_error(&mut self, error: &ArxivError) {
+        *self.errors.entry(error.class()).or_default() += 1;
+        #[cfg(feature = "metrics")]
+        metrics::counter!(ERRORS, "class" => error.class()).increment(1);
+    }
+
+    pub(crate) fn record_papers(&mut self, papers: usize) {
+        self.papers += papers as u64;
+        #[cfg(feature = "metrics")]
+        metrics::counter!(PAPERS).increment(papers as u64);
+    }
+
+    /// Number of failures of all classes.
+    pub fn error_count(&self) -> u64 {
+        return self.errors.values().sum();
+    }
+
+    /// Average latency of the last [`ROLLING_WINDOW`] requests.
+    pub fn rolling_average(&self) -> Option<Duration> {
+        if self.recent.is_empty() {
+            return None;
+        }
+        let total: Duration = self.recent.iter().sum();
+        return Some(total / self.recent.len() as u32);
+    }
+}
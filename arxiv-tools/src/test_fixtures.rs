@@ -0,0 +1,18 @@
+//! Papers shared by the unit tests.
+use crate::builder::PaperBuilder;
+use crate::Paper;
+
+/// A builder preset with a paper filling the fields most tests look at, to be adjusted by the test before `build`.
+pub(crate) fn paper() -> PaperBuilder {
+    let mut builder = Paper::builder();
+    builder
+        .id("2101.00001v2")
+        .title("Counting Lattice Paths")
+        .abstract_text("We count\nlattice paths.")
+        .author("Adrian Del Maestro")
+        .author("J. D. van der Waals")
+        .category("math.CO")
+        .category("cs.NA")
+        .published("2021-01-01T09:15:00Z".parse().unwrap());
+    return builder;
+}
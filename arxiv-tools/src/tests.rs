@@ -1,4 +1,7 @@
 use super::*;
+use chrono::TimeZone;
+use std::collections::{BTreeSet, HashSet};
+use std::time::Duration;
 
 #[tokio::test]
 async fn test_no_such_a_paper() {
@@ -76,3 +79,805 @@ async fn test_query_complex() {
     let response = serde_json::to_string_pretty(&response.first().unwrap()).unwrap();
     println!("{}", response);
 }
+
+#[test]
+fn test_query_file_roundtrip() {
+    let args = QueryParams::and(vec![
+        QueryParams::title("attention"),
+        QueryParams::SubmittedDate(String::from("202412010000"), String::from("202412012359")),
+    ]);
+    let mut arxiv = ArXiv::from_args(args);
+    arxiv.max_results(50);
+    arxiv.sort_by(SortBy::SubmittedDate);
+    arxiv.sort_order(SortOrder::Descending);
+
+    for ext in ["toml", "json"] {
+        let path = std::env::temp_dir().join(format!("arxiv_tools_test_query.{}", ext));
+        arxiv.to_file(&path).unwrap();
+        let loaded = ArXiv::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.build_query(), arxiv.build_query());
+    }
+
+    assert!(arxiv
+        .to_file(std::env::temp_dir().join("query.yaml"))
+        .is_err());
+}
+
+#[test]
+fn test_query_job_serde() {
+    let args = QueryParams::and(vec![
+        QueryParams::author("Vaswani"),
+        QueryParams::group(vec![QueryParams::or(vec![
+            QueryParams::subject_category(Category::CsCl),
+            QueryParams::subject_category(Category::CsLg),
+        ])]),
+        QueryParams::SubmittedDate(String::from("202412010000"), String::from("202412012359")),
+    ]);
+    let mut arxiv = ArXiv::from_args(args.clone());
+    arxiv.start(100);
+    arxiv.sort_order(SortOrder::Descending);
+
+    let payload = serde_json::to_string(&arxiv).unwrap();
+    assert!(!payload.contains("sort_by"));
+    assert!(payload.contains(r#""sort_order":"descending""#));
+
+    let job: ArXiv = serde_json::from_str(&payload).unwrap();
+    assert_eq!(job.args, args);
+    assert_eq!(job.start, Some(100));
+    assert_eq!(job.max_resutls, None);
+    assert_eq!(job.sort_by, None);
+    assert_eq!(job.sort_order, Some(SortOrder::Descending));
+
+    let job: ArXiv = serde_json::from_str(r#"{"args":{"Title":"llm"}}"#).unwrap();
+    assert_eq!(job.args, QueryParams::title("llm"));
+}
+
+#[test]
+fn test_exclude_category() {
+    let args = QueryParams::title("llm").exclude_category(Category::CsCv);
+    assert_eq!(
+        args,
+        QueryParams::AndNot(vec![
+            QueryParams::title("llm"),
+            QueryParams::subject_category(Category::CsCv)
+        ])
+    );
+
+    let args = QueryParams::or(vec![QueryParams::title("ai"), QueryParams::title("llm")])
+        .exclude_category(Category::CsCv);
+    assert_eq!(args.to_string(), "(ti:ai OR ti:llm) ANDNOT cat:cs.CV");
+}
+
+#[test]
+fn test_categories() {
+    let args = QueryParams::and(vec![
+        QueryParams::title("llm"),
+        QueryParams::categories(&[Category::CsAi, Category::CsLg, Category::CsCl]),
+    ]);
+    assert_eq!(
+        args.to_string(),
+        "ti:llm AND (cat:cs.AI OR cat:cs.LG OR cat:cs.CL)"
+    );
+
+    let args = QueryParams::categories(&[Category::CsIr]);
+    assert_eq!(args, QueryParams::subject_category(Category::CsIr));
+}
+
+#[test]
+fn test_author_name_helpers() {
+    assert_eq!(
+        QueryParams::author_surname_initial("Vaswani", "A").to_string(),
+        "au:Vaswani_A"
+    );
+    assert_eq!(
+        QueryParams::author_surname("del Maestro").to_string(),
+        "au:del_Maestro"
+    );
+    assert_eq!(
+        QueryParams::author_full_name("Ashish Vaswani"),
+        QueryParams::author_surname_initial("Vaswani", "A")
+    );
+    assert_eq!(
+        QueryParams::author_full_name("Vaswani, Ashish"),
+        QueryParams::author_surname_initial("Vaswani", "A")
+    );
+    assert_eq!(
+        QueryParams::author_full_name("Vaswani"),
+        QueryParams::author_surname("Vaswani")
+    );
+}
+
+#[test]
+fn test_author_names() {
+    let cases = [
+        ("Ashish Vaswani", "Ashish", "Vaswani", "vaswani_a"),
+        ("Vaswani, Ashish", "Ashish", "Vaswani", "vaswani_a"),
+        (
+            "J. D. van der Waals",
+            "J. D.",
+            "van der Waals",
+            "van_der_waals_j",
+        ),
+        ("Adrian Del Maestro", "Adrian Del", "Maestro", "maestro_a"),
+        (
+            "Del Maestro, Adrian",
+            "Adrian",
+            "Del Maestro",
+            "del_maestro_a",
+        ),
+        (
+            "  Ludwig  de  la Vallée ",
+            "Ludwig",
+            "de  la Vallée",
+            "de_la_vallée_l",
+        ),
+        ("de Gennes", "de", "Gennes", "gennes_d"),
+        // words separated by other whitespace and starting with multi-byte characters
+        (
+            "Émile\tÉtienne\u{a0}Borel",
+            "Émile\tÉtienne",
+            "Borel",
+            "borel_é",
+        ),
+        ("Plato", "", "Plato", "plato"),
+        ("", "", "", ""),
+    ];
+    for (name, given, family, key) in cases {
+        let author = Author::new(name);
+        assert_eq!(author.given(), given, "{:?}", name);
+        assert_eq!(author.family(), family, "{:?}", name);
+        assert_eq!(author.key(), key, "{:?}", name);
+    }
+    assert_eq!(
+        QueryParams::author_full_name("Johannes van der Waals").to_string(),
+        "au:van_der_Waals_J"
+    );
+}
+
+#[test]
+fn test_client_stats() {
+    let mut stats = ClientStats::default();
+    assert_eq!(stats.rolling_average(), None);
+
+    stats.record(Duration::from_millis(100));
+    stats.record(Duration::from_millis(300));
+    assert_eq!(stats.requests, 2);
+    assert_eq!(stats.last_duration, Some(Duration::from_millis(300)));
+    assert_eq!(stats.rolling_average(), Some(Duration::from_millis(200)));
+
+    for _ in 0..stats::ROLLING_WINDOW {
+        stats.record(Duration::from_millis(50));
+    }
+    assert_eq!(stats.requests, 2 + stats::ROLLING_WINDOW as u64);
+    assert_eq!(stats.rolling_average(), Some(Duration::from_millis(50)));
+
+    let arxiv = ArXiv::from_args(QueryParams::title("llm"));
+    assert_eq!(arxiv.stats().requests, 0);
+}
+
+#[test]
+fn test_compose_queries() {
+    let mut topic = ArXiv::from_args(QueryParams::or(vec![
+        QueryParams::title("ai"),
+        QueryParams::title("llm"),
+    ]));
+    topic.max_results(10);
+    let lab = ArXiv::from_args(QueryParams::author("Doe"));
+
+    let combined = topic.and(&lab);
+    assert_eq!(combined.args.to_string(), "(ti:ai OR ti:llm) AND au:Doe");
+    assert_eq!(combined.max_resutls, Some(10));
+
+    let combined = lab.or(&topic);
+    assert_eq!(combined.args.to_string(), "au:Doe OR (ti:ai OR ti:llm)");
+    assert_eq!(combined.max_resutls, None);
+
+    let combined = topic.and_not(&lab);
+    assert_eq!(combined.args.to_string(), "(ti:ai OR ti:llm) ANDNOT au:Doe");
+}
+
+#[test]
+fn test_scope() {
+    assert_eq!(
+        QueryParams::scope(Scope::MachineLearning).to_string(),
+        "(cat:cs.LG OR cat:cs.AI OR cat:stat.ML)"
+    );
+    assert_eq!(
+        QueryParams::scope(Scope::NLP),
+        QueryParams::subject_category(Category::CsCl)
+    );
+    assert_eq!(
+        Scope::ComputerVision.categories(),
+        vec![Category::CsCv, Category::EessIv]
+    );
+}
+
+#[test]
+fn test_raw_terms_encoded_once() {
+    let args = QueryParams::and(vec![
+        QueryParams::title("attention is all you need"),
+        QueryParams::abstract_text("R&D 100%"),
+        QueryParams::group(vec![QueryParams::or(vec![
+            QueryParams::subject_category(Category::CsAi),
+            QueryParams::subject_category(Category::CsLg),
+        ])]),
+        QueryParams::SubmittedDate(String::from("202412010000"), String::from("202412012359")),
+    ]);
+    assert_eq!(
+        args,
+        QueryParams::And(vec![
+            QueryParams::Title(String::from("attention is all you need")),
+            QueryParams::Abstract(String::from("R&D 100%")),
+            QueryParams::Group(vec![QueryParams::Or(vec![
+                QueryParams::SubjectCategory(String::from("cs.AI")),
+                QueryParams::SubjectCategory(String::from("cs.LG")),
+            ])]),
+            QueryParams::SubmittedDate(String::from("202412010000"), String::from("202412012359")),
+        ])
+    );
+    assert_eq!(
+        args.to_string(),
+        "ti:\"attention is all you need\" AND abs:\"R&D 100%\" AND (cat:cs.AI OR cat:cs.LG) \
+         AND submittedDate:[202412010000 TO 202412012359]"
+    );
+
+    let arxiv = ArXiv::from_args(args);
+    assert_eq!(
+        arxiv.build_query().as_str(),
+        "https://export.arxiv.org/api/query?search_query=\
+         ti:%22attention+is+all+you+need%22+AND+abs:%22R%26D+100%25%22+AND+\
+         %28cat:cs.AI+OR+cat:cs.LG%29+AND+submittedDate:[202412010000+TO+202412012359]"
+    );
+}
+
+#[test]
+fn test_parse_feed() {
+    let arxiv = ArXiv::default();
+    let results = arxiv
+        .parse_xml(include_str!("../fixtures/feed.xml").to_string())
+        .unwrap();
+    assert_eq!(results.total, Some(28374));
+    assert_eq!(results.start, Some(0));
+    assert_eq!(results.per_page, Some(2));
+    assert!(results.has_more());
+    assert!(!results.is_definitely_empty());
+    assert_eq!(results.papers.len(), 2);
+
+    let paper = &results.papers[0];
+    assert_eq!(paper.id, "http://arxiv.org/abs/1706.03762v7");
+    assert_eq!(paper.arxiv_id().unwrap().to_string(), "1706.03762v7");
+    assert_eq!(paper.version(), Some(7));
+    assert_eq!(
+        paper.published,
+        Some(Utc.with_ymd_and_hms(2017, 6, 12, 17, 57, 34).unwrap())
+    );
+    assert_eq!(paper.published_rfc3339(), "2017-06-12T17:57:34Z");
+    assert_eq!(paper.updated_rfc3339(), "2023-08-02T00:41:18Z");
+    assert_eq!(paper.id_without_version(), "1706.03762");
+    assert!(paper.is_revision());
+    assert_eq!(
+        results.papers[1].arxiv_id().unwrap().base(),
+        "hep-ph/0701001"
+    );
+    assert_eq!(paper.title, "Attention Is All You Need");
+    assert_eq!(
+        paper
+            .authors
+            .iter()
+            .map(|author| author.name.as_ref())
+            .collect::<Vec<_>>(),
+        vec!["Ashish Vaswani", "Noam Shazeer", "Niki Parmar"]
+    );
+    assert_eq!(paper.comment.as_deref(), Some("15 pages, 5 figures"));
+    assert_eq!(
+        paper.pdf_url.as_deref(),
+        Some("http://arxiv.org/pdf/1706.03762v7")
+    );
+    assert_eq!(&*paper.primary_category, "cs.CL");
+    assert_eq!(paper.categories, ["cs.CL", "cs.LG"].map(Arc::<str>::from));
+    assert_eq!(paper.primary_archive(), Some(Archive::Cs));
+    assert_eq!(paper.parsed_primary_category(), Category::CsCl);
+    assert_eq!(
+        paper.parsed_categories(),
+        vec![Category::CsCl, Category::CsLg]
+    );
+    assert!(paper.has_category(Category::CsLg));
+    assert!(!paper.has_category(Category::CsCv));
+
+    let paper = &results.papers[1];
+    assert_eq!(paper.doi.as_deref(), Some("10.1103/PhysRevD.76.013009"));
+    assert_eq!(
+        paper.doi_url().as_deref(),
+        Some("https://doi.org/10.1103/PhysRevD.76.013009")
+    );
+    assert_eq!(paper.categories, ["hep-ph", "hep-ex"].map(Arc::<str>::from));
+}
+
+#[test]
+fn test_parse_empty_feed() {
+    let arxiv = ArXiv::default();
+    let results = arxiv
+        .parse_xml(include_str!("../fixtures/empty.xml").to_string())
+        .unwrap();
+    assert!(results.papers.is_empty());
+    assert!(results.is_definitely_empty());
+
+    // a feed without totalResults is not known to be empty
+    let results = SearchResults::default();
+    assert!(!results.is_definitely_empty());
+}
+
+#[test]
+fn test_parse_error_feed() {
+    let arxiv = ArXiv::default();
+    match arxiv.parse_xml(include_str!("../fixtures/error.xml").to_string()) {
+        Err(ArxivError::Api(message)) => {
+            assert_eq!(message, "incorrect id format for 1234.12345")
+        }
+        other => panic!("expected an API error, got {:?}", other),
+    }
+
+    assert!(matches!(
+        arxiv.parse_xml(String::from("<feed><entry></feed>")),
+        Err(ArxivError::Xml(_))
+    ));
+}
+
+#[test]
+fn test_parse_with_whitespace() {
+    let xml = include_str!("../fixtures/rich.xml").to_string();
+    let mut arxiv = ArXiv::from_args(QueryParams::title("lattice paths"));
+    assert_eq!(
+        arxiv.parse_xml(xml.clone()).unwrap().papers[0].title,
+        "Counting Lattice Paths with Restricted Steps"
+    );
+
+    arxiv.whitespace(Whitespace::Raw);
+    assert_eq!(
+        arxiv.parse_xml(xml.clone()).unwrap().papers[0].title,
+        "Counting Lattice Paths\n  with Restricted Steps"
+    );
+    // the setting is kept by combined queries
+    let combined = arxiv.and(&ArXiv::from_args(QueryParams::author("Author")));
+    assert_eq!(combined.whitespace, Some(Whitespace::Raw));
+}
+
+#[test]
+fn test_parse_with_latex() {
+    let xml = include_str!("../fixtures/rich.xml").to_string();
+    let mut arxiv = ArXiv::from_args(QueryParams::title("lattice paths"));
+    assert!(arxiv.parse_xml(xml.clone()).unwrap().papers[0]
+        .abstract_text
+        .contains("a finite set $S$"));
+
+    arxiv.latex(Latex::StripDelimiters);
+    assert!(arxiv.parse_xml(xml).unwrap().papers[0]
+        .abstract_text
+        .contains("a finite set S"));
+}
+
+#[test]
+fn test_paper_versions() {
+    let paper = Paper {
+        id: String::from("http://arxiv.org/abs/2101.00001v1"),
+        published: "2021-01-01T00:00:00Z".parse().ok(),
+        updated: "2021-01-01T00:00:00Z".parse().ok(),
+        ..Default::default()
+    };
+    assert_eq!(paper.version(), Some(1));
+    assert!(!paper.is_revision());
+
+    let unversioned = Paper {
+        id: String::from("2101.00001"),
+        ..Default::default()
+    };
+    assert_eq!(unversioned.version(), None);
+    assert_eq!(unversioned.id_without_version(), paper.id_without_version());
+    assert!(!unversioned.is_revision());
+
+    let foreign = Paper {
+        id: String::from("urn:example:1"),
+        ..Default::default()
+    };
+    assert_eq!(foreign.version(), None);
+    assert_eq!(foreign.id_without_version(), "urn:example:1");
+    assert_eq!(foreign.abs_url(), None);
+    assert_eq!(foreign.pdf_link(), None);
+
+    let linked = Paper {
+        pdf_url: Some(String::from("https://example.org/1.pdf")),
+        ..foreign
+    };
+    assert_eq!(linked.pdf_link().unwrap(), "https://example.org/1.pdf");
+    assert_eq!(
+        paper.html_url().unwrap(),
+        "https://ar5iv.labs.arxiv.org/html/2101.00001"
+    );
+}
+
+#[test]
+fn test_paper_eq_hash_ord() {
+    let paper = |id: &str, published: &str| {
+        return Paper {
+            id: id.to_string(),
+            published: published.parse().ok(),
+            ..Default::default()
+        };
+    };
+    let v1 = paper("http://arxiv.org/abs/2101.00001v1", "2021-01-01T00:00:00Z");
+    let v2 = paper("http://arxiv.org/abs/2101.00001v2", "2021-01-01T00:00:00Z");
+    let older = paper(
+        "http://arxiv.org/abs/hep-ph/0701001v2",
+        "2007-01-01T16:40:21Z",
+    );
+    let undated = paper("urn:example:1", "");
+
+    assert_eq!(v1, paper("2101.00001v1", "2021-01-02T00:00:00Z"));
+    assert_ne!(v1, v2);
+    assert_ne!(undated, paper("urn:example:2", ""));
+
+    let unique = HashSet::from([v1.clone(), v2.clone(), v1.clone(), older.clone()]);
+    assert_eq!(unique.len(), 3);
+
+    let mut papers = vec![v2.clone(), undated.clone(), v1.clone(), older.clone()];
+    papers.sort();
+    assert_eq!(papers, vec![undated, older, v1, v2]);
+}
+
+#[test]
+fn test_paper_ord_by_published() {
+    let paper = |id: &str, published: &str| {
+        return Paper {
+            id: format!("http://arxiv.org/abs/{}", id),
+            published: published.parse().ok(),
+            ..Default::default()
+        };
+    };
+    let newest = paper("2101.00001v1", "2021-01-01T00:00:00Z");
+    let oldest = paper("2201.00001v1", "2019-06-01T00:00:00Z");
+    let middle = paper("1901.00001v1", "2020-01-01T00:00:00Z");
+    let same_day = paper("1901.00002v1", "2020-01-01T00:00:00Z");
+
+    let papers = BTreeSet::from([
+        newest.clone(),
+        same_day.clone(),
+        oldest.clone(),
+        middle.clone(),
+        newest.clone(),
+    ]);
+    assert_eq!(
+        papers.into_iter().collect::<Vec<Paper>>(),
+        vec![oldest, middle, same_day, newest]
+    );
+}
+
+#[test]
+fn test_paper_display() {
+    let paper = Paper {
+        id: String::from("http://arxiv.org/abs/hep-ph/0701001v2"),
+        title: String::from("Neutrino Masses"),
+        authors: vec![Author::new("A. Author"), Author::new("B. Author")],
+        published: "2007-01-01T16:40:21Z".parse().ok(),
+        categories: vec!["hep-ph".into(), "hep-ex".into()],
+        ..Default::default()
+    };
+    assert_eq!(
+        paper.to_string(),
+        "hep-ph/0701001v2 [hep-ph, hep-ex] Neutrino Masses - A. Author, B. Author (2007-01-01)"
+    );
+    assert_eq!(
+        paper.summary(80),
+        "hep-ph/0701001v2 2007-01-01 hep-ph, hep-ex\nNeutrino Masses\nA. Author, B. Author"
+    );
+    assert_eq!(
+        paper.summary(12),
+        "hep-ph/0701…\nNeutrino\nMasses\nA. Author,\nB. Author"
+    );
+
+    let sparse = Paper {
+        id: String::from("urn:example:1"),
+        title: String::from("Untitled"),
+        ..Default::default()
+    };
+    assert_eq!(sparse.to_string(), "urn:example:1 Untitled");
+    assert_eq!(sparse.summary(80), "urn:example:1\nUntitled");
+}
+
+#[test]
+fn test_withdrawn_papers() {
+    let results = parse_atom(include_str!("../fixtures/rich.xml")).unwrap();
+    let withdrawn = &results.papers[1];
+    assert!(withdrawn.is_withdrawn());
+    assert_eq!(
+        withdrawn.withdrawal_note(),
+        Some("This paper has been withdrawn by the author due to an error in Lemma 3")
+    );
+    assert!(!results.papers[0].is_withdrawn());
+    assert!(!results.papers[2].is_withdrawn());
+
+    let paper = |comment: &str| Paper {
+        comment: Some(comment.to_string()),
+        ..Default::default()
+    };
+    assert!(paper("Withdrawn due to a crucial sign error").is_withdrawn());
+    assert!(paper("This submission was withdrawn by arXiv administrators").is_withdrawn());
+    assert!(paper("Paper withdrawn; superseded by 2401.00001").is_withdrawn());
+    assert!(!paper("On withdrawal rates of groundwater").is_withdrawn());
+}
+
+#[test]
+fn test_optional_paper_fields_serde() {
+    // papers saved when missing values were empty strings and comments a list
+    let saved = r#"{
+        "id": "http://arxiv.org/abs/1706.03762v7",
+        "title": "Attention Is All You Need",
+        "authors": ["Ashish Vaswani"],
+        "abstract": "",
+        "published": "2017-06-12T17:57:34Z",
+        "updated": "2023-08-02T00:41:18Z",
+        "doi": "",
+        "comment": ["15 pages, 5 figures"],
+        "journal_ref": "",
+        "pdf_url": "http://arxiv.org/pdf/1706.03762v7",
+        "primary_category": "cs.CL",
+        "categories": ["cs.CL"]
+    }"#;
+    let paper: Paper = serde_json::from_str(saved).unwrap();
+    assert_eq!(paper.doi, None);
+    assert_eq!(paper.journal_ref, None);
+    assert_eq!(paper.comment.as_deref(), Some("15 pages, 5 figures"));
+    assert_eq!(
+        paper.pdf_url.as_deref(),
+        Some("http://arxiv.org/pdf/1706.03762v7")
+    );
+
+    let json = serde_json::to_value(&paper).unwrap();
+    assert!(json.get("doi").is_none());
+    assert_eq!(json["comment"], "15 pages, 5 figures");
+    assert_eq!(json["published"], "2017-06-12T17:57:34Z");
+    let reloaded: Paper = serde_json::from_value(json).unwrap();
+    assert_eq!(reloaded.comment, paper.comment);
+    assert_eq!(reloaded.doi, None);
+    assert_eq!(reloaded.updated, paper.updated);
+
+    // papers saved with an unknown date
+    let undated = saved.replace("2023-08-02T00:41:18Z", "");
+    let paper: Paper = serde_json::from_str(&undated).unwrap();
+    assert_eq!(paper.updated, None);
+    assert_eq!(paper.updated_rfc3339(), "");
+    assert!(serde_json::from_str::<Paper>(&saved.replace("2023-08-02", "August 2")).is_err());
+}
+
+#[cfg(feature = "raw-xml")]
+#[test]
+fn test_parse_with_raw_xml() {
+    let xml = include_str!("../fixtures/rich.xml").to_string();
+    let mut arxiv = ArXiv::from_args(QueryParams::title("lattice paths"));
+    assert_eq!(
+        arxiv.parse_xml(xml.clone()).unwrap().papers[0].raw_xml,
+        None
+    );
+
+    arxiv.retain_raw_xml(true).whitespace(Whitespace::Raw);
+    let results = arxiv.parse_xml(xml).unwrap();
+    assert_eq!(results.total, Some(3));
+    let paper = &results.papers[0];
+    assert_eq!(
+        paper.title,
+        "Counting Lattice Paths\n  with Restricted Steps"
+    );
+    assert!(paper
+        .raw_xml
+        .as_deref()
+        .unwrap()
+        .contains("<title>Counting Lattice Paths\n  with Restricted Steps</title>"));
+}
+
+#[test]
+fn test_dry_run() {
+    let mut arxiv = ArXiv::from_args(QueryParams::title("llm"));
+    let dry_run = arxiv.dry_run();
+    assert_eq!(dry_run.url, arxiv.build_query());
+    assert_eq!(dry_run.max_results, DEFAULT_MAX_RESULTS);
+    assert_eq!(dry_run.estimated_pages, 1);
+
+    arxiv.max_results(MAX_PAGE_SIZE);
+    assert_eq!(arxiv.dry_run().estimated_pages, 1);
+    arxiv.max_results(MAX_PAGE_SIZE + 1);
+    assert_eq!(arxiv.dry_run().estimated_pages, 2);
+    assert_eq!(
+        arxiv.dry_run().url.query(),
+        Some("search_query=ti:llm&max_results=2001")
+    );
+}
+
+#[test]
+fn test_session_shares_state() {
+    let session = ArxivSession::new();
+    let first = session.query(QueryParams::title("llm"));
+    let second = session.query(QueryParams::title("agent"));
+    let combined = first.and(&second);
+
+    session.record(Duration::from_millis(100));
+    assert_eq!(first.stats().requests, 1);
+    assert_eq!(second.stats().requests, 1);
+    assert_eq!(combined.stats().requests, 1);
+
+    let standalone = ArXiv::from_args(QueryParams::title("llm"));
+    assert_eq!(standalone.stats().requests, 0);
+
+    let injected = ArxivSession::with_client(reqwest::Client::new());
+    let query = injected.query(QueryParams::title("llm"));
+    injected.record(Duration::from_millis(100));
+    assert_eq!(query.stats().requests, 1);
+}
+
+#[test]
+fn test_explain() {
+    let args = QueryParams::title("llm")
+        .exclude_category(Category::CsCv)
+        .exclude_category(Category::CsRo);
+    let args = QueryParams::and(vec![
+        args,
+        QueryParams::SubmittedDate(String::from("202412010000"), String::from("202412012359")),
+    ]);
+    assert_eq!(
+        args.explain(),
+        "AND
+  ANDNOT
+    GROUP
+      ANDNOT
+        title: \"llm\"
+        category: \"cs.CV\"
+    category: \"cs.RO\"
+  submitted date: 202412010000 to 202412012359
+"
+    );
+}
+
+#[test]
+fn test_nested_operators_grouped() {
+    let args = QueryParams::and_not(vec![
+        QueryParams::and(vec![
+            QueryParams::or(vec![QueryParams::title("ai"), QueryParams::title("llm")]),
+            QueryParams::subject_category(Category::CsCl),
+        ]),
+        QueryParams::group(vec![QueryParams::or(vec![
+            QueryParams::subject_category(Category::CsCv),
+            QueryParams::subject_category(Category::CsRo),
+        ])]),
+    ]);
+    assert_eq!(
+        args.to_string(),
+        "((ti:ai OR ti:llm) AND cat:cs.CL) ANDNOT (cat:cs.CV OR cat:cs.RO)"
+    );
+
+    let arxiv = ArXiv::from_args(args);
+    assert_eq!(
+        arxiv.build_query().as_str(),
+        "https://export.arxiv.org/api/query?search_query=\
+         %28%28ti:ai+OR+ti:llm%29+AND+cat:cs.CL%29+ANDNOT+%28cat:cs.CV+OR+cat:cs.RO%29"
+    );
+}
+
+#[test]
+fn test_submitted_date_validation() {
+    assert_eq!(
+        QueryParams::submitted_date("20241201", "20241231").unwrap(),
+        QueryParams::SubmittedDate(String::from("202412010000"), String::from("202412312359"))
+    );
+    assert_eq!(
+        QueryParams::submitted_date("202412010930", "202412011800").unwrap(),
+        QueryParams::SubmittedDate(String::from("202412010930"), String::from("202412011800"))
+    );
+
+    for invalid in [
+        "2024120",
+        "2024-12-01",
+        "20241301",
+        "202412012400",
+        "2024120100000",
+    ] {
+        assert!(matches!(
+            QueryParams::submitted_date(invalid, "20241231"),
+            Err(ArxivError::InvalidDate(date)) if date == invalid
+        ));
+    }
+}
+
+#[test]
+fn test_paper_category_aliases() {
+    let paper = Paper {
+        primary_category: "cs.NA".into(),
+        categories: vec!["cs.NA".into(), "chao-dyn".into()],
+        ..Default::default()
+    };
+    assert_eq!(
+        paper.parsed_categories(),
+        vec![Category::CsNa, Category::Other(String::from("chao-dyn"))]
+    );
+    assert!(paper.has_category(Category::MathNa));
+    assert!(paper.has_category(Category::NlinCd));
+    assert!(paper.is_primarily(Category::MathNa));
+    assert!(paper.is_cross_listed());
+    assert_eq!(
+        paper.secondary_categories(),
+        vec![Category::Other(String::from("chao-dyn"))]
+    );
+    assert_eq!(paper.cross_list_categories(), vec!["chao-dyn"]);
+
+    let paper = Paper {
+        primary_category: "hep-th".into(),
+        categories: vec!["hep-th".into()],
+        ..Default::default()
+    };
+    assert!(!paper.is_cross_listed());
+    assert!(paper.cross_list_categories().is_empty());
+}
+
+#[test]
+fn test_enum_serde_codes() {
+    assert_eq!(
+        serde_json::to_string(&SortBy::LastUpdatedDate).unwrap(),
+        r#""lastUpdatedDate""#
+    );
+    assert_eq!(
+        serde_json::from_str::<SortBy>(r#""submittedDate""#).unwrap(),
+        SortBy::SubmittedDate
+    );
+    assert_eq!(
+        serde_json::to_string(&SortOrder::Ascending).unwrap(),
+        r#""ascending""#
+    );
+
+    let categories = vec![
+        Category::CsCl,
+        Category::CondMatStrEl,
+        Category::Other(String::from("cmp-lg")),
+    ];
+    let payload = serde_json::to_string(&categories).unwrap();
+    assert_eq!(payload, r#"["cs.CL","cond-mat.str-el","cmp-lg"]"#);
+    assert_eq!(
+        serde_json::from_str::<Vec<Category>>(&payload).unwrap(),
+        categories
+    );
+}
+
+#[test]
+fn test_progress_hook_kept() {
+    let mut arxiv = ArXiv::from_args(QueryParams::title("llm"));
+    arxiv.on_progress(|_| ());
+    let combined = arxiv.and(&ArXiv::from_args(QueryParams::author("doe")));
+    assert!(combined.progress.is_some());
+    assert!(!serde_json::to_string(&combined)
+        .unwrap()
+        .contains("progress"));
+}
+
+#[test]
+fn test_base_url() {
+    let mut arxiv = ArXiv::from_args(QueryParams::title("llm"));
+    arxiv.with_base_url(Url::parse("https://mirror.example.org/api/query").unwrap());
+    let combined = arxiv.and(&ArXiv::from_args(QueryParams::author("doe")));
+    assert_eq!(
+        combined.build_query().as_str(),
+        "https://mirror.example.org/api/query?search_query=ti:llm+AND+au:doe"
+    );
+
+    let payload = serde_json::to_string(&arxiv).unwrap();
+    assert!(payload.contains(r#""base_url":"https://mirror.example.org/api/query""#));
+    let loaded: ArXiv = serde_json::from_str(&payload).unwrap();
+    assert_eq!(loaded.base_url, arxiv.base_url);
+}
+
+#[test]
+fn test_parse_atom_from_file() {
+    let path = std::env::temp_dir().join("arxiv_tools_test_saved_feed.xml");
+    std::fs::write(&path, include_str!("../fixtures/feed.xml")).unwrap();
+    let results = parse_atom(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(results.papers.len(), 2);
+    assert_eq!(results.total, Some(28374));
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,211 @@
+//! # Transport
+//! The HTTP layer behind every request, abstracted as a [`Transport`] so that it can be replaced,
+//! e.g. to add authentication, route requests differently or stub the network out in tests.
+//!
+//! ```rust
+//! # use arxiv_tools::{ArxivError, ArxivSession, QueryParams};
+//! # use arxiv_tools::transport::{async_trait, Transport};
+//! # use bytes::Bytes;
+//! # use url::Url;
+//! /// Answers every request with an empty feed.
+//! #[derive(Debug)]
+//! struct Offline;
+//!
+//! #[async_trait]
+//! impl Transport for Offline {
+//!     async fn get(&self, _url: &Url) -> Result<Bytes, ArxivError> {
+//!         return Ok(Bytes::from_static(b"<feed></feed>"));
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let session = ArxivSession::with_transport(Offline);
+//! let results = session.query(QueryParams::title("llm")).try_query().await.unwrap();
+//! assert!(results.papers.is_empty());
+//! # }
+//! ```
+use crate::middleware::Request;
+use crate::{parse_atom, ArxivError, ClientConfig};
+pub use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::{self, BoxStream, TryStreamExt};
+use reqwest::header::HeaderMap;
+use std::fmt::Debug;
+use std::sync::OnceLock;
+use url::Url;
+
+#[async_trait]
+pub trait Transport: Debug + Send + Sync {
+    /// Fetch `url` and return the response body.
+    ///
+    /// Non-success responses are errors: [`ArxivError::Api`] if the body reports an API error,
+    /// [`ArxivError::Status`] otherwise.
+    async fn get(&self, url: &Url) -> Result<Bytes, ArxivError>;
+
+    /// Send `request` and return the response, with errors as [`Transport::get`].
+    ///
+    /// `304 Not Modified` is not an error, so that conditional requests can be answered from a previous response.
+    /// The default implementation ignores the headers of `request` and returns no response headers; transports
+    /// that can send and receive them override it.
+    async fn send(&self, request: &Request) -> Result<Reply, ArxivError> {
+        return Ok(Reply::new(self.get(&request.url).await?));
+    }
+
+    /// Send `request` and return the response body as a stream of chunks, as they arrive.
+    ///
+    /// Errors are returned as by [`Transport::get`] before the body is streamed. The default implementation reads
+    /// the whole body with [`Transport::send`] and returns it as a single chunk; transports that can stream
+    /// responses override it.
+    async fn get_stream(&self, request: &Request) -> Result<BodyStream, ArxivError> {
+        let reply = self.send(request).await?;
+        if reply.status == 304 {
+            return Err(ArxivError::Status(reply.status));
+        }
+        return Ok(Box::pin(stream::iter(Some(Ok(reply.body)))));
+    }
+}
+
+/// A response body streamed by [`Transport::get_stream`].
+pub type BodyStream = BoxStream<'static, Result<Bytes, ArxivError>>;
+
+/// A response of [`Transport::send`].
+#[derive(Clone, Debug)]
+pub struct Reply {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl Reply {
+    /// A `200 OK` response with `body` and no headers.
+    pub fn new(body: Bytes) -> Self {
+        return Reply {
+            status: 200,
+            headers: HeaderMap::new(),
+            body,
+        };
+    }
+}
+
+/// The default [`Transport`], sending requests with a [`reqwest::Client`].
+///
+/// The default client is built from [`ClientConfig::default`].
+#[derive(Clone, Debug)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl Default for ReqwestTransport {
+    fn default() -> Self {
+        let client = ClientConfig::default()
+            .build()
+            .expect("the default client configuration is valid");
+        return ReqwestTransport::new(client);
+    }
+}
+
+impl ReqwestTransport {
+    /// The transport with the default client, built on first use and shared by the whole process.
+    ///
+    /// Fails with [`ArxivError::Client`], on every call, if the default client could not be built.
+    pub(crate) fn shared() -> Result<&'static ReqwestTransport, ArxivError> {
+        static SHARED: OnceLock<Result<ReqwestTransport, String>> = OnceLock::new();
+        return SHARED
+            .get_or_init(|| {
+                return ClientConfig::default()
+                    .build()
+                    .map(ReqwestTransport::new)
+                    .map_err(|e| e.to_string());
+            })
+            .as_ref()
+            .map_err(|e| ArxivError::Client(e.clone()));
+    }
+
+    pub fn new(client: reqwest::Client) -> Self {
+        return ReqwestTransport { client };
+    }
+
+    pub fn client(&self) -> &reqwest::Client {
+        return &self.client;
+    }
+}
+
+/// The transport of the default session: sends requests through [`ReqwestTransport::shared`], so that default
+/// sessions don't build a client each and report a client that can't be built as an error of their requests.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct SharedTransport;
+
+#[async_trait]
+impl Transport for SharedTransport {
+    async fn get(&self, url: &Url) -> Result<Bytes, ArxivError> {
+        return ReqwestTransport::shared()?.get(url).await;
+    }
+
+    async fn send(&self, request: &Request) -> Result<Reply, ArxivError> {
+        return ReqwestTransport::shared()?.send(request).await;
+    }
+
+    async fn get_stream(&self, request: &Request) -> Result<BodyStream, ArxivError> {
+        return ReqwestTransport::shared()?.get_stream(request).await;
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn get(&self, url: &Url) -> Result<Bytes, ArxivError> {
+        let reply = self.send(&Request::new(url.clone())).await?;
+        if reply.status == 304 {
+            return Err(ArxivError::Status(reply.status));
+        }
+        return Ok(reply.body);
+    }
+
+    async fn send(&self, request: &Request) -> Result<Reply, ArxivError> {
+        let response = self.request(request).await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+        if status.is_success() || status == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(Reply {
+                status: status.as_u16(),
+                headers,
+                body,
+            });
+        }
+        return Err(status_error(status.as_u16(), &body));
+    }
+
+    async fn get_stream(&self, request: &Request) -> Result<BodyStream, ArxivError> {
+        let response = self.request(request).await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.bytes().await?;
+            return Err(status_error(status.as_u16(), &body));
+        }
+        return Ok(Box::pin(response.bytes_stream().map_err(ArxivError::from)));
+    }
+}
+
+impl ReqwestTransport {
+    async fn request(&self, request: &Request) -> Result<reqwest::Response, ArxivError> {
+        let response = self
+            .client
+            .get(request.url.clone())
+            .headers(request.headers.clone())
+            .send()
+            .await?;
+        return Ok(response);
+    }
+}
+
+/// The error of a non-success response: the API error its body reports, if any.
+fn status_error(status: u16, body: &[u8]) -> ArxivError {
+    match parse_atom(&String::from_utf8_lossy(body)) {
+        Err(ArxivError::Api(message)) => return ArxivError::Api(message),
+        _ => return ArxivError::Status(status),
+    }
+}
+
+#[cfg(test)]
+mod tests;
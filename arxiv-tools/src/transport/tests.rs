@@ -0,0 +1,358 @@
+use super::*;
+use crate::{ArxivSession, QueryParams};
+use std::sync::Mutex;
+
+/// Serves a fixed body and records the requested URLs.
+#[derive(Debug, Default)]
+struct Stub {
+    body: &'static str,
+    requests: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl Transport for Stub {
+    async fn get(&self, url: &Url) -> Result<Bytes, ArxivError> {
+        self.requests.lock().unwrap().push(url.to_string());
+        return Ok(Bytes::from_static(self.body.as_bytes()));
+    }
+}
+
+#[tokio::test]
+async fn test_stub_transport() {
+    let session = ArxivSession::with_transport(Stub {
+        body: include_str!("../../fixtures/feed.xml"),
+        ..Default::default()
+    });
+    let mut arxiv = session.query(QueryParams::title("attention"));
+    let results = arxiv.try_query().await.unwrap();
+    assert_eq!(results.papers.len(), 2);
+    assert_eq!(results.papers[0].title, "Attention Is All You Need");
+    assert_eq!(session.stats().requests, 1);
+}
+
+#[tokio::test]
+async fn test_stub_transport_error() {
+    let session = ArxivSession::with_transport(Stub {
+        body: include_str!("../../fixtures/error.xml"),
+        ..Default::default()
+    });
+    let mut arxiv = session.query(QueryParams::id("1234.12345"));
+    assert!(matches!(arxiv.try_query().await, Err(ArxivError::Api(_))));
+}
+
+#[tokio::test]
+async fn test_requests_wait_for_rate_limiter() {
+    let session = ArxivSession::with_transport(Stub {
+        body: include_str!("../../fixtures/feed.xml"),
+        ..Default::default()
+    });
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::from_millis(50));
+    let mut arxiv = session.query(QueryParams::title("attention"));
+    let started = std::time::Instant::now();
+    for _ in 0..3 {
+        arxiv.try_query().await.unwrap();
+    }
+    assert!(started.elapsed() >= std::time::Duration::from_millis(100));
+    assert_eq!(session.stats().requests, 3);
+}
+
+/// Fails with `status` until `failures` requests were made, then serves `body`.
+#[derive(Debug)]
+struct Flaky {
+    status: u16,
+    failures: usize,
+    body: &'static str,
+    requests: Mutex<usize>,
+}
+
+#[async_trait]
+impl Transport for Flaky {
+    async fn get(&self, _url: &Url) -> Result<Bytes, ArxivError> {
+        let mut requests = self.requests.lock().unwrap();
+        *requests += 1;
+        if *requests <= self.failures {
+            return Err(ArxivError::Status(self.status));
+        }
+        return Ok(Bytes::from_static(self.body.as_bytes()));
+    }
+}
+
+#[tokio::test]
+async fn test_transient_failures_are_retried() {
+    let session = ArxivSession::with_transport(Flaky {
+        status: 503,
+        failures: 2,
+        body: include_str!("../../fixtures/feed.xml"),
+        requests: Mutex::new(0),
+    });
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::ZERO);
+    session.set_retry_policy(crate::RetryPolicy {
+        base_delay: std::time::Duration::from_millis(1),
+        ..Default::default()
+    });
+    let mut arxiv = session.query(QueryParams::title("attention"));
+    assert_eq!(arxiv.try_query().await.unwrap().papers.len(), 2);
+    assert_eq!(session.stats().requests, 3);
+    assert_eq!(session.stats().retries, 2);
+}
+
+#[tokio::test]
+async fn test_permanent_failures_are_not_retried() {
+    let session = ArxivSession::with_transport(Flaky {
+        status: 404,
+        failures: 1,
+        body: include_str!("../../fixtures/feed.xml"),
+        requests: Mutex::new(0),
+    });
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::ZERO);
+    let mut arxiv = session.query(QueryParams::title("attention"));
+    assert!(matches!(
+        arxiv.try_query().await,
+        Err(ArxivError::Status(404))
+    ));
+    assert_eq!(session.stats().retries, 0);
+}
+
+#[tokio::test]
+async fn test_stats_count_papers_and_errors() {
+    let session = ArxivSession::with_transport(Stub {
+        body: include_str!("../../fixtures/feed.xml"),
+        ..Default::default()
+    });
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::ZERO);
+    session
+        .query(QueryParams::title("attention"))
+        .try_query()
+        .await
+        .unwrap();
+    session
+        .query(QueryParams::title("attention"))
+        .try_query()
+        .await
+        .unwrap();
+
+    let failing = ArxivSession::with_transport(Stub {
+        body: include_str!("../../fixtures/error.xml"),
+        ..Default::default()
+    });
+    let _ = failing
+        .query(QueryParams::id("1234.12345"))
+        .try_query()
+        .await;
+
+    assert_eq!(session.stats().papers, 4);
+    assert_eq!(session.stats().error_count(), 0);
+    assert_eq!(failing.stats().papers, 0);
+    assert_eq!(failing.stats().errors.get("api"), Some(&1));
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_short_circuits() {
+    let session = ArxivSession::with_transport(Flaky {
+        status: 503,
+        failures: usize::MAX,
+        body: include_str!("../../fixtures/feed.xml"),
+        requests: Mutex::new(0),
+    });
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::ZERO);
+    session.set_retry_policy(crate::RetryPolicy {
+        max_attempts: 5,
+        base_delay: std::time::Duration::from_millis(1),
+        ..Default::default()
+    });
+    session.set_circuit_breaker(crate::CircuitBreaker::new(
+        2,
+        std::time::Duration::from_secs(60),
+    ));
+    let mut arxiv = session.query(QueryParams::title("attention"));
+    assert!(matches!(
+        arxiv.try_query().await,
+        Err(ArxivError::CircuitOpen { .. })
+    ));
+    assert!(matches!(
+        arxiv.try_query().await,
+        Err(ArxivError::CircuitOpen { .. })
+    ));
+    assert_eq!(session.stats().requests, 2);
+    assert_eq!(session.stats().errors.get("circuit_open"), Some(&2));
+}
+
+/// Fails the first request, never answers the second and serves the feed afterwards.
+#[derive(Debug, Default)]
+struct Hanging {
+    requests: Mutex<usize>,
+}
+
+#[async_trait]
+impl Transport for Hanging {
+    async fn get(&self, _url: &Url) -> Result<Bytes, ArxivError> {
+        let request = {
+            let mut requests = self.requests.lock().unwrap();
+            *requests += 1;
+            *requests
+        };
+        match request {
+            1 => return Err(ArxivError::Status(503)),
+            2 => return futures::future::pending().await,
+            _ => {
+                return Ok(Bytes::from_static(
+                    include_str!("../../fixtures/feed.xml").as_bytes(),
+                ))
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_dropped_trial() {
+    let session = ArxivSession::with_transport(Hanging::default());
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::ZERO);
+    session.set_retry_policy(crate::RetryPolicy::never());
+    session.set_circuit_breaker(crate::CircuitBreaker::new(1, std::time::Duration::ZERO));
+    let mut arxiv = session.query(QueryParams::title("attention"));
+    assert!(arxiv.try_query().await.is_err());
+
+    // the trial request never completes and is cancelled
+    let trial = tokio::time::timeout(std::time::Duration::from_millis(50), arxiv.try_query()).await;
+    assert!(trial.is_err());
+
+    assert!(arxiv.try_query().await.is_ok());
+    assert!(!session.circuit_breaker().unwrap().is_open());
+}
+
+/// Answers `304 Not Modified` to requests carrying the `ETag` it hands out.
+#[derive(Debug, Default)]
+struct Revalidating {
+    requests: Mutex<Vec<Option<String>>>,
+}
+
+#[async_trait]
+impl Transport for Revalidating {
+    async fn get(&self, _url: &Url) -> Result<Bytes, ArxivError> {
+        unreachable!("requests are sent with `send`");
+    }
+
+    async fn send(&self, request: &crate::middleware::Request) -> Result<Reply, ArxivError> {
+        let if_none_match = request
+            .headers
+            .get("if-none-match")
+            .map(|value| value.to_str().unwrap().to_string());
+        self.requests.lock().unwrap().push(if_none_match.clone());
+        if if_none_match.as_deref() == Some("\"v1\"") {
+            return Ok(Reply {
+                status: 304,
+                headers: Default::default(),
+                body: Bytes::new(),
+            });
+        }
+        let mut reply = Reply::new(Bytes::from_static(
+            include_str!("../../fixtures/feed.xml").as_bytes(),
+        ));
+        reply
+            .headers
+            .insert("etag", reqwest::header::HeaderValue::from_static("\"v1\""));
+        return Ok(reply);
+    }
+}
+
+#[tokio::test]
+async fn test_conditional_requests() {
+    let session = ArxivSession::with_transport(Revalidating::default());
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::ZERO);
+    session.set_conditional_requests(true);
+    let mut arxiv = session.query(QueryParams::title("attention"));
+    assert_eq!(arxiv.try_query().await.unwrap().papers.len(), 2);
+    assert_eq!(arxiv.try_query().await.unwrap().papers.len(), 2);
+    assert_eq!(session.stats().requests, 2);
+    assert_eq!(session.stats().not_modified, 1);
+
+    session.set_conditional_requests(false);
+    assert_eq!(arxiv.try_query().await.unwrap().papers.len(), 2);
+    assert_eq!(session.stats().not_modified, 1);
+}
+
+#[tokio::test]
+async fn test_cached_responses() {
+    let session = ArxivSession::with_transport(Stub {
+        body: include_str!("../../fixtures/feed.xml"),
+        ..Default::default()
+    });
+    session.set_cache(
+        crate::cache::MemoryCache::new(),
+        std::time::Duration::from_secs(60),
+    );
+    let mut arxiv = session.query(QueryParams::title("attention"));
+    assert_eq!(arxiv.try_query().await.unwrap().papers.len(), 2);
+    assert_eq!(arxiv.try_query().await.unwrap().papers.len(), 2);
+    assert_eq!(session.stats().requests, 1);
+    assert_eq!(session.stats().cache_hits, 1);
+}
+
+/// Streams a fixed body in small chunks.
+#[derive(Debug)]
+struct Chunked(&'static str);
+
+#[async_trait]
+impl Transport for Chunked {
+    async fn get(&self, _url: &Url) -> Result<Bytes, ArxivError> {
+        return Ok(Bytes::from_static(self.0.as_bytes()));
+    }
+
+    async fn get_stream(&self, _request: &Request) -> Result<BodyStream, ArxivError> {
+        let chunks = self
+            .0
+            .as_bytes()
+            .chunks(100)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect::<Vec<_>>();
+        return Ok(Box::pin(stream::iter(chunks)));
+    }
+}
+
+#[tokio::test]
+async fn test_query_stream() {
+    let session = ArxivSession::with_transport(Chunked(include_str!("../../fixtures/feed.xml")));
+    let arxiv = session.query(QueryParams::title("attention"));
+    let papers: Vec<_> = arxiv.query_stream().try_collect().await.unwrap();
+    assert_eq!(papers.len(), 2);
+    assert_eq!(papers[0].title, "Attention Is All You Need");
+    assert_eq!(session.stats().requests, 1);
+    assert_eq!(session.stats().papers, 2);
+
+    // the default `get_stream` returns the body of `get` as one chunk
+    let session = ArxivSession::with_transport(Stub {
+        body: include_str!("../../fixtures/error.xml"),
+        ..Default::default()
+    });
+    let arxiv = session.query(QueryParams::id("1234.12345"));
+    let papers: Result<Vec<_>, _> = arxiv.query_stream().try_collect().await;
+    assert!(matches!(papers, Err(ArxivError::Api(_))));
+    assert_eq!(session.stats().error_count(), 1);
+}
+
+#[test]
+fn test_default_sessions_share_a_client() {
+    let first = ReqwestTransport::shared().unwrap();
+    let second = ReqwestTransport::shared().unwrap();
+    assert!(std::ptr::eq(first, second));
+
+    let arxiv = crate::ArXiv::from_args(QueryParams::title("llm"));
+    assert_eq!(
+        format!("{:?}", arxiv.session().transport()),
+        "SharedTransport"
+    );
+}
@@ -0,0 +1,42 @@
+//! # Versions
+//! The metadata of every version of a paper, e.g. to track how its title, abstract or authors changed:
+//! ```rust,no_run
+//! # use arxiv_tools::ArXiv;
+//! # #[tokio::main]
+//! # async fn main() {
+//! let versions = ArXiv::versions("1706.03762").await.unwrap();
+//! for paper in versions {
+//!     println!("v{} {:?} {}", paper.version().unwrap(), paper.updated, paper.title);
+//! }
+//! # }
+//! ```
+//! Versions are requested one by one by `id_list`, starting with `v1`, until the API has no paper for the next one, or
+//! reports it as an error.
+use crate::{ArxivError, ArxivId, ArxivSession, Paper, QueryParams};
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) async fn versions_in(
+    session: &ArxivSession,
+    id: &str,
+) -> Result<Vec<Paper>, ArxivError> {
+    let id = id.parse::<ArxivId>()?;
+    let mut versions = Vec::new();
+    for version in 1.. {
+        let mut query = session.query(QueryParams::default());
+        query.id_list(&[&format!("{}v{}", id.base(), version)]);
+        let paper = match query.try_query().await {
+            Ok(page) => page.papers.into_iter().next(),
+            // the API may report a version after the last one as an error instead of an empty feed
+            Err(ArxivError::Api(_)) if !versions.is_empty() => None,
+            Err(e) => return Err(e),
+        };
+        // a paper of another version means the API ignored the version, which would never end
+        match paper {
+            Some(paper) if paper.version() == Some(version) => versions.push(paper),
+            _ => break,
+        }
+    }
+    return Ok(versions);
+}
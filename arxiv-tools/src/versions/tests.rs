@@ -0,0 +1,128 @@
+use super::*;
+use crate::transport::Transport;
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+/// Serves the versions up to `latest` of a paper, and an empty feed for later ones, or an API error if
+/// `api_error` is set.
+#[derive(Debug)]
+struct Versions {
+    latest: u32,
+    api_error: bool,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Transport for Versions {
+    async fn get(&self, url: &Url) -> Result<Bytes, ArxivError> {
+        let query = url.query().unwrap().to_string();
+        self.requests.lock().unwrap().push(query.clone());
+        let version = query
+            .rsplit_once('v')
+            .and_then(|(_, version)| version.parse::<u32>().ok())
+            .unwrap();
+        let entry = match version <= self.latest {
+            true => format!(
+                "<entry><id>http://arxiv.org/abs/2301.00001v{0}</id><title>Title v{0}</title>\
+                 <summary>Abstract</summary><published>2023-01-01T00:00:00Z</published>\
+                 <updated>2023-0{0}-01T00:00:00Z</updated></entry>",
+                version
+            ),
+            false if self.api_error => format!(
+                "<entry><id>http://arxiv.org/api/errors#incorrect_id_format_for_2301.00001v{}</id>\
+                 <title>Error</title><summary>incorrect id format for 2301.00001v{}</summary></entry>",
+                version, version
+            ),
+            false => String::new(),
+        };
+        let feed = format!(
+            "<feed xmlns=\"http://www.w3.org/2005/Atom\">{}</feed>",
+            entry
+        );
+        return Ok(Bytes::from(feed));
+    }
+}
+
+#[tokio::test]
+async fn test_versions() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let session = ArxivSession::with_transport(Versions {
+        latest: 3,
+        api_error: false,
+        requests: requests.clone(),
+    });
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::from_millis(1));
+    let versions = session.versions("arXiv:2301.00001v2").await.unwrap();
+    assert_eq!(
+        versions
+            .iter()
+            .map(|paper| paper.title.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["Title v1", "Title v2", "Title v3"]
+    );
+    assert!(versions
+        .windows(2)
+        .all(|pair| pair[0].updated < pair[1].updated));
+    assert_eq!(session.stats().requests, 4);
+    assert_eq!(
+        requests.lock().unwrap().as_slice(),
+        [
+            "id_list=2301.00001v1",
+            "id_list=2301.00001v2",
+            "id_list=2301.00001v3",
+            "id_list=2301.00001v4"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_versions_end_with_api_error() {
+    let session = ArxivSession::with_transport(Versions {
+        latest: 2,
+        api_error: true,
+        requests: Arc::default(),
+    });
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::ZERO);
+    let versions = session.versions("2301.00001").await.unwrap();
+    assert_eq!(
+        versions
+            .iter()
+            .map(|paper| paper.version())
+            .collect::<Vec<Option<u32>>>(),
+        vec![Some(1), Some(2)]
+    );
+
+    // without any version, the error is the answer
+    let session = ArxivSession::with_transport(Versions {
+        latest: 0,
+        api_error: true,
+        requests: Arc::default(),
+    });
+    session
+        .rate_limiter()
+        .set_interval(std::time::Duration::ZERO);
+    assert!(matches!(
+        session.versions("2301.00001").await,
+        Err(ArxivError::Api(message)) if message == "incorrect id format for 2301.00001v1"
+    ));
+}
+
+#[tokio::test]
+async fn test_versions_invalid_id() {
+    let session = ArxivSession::with_transport(Versions {
+        latest: 1,
+        api_error: false,
+        requests: Arc::default(),
+    });
+    assert!(matches!(
+        session.versions("llm").await,
+        Err(ArxivError::InvalidId(_))
+    ));
+    assert_eq!(session.stats().requests, 0);
+}